@@ -1,9 +1,23 @@
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
 pub(crate) mod neon;
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
 pub type GF2_128 = neon::NeonGF2_128;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(target_arch = "x86_64", not(feature = "force-scalar")))]
 mod avx;
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(target_arch = "x86_64", not(feature = "force-scalar")))]
 pub type GF2_128 = avx::AVXGF2_128;
+
+// Fallback, no SIMD backend applies or the scalar one is forced.
+// Always built for tests, as the reference for the SIMD backends.
+#[cfg(any(
+    test,
+    feature = "force-scalar",
+    not(any(target_arch = "aarch64", target_arch = "x86_64"))
+))]
+pub(crate) mod scalar;
+#[cfg(any(
+    feature = "force-scalar",
+    not(any(target_arch = "aarch64", target_arch = "x86_64"))
+))]
+pub type GF2_128 = scalar::ScalarGF2_128;
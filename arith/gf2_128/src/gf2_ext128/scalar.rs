@@ -0,0 +1,236 @@
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use arith::{field_common, ExtensionField, Field, FieldSerde, FieldSerdeResult};
+
+use gf2::GF2;
+
+/// Portable GF2_128, used when no SIMD backend applies.
+/// Bit i of `v` is the coefficient of x^i, matching the layout of the SIMD backends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScalarGF2_128 {
+    pub v: u128,
+}
+
+field_common!(ScalarGF2_128);
+
+impl FieldSerde for ScalarGF2_128 {
+    const SERIALIZED_SIZE: usize = 16;
+
+    #[inline(always)]
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        writer.write_all(self.v.to_le_bytes().as_ref())?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn deserialize_from<R: std::io::Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut u = [0u8; Self::SERIALIZED_SIZE];
+        reader.read_exact(&mut u)?;
+        Ok(ScalarGF2_128 {
+            v: u128::from_le_bytes(u),
+        })
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: std::io::Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut u = [0u8; 32];
+        reader.read_exact(&mut u)?;
+        Ok(ScalarGF2_128 {
+            v: u128::from_le_bytes(u[..16].try_into().unwrap()),
+        })
+    }
+}
+
+impl Field for ScalarGF2_128 {
+    const NAME: &'static str = "Galios Field 2^128";
+
+    const SIZE: usize = 128 / 8;
+
+    const FIELD_SIZE: usize = 128; // in bits
+
+    const ZERO: Self = ScalarGF2_128 { v: 0 };
+
+    const ONE: Self = ScalarGF2_128 { v: 1 };
+
+    const INV_2: Self = ScalarGF2_128 { v: 0 }; // should not be used
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl rand::RngCore) -> Self {
+        let mut u = [0u8; 16];
+        rng.fill_bytes(&mut u);
+        ScalarGF2_128 {
+            v: u128::from_le_bytes(u),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl rand::RngCore) -> Self {
+        ScalarGF2_128 {
+            v: (rng.next_u32() % 2) as u128,
+        }
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v == 0
+    }
+
+    #[inline(always)]
+    fn exp(&self, exponent: u128) -> Self {
+        let mut e = exponent;
+        let mut res = Self::one();
+        let mut t = *self;
+        while e > 0 {
+            if e & 1 == 1 {
+                res *= t;
+            }
+            t = t * t;
+            e >>= 1;
+        }
+        res
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let p_m2 = !(0u128) - 1;
+        Some(Self::exp(self, p_m2))
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        self * self
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("u32 for GF128 doesn't make sense")
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        ScalarGF2_128 {
+            v: u128::from_le_bytes(bytes[..16].try_into().unwrap()),
+        }
+    }
+}
+
+impl ExtensionField for ScalarGF2_128 {
+    const DEGREE: usize = 128;
+
+    const W: u32 = 0x87;
+
+    const X: Self = ScalarGF2_128 { v: 2 };
+
+    type BaseField = GF2;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        if base.v == 0 {
+            Self::zero()
+        } else {
+            *self
+        }
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        ScalarGF2_128 {
+            v: self.v ^ base.v as u128,
+        }
+    }
+
+    #[inline]
+    fn mul_by_x(&self) -> Self {
+        // shift left by 1 bit, and reduce x^128 to x^7 + x^2 + x + 1 if it overflows
+        let reduction = (self.v >> 127) * Self::W as u128;
+        ScalarGF2_128 {
+            v: (self.v << 1) ^ reduction,
+        }
+    }
+}
+
+impl From<GF2> for ScalarGF2_128 {
+    #[inline(always)]
+    fn from(v: GF2) -> Self {
+        ScalarGF2_128 { v: v.v as u128 }
+    }
+}
+
+/// Carry-less multiplication of two 64-bit polynomials
+#[inline]
+fn clmul(a: u64, b: u64) -> u128 {
+    let a = a as u128;
+    let mut res = 0u128;
+    for i in 0..64 {
+        // branchless select of a << i depending on the i-th bit of b
+        let mask = 0u128.wrapping_sub(((b >> i) & 1) as u128);
+        res ^= (a << i) & mask;
+    }
+    res
+}
+
+/// Multiplication modulo p(x) = x^128 + x^7 + x^2 + x + 1
+#[inline]
+pub(crate) fn gfmul(a: u128, b: u128) -> u128 {
+    let (a0, a1) = (a as u64, (a >> 64) as u64);
+    let (b0, b1) = (b as u64, (b >> 64) as u64);
+
+    // Karatsuba: a0 * b1 + a1 * b0 = (a0 + a1) * (b0 + b1) - a0 * b0 - a1 * b1
+    let lo = clmul(a0, b0);
+    let hi = clmul(a1, b1);
+    let mid = clmul(a0 ^ a1, b0 ^ b1) ^ lo ^ hi;
+
+    // the 256-bit product is hi * x^128 + lo
+    let lo = lo ^ (mid << 64);
+    let hi = hi ^ (mid >> 64);
+
+    // x^128 = x^7 + x^2 + x + 1; first fold the bits of hi that overflow past x^128 again
+    let overflow = (hi >> 127) ^ (hi >> 126) ^ (hi >> 121);
+    let hi = hi ^ overflow;
+    lo ^ hi ^ (hi << 1) ^ (hi << 2) ^ (hi << 7)
+}
+
+impl Neg for ScalarGF2_128 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl From<u32> for ScalarGF2_128 {
+    #[inline(always)]
+    fn from(v: u32) -> Self {
+        ScalarGF2_128 { v: v as u128 }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &ScalarGF2_128, b: &ScalarGF2_128) -> ScalarGF2_128 {
+    ScalarGF2_128 { v: a.v ^ b.v }
+}
+
+#[inline(always)]
+fn sub_internal(a: &ScalarGF2_128, b: &ScalarGF2_128) -> ScalarGF2_128 {
+    ScalarGF2_128 { v: a.v ^ b.v }
+}
+
+#[inline(always)]
+fn mul_internal(a: &ScalarGF2_128, b: &ScalarGF2_128) -> ScalarGF2_128 {
+    ScalarGF2_128 { v: gfmul(a.v, b.v) }
+}
@@ -1,15 +1,52 @@
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
 pub(crate) mod neon;
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
 pub type GF2_128x8 = neon::NeonGF2_128x8;
 
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx512f",
+    not(feature = "force-scalar")
+))]
 mod avx512;
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx512f",
+    not(feature = "force-scalar")
+))]
 pub type GF2_128x8 = avx512::AVX512GF2_128x8;
 
-// Fallback, use avx2
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "force-scalar")
+))]
 mod avx256;
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "force-scalar")
+))]
 pub type GF2_128x8 = avx256::AVX256GF2_128x8;
+
+// Fallback, no SIMD backend applies or the scalar one is forced.
+// Always built for tests, as the reference for the SIMD backends.
+#[cfg(any(
+    test,
+    feature = "force-scalar",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub(crate) mod scalar;
+#[cfg(any(
+    feature = "force-scalar",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub type GF2_128x8 = scalar::ScalarGF2_128x8;
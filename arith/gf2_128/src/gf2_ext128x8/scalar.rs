@@ -0,0 +1,276 @@
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use arith::{field_common, ExtensionField, Field, FieldSerde, FieldSerdeResult, SimdField};
+use gf2::{GF2x8, GF2};
+
+use crate::GF2_128;
+
+const GF2_128_PACK_SIZE: usize = 8;
+
+/// Portable GF2_128x8, used when no SIMD backend applies.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScalarGF2_128x8 {
+    v: [GF2_128; GF2_128_PACK_SIZE],
+}
+
+field_common!(ScalarGF2_128x8);
+
+impl ScalarGF2_128x8 {
+    /// Lane i holds the (7 - i)-th bit of a GF2x8, as in the SIMD backends
+    #[inline(always)]
+    fn unpack_gf2x8(v: GF2x8) -> [GF2; GF2_128_PACK_SIZE] {
+        std::array::from_fn(|i| GF2 {
+            v: (v.v >> (GF2_128_PACK_SIZE - 1 - i)) & 1u8,
+        })
+    }
+}
+
+impl FieldSerde for ScalarGF2_128x8 {
+    const SERIALIZED_SIZE: usize = 16 * GF2_128_PACK_SIZE;
+
+    #[inline(always)]
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.v
+            .iter()
+            .try_for_each(|x| x.serialize_into(&mut writer))
+    }
+
+    #[inline(always)]
+    fn deserialize_from<R: std::io::Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut v = [GF2_128::ZERO; GF2_128_PACK_SIZE];
+        for x in v.iter_mut() {
+            *x = GF2_128::deserialize_from(&mut reader)?;
+        }
+        Ok(ScalarGF2_128x8 { v })
+    }
+
+    #[inline]
+    fn try_deserialize_from_ecc_format<R: std::io::Read>(mut _reader: R) -> FieldSerdeResult<Self> {
+        unimplemented!("We don't have a serialization for gf2_128 in ecc yet.")
+    }
+}
+
+impl Field for ScalarGF2_128x8 {
+    const NAME: &'static str = "Scalar Galios Field 2^128x8";
+
+    const SIZE: usize = 16 * GF2_128_PACK_SIZE;
+
+    const FIELD_SIZE: usize = 128; // in bits
+
+    const ZERO: Self = ScalarGF2_128x8 {
+        v: [GF2_128::ZERO; GF2_128_PACK_SIZE],
+    };
+
+    const ONE: Self = ScalarGF2_128x8 {
+        v: [GF2_128::ONE; GF2_128_PACK_SIZE],
+    };
+
+    const INV_2: Self = ScalarGF2_128x8 {
+        v: [GF2_128::INV_2; GF2_128_PACK_SIZE],
+    }; // should not be used
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|x| x.is_zero())
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl rand::RngCore) -> Self {
+        ScalarGF2_128x8 {
+            v: std::array::from_fn(|_| GF2_128::random_unsafe(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl rand::RngCore) -> Self {
+        ScalarGF2_128x8 {
+            v: std::array::from_fn(|_| GF2_128::random_bool(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn exp(&self, exponent: u128) -> Self {
+        ScalarGF2_128x8 {
+            v: self.v.map(|x| x.exp(exponent)),
+        }
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        if self.v.iter().any(|x| x.is_zero()) {
+            return None;
+        }
+        Some(ScalarGF2_128x8 {
+            v: self.v.map(|x| x.inv().unwrap()), // safe unwrap
+        })
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        self * self
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("u32 for GF128 doesn't make sense")
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(_bytes: &[u8; 32]) -> Self {
+        unimplemented!("from_uniform_bytes for GF128 doesn't make sense")
+    }
+}
+
+impl SimdField for ScalarGF2_128x8 {
+    type Scalar = GF2_128;
+
+    #[inline(always)]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        ScalarGF2_128x8 {
+            v: self.v.map(|x| x * challenge),
+        }
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        GF2_128_PACK_SIZE
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == GF2_128_PACK_SIZE);
+        ScalarGF2_128x8 {
+            v: base_vec.try_into().unwrap(),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.to_vec()
+    }
+}
+
+impl From<GF2_128> for ScalarGF2_128x8 {
+    #[inline(always)]
+    fn from(v: GF2_128) -> Self {
+        ScalarGF2_128x8 {
+            v: [v; GF2_128_PACK_SIZE],
+        }
+    }
+}
+
+impl Neg for ScalarGF2_128x8 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+impl From<u32> for ScalarGF2_128x8 {
+    #[inline(always)]
+    fn from(v: u32) -> Self {
+        ScalarGF2_128x8 {
+            v: [GF2_128::from(v); GF2_128_PACK_SIZE],
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &ScalarGF2_128x8, b: &ScalarGF2_128x8) -> ScalarGF2_128x8 {
+    ScalarGF2_128x8 {
+        v: std::array::from_fn(|i| a.v[i] + b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &ScalarGF2_128x8, b: &ScalarGF2_128x8) -> ScalarGF2_128x8 {
+    add_internal(a, b)
+}
+
+#[inline(always)]
+fn mul_internal(a: &ScalarGF2_128x8, b: &ScalarGF2_128x8) -> ScalarGF2_128x8 {
+    ScalarGF2_128x8 {
+        v: std::array::from_fn(|i| a.v[i] * b.v[i]),
+    }
+}
+
+impl ExtensionField for ScalarGF2_128x8 {
+    const DEGREE: usize = GF2_128::DEGREE;
+
+    const W: u32 = GF2_128::W;
+
+    const X: Self = ScalarGF2_128x8 {
+        v: [GF2_128::X; GF2_128_PACK_SIZE],
+    };
+
+    type BaseField = GF2x8;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        let base = Self::unpack_gf2x8(*base);
+        ScalarGF2_128x8 {
+            v: std::array::from_fn(|i| self.v[i].mul_by_base_field(&base[i])),
+        }
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        let base = Self::unpack_gf2x8(*base);
+        ScalarGF2_128x8 {
+            v: std::array::from_fn(|i| self.v[i].add_by_base_field(&base[i])),
+        }
+    }
+
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        ScalarGF2_128x8 {
+            v: self.v.map(|x| x.mul_by_x()),
+        }
+    }
+}
+
+impl From<GF2x8> for ScalarGF2_128x8 {
+    #[inline(always)]
+    fn from(v: GF2x8) -> Self {
+        ScalarGF2_128x8 {
+            v: Self::unpack_gf2x8(v).map(GF2_128::from),
+        }
+    }
+}
+
+impl Mul<GF2> for ScalarGF2_128x8 {
+    type Output = ScalarGF2_128x8;
+
+    #[inline(always)]
+    fn mul(self, rhs: GF2) -> Self::Output {
+        if rhs.is_zero() {
+            Self::zero()
+        } else {
+            self
+        }
+    }
+}
+
+impl Add<GF2> for ScalarGF2_128x8 {
+    type Output = ScalarGF2_128x8;
+
+    #[inline(always)]
+    fn add(self, rhs: GF2) -> Self::Output {
+        ScalarGF2_128x8 {
+            v: self.v.map(|x| x.add_by_base_field(&rhs)),
+        }
+    }
+}
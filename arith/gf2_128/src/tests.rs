@@ -2,13 +2,16 @@ use std::io::Cursor;
 
 use arith::{
     random_extension_field_tests, random_field_tests, random_inversion_tests,
-    random_simd_field_tests, FieldSerde,
+    random_simd_field_tests, ExtensionField, Field, FieldSerde, SimdField,
 };
 use ark_std::test_rng;
+use gf2::GF2x8;
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
 use crate::gf2_ext128::neon::{gfadd, gfmul};
 
+use crate::gf2_ext128::scalar::{gfmul as scalar_gfmul, ScalarGF2_128};
+use crate::gf2_ext128x8::scalar::ScalarGF2_128x8;
 use crate::{GF2_128x8, GF2_128};
 
 #[test]
@@ -40,7 +43,96 @@ fn test_custom_serde_vectorize_gf2_128() {
     assert_eq!(a, b);
 }
 
-#[cfg(target_arch = "aarch64")]
+#[test]
+fn test_scalar_simd_field() {
+    random_field_tests::<ScalarGF2_128>("Scalar GF2 Ext128".to_string());
+    random_extension_field_tests::<ScalarGF2_128>("Scalar GF2 Ext128".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<ScalarGF2_128, _>(&mut rng, "Scalar GF2_128".to_string());
+
+    random_field_tests::<ScalarGF2_128x8>("Scalar Simd GF2 Ext128".to_string());
+    random_extension_field_tests::<ScalarGF2_128x8>("Scalar Simd GF2 Ext128".to_string());
+    random_simd_field_tests::<ScalarGF2_128x8>("Scalar Simd GF2 Ext128".to_string());
+}
+
+#[test]
+// known answer test, the same vectors as the neon one
+fn test_scalar_gf_mul_kat() {
+    let lanes = |x: u128| -> [u32; 4] { std::array::from_fn(|i| (x >> (32 * i)) as u32) };
+
+    assert_eq!(scalar_gfmul(0, 1234567890), 0);
+    assert_eq!(scalar_gfmul((3u128 << 64) + 5, 0), 0);
+    assert_eq!(scalar_gfmul(1, (3u128 << 64) + 5), (3u128 << 64) + 5);
+    assert_eq!(
+        lanes(scalar_gfmul((3u128 << 64) + 5, (1u128 << 64) + 7)),
+        [402, 0, 12, 0]
+    );
+    assert_eq!(
+        lanes(scalar_gfmul(
+            (1u128 << 64) + 7,
+            (1u128 << 96) + (1 << 64) + (1 << 32) + 1
+        )),
+        [128, 128, 6, 6]
+    );
+    assert_eq!(
+        lanes(scalar_gfmul(
+            u128::from_le_bytes([7u8; 16]),
+            u128::from_le_bytes([5u8; 16])
+        )),
+        [232394202; 4]
+    );
+
+    let mut a = [6u8; 16];
+    a[8] = 0;
+    let mut b = [5u8; 16];
+    b[4] = 1;
+    assert_eq!(
+        lanes(scalar_gfmul(u128::from_le_bytes(a), u128::from_le_bytes(b))),
+        [508894806, 1107902981, 155322701, 155322714]
+    );
+}
+
+/// The selected GF2_128 and GF2_128x8 backends must agree with the portable ones
+#[test]
+fn test_simd_against_scalar() {
+    fn to_scalar(x: &GF2_128) -> ScalarGF2_128 {
+        let mut buffer = vec![];
+        x.serialize_into(&mut buffer).unwrap();
+        ScalarGF2_128::deserialize_from(buffer.as_slice()).unwrap()
+    }
+
+    let mut rng = test_rng();
+    for _ in 0..1000 {
+        let a = GF2_128::random_unsafe(&mut rng);
+        let b = GF2_128::random_unsafe(&mut rng);
+        assert_eq!(to_scalar(&(a * b)), to_scalar(&a) * to_scalar(&b));
+        assert_eq!(to_scalar(&(a + b)), to_scalar(&a) + to_scalar(&b));
+        assert_eq!(to_scalar(&a.mul_by_x()), to_scalar(&a).mul_by_x());
+
+        let a = GF2_128x8::random_unsafe(&mut rng);
+        let b = GF2_128x8::random_unsafe(&mut rng);
+        let base = GF2x8::random_unsafe(&mut rng);
+        let a_scalar = ScalarGF2_128x8::pack(&a.unpack());
+        let b_scalar = ScalarGF2_128x8::pack(&b.unpack());
+        assert_eq!((a * b).unpack(), (a_scalar * b_scalar).unpack());
+        assert_eq!(a.mul_by_x().unpack(), a_scalar.mul_by_x().unpack());
+        assert_eq!(
+            a.mul_by_base_field(&base).unpack(),
+            a_scalar.mul_by_base_field(&base).unpack()
+        );
+        assert_eq!(
+            a.add_by_base_field(&base).unpack(),
+            a_scalar.add_by_base_field(&base).unpack()
+        );
+        assert_eq!(
+            GF2_128x8::from(base).unpack(),
+            ScalarGF2_128x8::from(base).unpack()
+        );
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
 #[test]
 // known answer test, results cross-checked with avx_gf2_128
 fn test_gf_mul_kat() {
@@ -127,7 +219,7 @@ fn test_gf_mul_kat() {
     }
 }
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
 #[test]
 fn test_gf_mul_rnd() {
     use core::mem::transmute;
@@ -0,0 +1,244 @@
+use std::{
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, FieldForECC, FieldSerde, FieldSerdeError, FieldSerdeResult};
+use ark_std::Zero;
+use rand::RngCore;
+
+/// Goldilocks modulus, p = 2^64 - 2^32 + 1
+pub const GOLDILOCKS_MOD: u64 = 0xFFFF_FFFF_0000_0001;
+
+/// 2^64 mod p = 2^32 - 1
+pub(crate) const EPSILON: u64 = 0xFFFF_FFFF;
+
+#[inline(always)]
+pub(crate) fn mod_reduce_u64(x: u64) -> u64 {
+    if x >= GOLDILOCKS_MOD {
+        x - GOLDILOCKS_MOD
+    } else {
+        x
+    }
+}
+
+/// Reduce a 128 bits integer, using 2^64 = 2^32 - 1 and 2^96 = -1 mod p.
+#[inline(always)]
+pub(crate) fn mod_reduce_u128(x: u128) -> u64 {
+    let x_lo = x as u64;
+    let x_hi = (x >> 64) as u64;
+    let x_hi_hi = x_hi >> 32;
+    let x_hi_lo = x_hi & EPSILON;
+
+    let (mut t0, borrow) = x_lo.overflowing_sub(x_hi_hi);
+    if borrow {
+        // cannot underflow: t0 >= 2^64 - 2^32 here
+        t0 -= EPSILON;
+    }
+    let t1 = x_hi_lo * EPSILON;
+    let (mut t2, carry) = t0.overflowing_add(t1);
+    if carry {
+        // cannot overflow: t2 < t1 <= (2^32 - 1)^2 here
+        t2 += EPSILON;
+    }
+    mod_reduce_u64(t2)
+}
+
+/// A Goldilocks element, always kept in the canonical form, i.e., v < p.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Goldilocks {
+    pub v: u64,
+}
+
+field_common!(Goldilocks);
+
+impl FieldSerde for Goldilocks {
+    const SERIALIZED_SIZE: usize = 64 / 8;
+
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        writer.write_all(self.v.to_le_bytes().as_ref())?;
+        Ok(())
+    }
+
+    // FIXME: this deserialization function auto corrects invalid inputs.
+    // We should use separate APIs for this and for the actual deserialization.
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut u = [0u8; Self::SERIALIZED_SIZE];
+        reader.read_exact(&mut u)?;
+        Ok(Goldilocks {
+            v: mod_reduce_u64(u64::from_le_bytes(u)),
+        })
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut buf = [0u8; 32];
+        reader.read_exact(&mut buf)?;
+        if buf.iter().skip(8).any(|&x| x != 0) {
+            // non-zero byte found in witness byte
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        Ok(Goldilocks {
+            v: mod_reduce_u64(u64::from_le_bytes(buf[..8].try_into().unwrap())),
+        })
+    }
+
+    #[inline(always)]
+    fn serialize_into_ecc_format<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        let mut buf = [0u8; 32];
+        buf[..8].copy_from_slice(&self.v.to_le_bytes());
+        writer.write_all(&buf)?;
+        Ok(())
+    }
+}
+
+impl Field for Goldilocks {
+    const NAME: &'static str = "Goldilocks";
+
+    const SIZE: usize = 64 / 8;
+
+    const ZERO: Self = Goldilocks { v: 0 };
+
+    const ONE: Self = Goldilocks { v: 1 };
+
+    // (p + 1) / 2
+    const INV_2: Goldilocks = Goldilocks {
+        v: 0x7FFF_FFFF_8000_0001,
+    };
+
+    const FIELD_SIZE: usize = 64;
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Goldilocks { v: 0 }
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Goldilocks { v: 1 }
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v == 0
+    }
+
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        Goldilocks {
+            v: mod_reduce_u64(rng.next_u64()),
+        }
+    }
+
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        (rng.next_u32() & 1).into()
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        let mut e = exponent;
+        let mut res = Self::one();
+        let mut t = *self;
+        while !e.is_zero() {
+            let b = e & 1;
+            if b == 1 {
+                res *= t;
+            }
+            t = t * t;
+            e >>= 1;
+        }
+        res
+    }
+
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        // Fermat's little theorem: a^(p-2) = a^-1
+        Some(self.exp((GOLDILOCKS_MOD - 2) as u128))
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        self.v as u32
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        Goldilocks {
+            v: mod_reduce_u64(u64::from_le_bytes(bytes[..8].try_into().unwrap())),
+        }
+    }
+}
+
+impl FieldForECC for Goldilocks {
+    fn modulus() -> ethnum::U256 {
+        ethnum::U256::from(GOLDILOCKS_MOD)
+    }
+    fn from_u256(x: ethnum::U256) -> Self {
+        Goldilocks {
+            v: (x % ethnum::U256::from(GOLDILOCKS_MOD)).as_u64(),
+        }
+    }
+    fn to_u256(&self) -> ethnum::U256 {
+        ethnum::U256::from(self.v)
+    }
+}
+
+impl Neg for Goldilocks {
+    type Output = Goldilocks;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Goldilocks {
+            v: if self.v == 0 {
+                0
+            } else {
+                GOLDILOCKS_MOD - self.v
+            },
+        }
+    }
+}
+
+impl From<u32> for Goldilocks {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        Goldilocks { v: x as u64 }
+    }
+}
+
+impl From<u64> for Goldilocks {
+    #[inline(always)]
+    fn from(x: u64) -> Self {
+        Goldilocks {
+            v: mod_reduce_u64(x),
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &Goldilocks, b: &Goldilocks) -> Goldilocks {
+    let (sum, over) = a.v.overflowing_add(b.v);
+    // on overflow, sum + 2^64 = sum + EPSILON mod p, and the result is already canonical
+    let v = if over {
+        sum + EPSILON
+    } else {
+        mod_reduce_u64(sum)
+    };
+    Goldilocks { v }
+}
+
+#[inline(always)]
+fn sub_internal(a: &Goldilocks, b: &Goldilocks) -> Goldilocks {
+    let (diff, borrow) = a.v.overflowing_sub(b.v);
+    // on borrow, diff - 2^64 = diff - EPSILON mod p
+    let v = if borrow { diff - EPSILON } else { diff };
+    Goldilocks { v }
+}
+
+#[inline(always)]
+fn mul_internal(a: &Goldilocks, b: &Goldilocks) -> Goldilocks {
+    Goldilocks {
+        v: mod_reduce_u128(a.v as u128 * b.v as u128),
+    }
+}
@@ -0,0 +1,315 @@
+use std::{
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, ExtensionField, Field, FieldSerde, FieldSerdeResult, SimdField};
+
+use crate::{Goldilocks, GoldilocksExt2, Goldilocksx8};
+
+/// The extension is defined by x^2 - 7
+const GOLDILOCKS_EXT_W: Goldilocks = Goldilocks { v: 7 };
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GoldilocksExt2x8 {
+    pub v: [Goldilocksx8; 2],
+}
+
+field_common!(GoldilocksExt2x8);
+
+impl FieldSerde for GoldilocksExt2x8 {
+    const SERIALIZED_SIZE: usize = (512 / 8) * 2;
+
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.v[0].serialize_into(&mut writer)?;
+        self.v[1].serialize_into(&mut writer)
+    }
+
+    // FIXME: this deserialization function auto corrects invalid inputs.
+    // We should use separate APIs for this and for the actual deserialization.
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        Ok(Self {
+            v: [
+                Goldilocksx8::deserialize_from(&mut reader)?,
+                Goldilocksx8::deserialize_from(&mut reader)?,
+            ],
+        })
+    }
+
+    fn try_deserialize_from_ecc_format<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        Ok(Self {
+            v: [
+                Goldilocksx8::try_deserialize_from_ecc_format(&mut reader)?,
+                Goldilocksx8::zero(),
+            ],
+        })
+    }
+}
+
+impl SimdField for GoldilocksExt2x8 {
+    type Scalar = GoldilocksExt2;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        Goldilocksx8::pack_size()
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == Self::pack_size());
+        let v0s: Vec<Goldilocks> = base_vec.iter().map(|x| x.v[0]).collect();
+        let v1s: Vec<Goldilocks> = base_vec.iter().map(|x| x.v[1]).collect();
+
+        Self {
+            v: [Goldilocksx8::pack(&v0s), Goldilocksx8::pack(&v1s)],
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        let v0s = self.v[0].unpack();
+        let v1s = self.v[1].unpack();
+
+        v0s.into_iter()
+            .zip(v1s)
+            .map(|(v0, v1)| GoldilocksExt2 { v: [v0, v1] })
+            .collect()
+    }
+}
+
+impl From<Goldilocksx8> for GoldilocksExt2x8 {
+    #[inline(always)]
+    fn from(x: Goldilocksx8) -> Self {
+        Self {
+            v: [x, Goldilocksx8::zero()],
+        }
+    }
+}
+
+impl ExtensionField for GoldilocksExt2x8 {
+    const DEGREE: usize = 2;
+
+    const W: u32 = 7;
+
+    const X: Self = GoldilocksExt2x8 {
+        v: [Goldilocksx8::ZERO, Goldilocksx8::ONE],
+    };
+
+    type BaseField = Goldilocksx8;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        GoldilocksExt2x8 {
+            v: [self.v[0] * base, self.v[1] * base],
+        }
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        GoldilocksExt2x8 {
+            v: [self.v[0] + base, self.v[1]],
+        }
+    }
+
+    /// Multiply the extension field by x, i.e, 0 + x + 0 x^2 + 0 x^3 + ...
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        Self {
+            v: [self.v[1] * GOLDILOCKS_EXT_W, self.v[0]],
+        }
+    }
+}
+
+impl From<GoldilocksExt2> for GoldilocksExt2x8 {
+    #[inline(always)]
+    fn from(x: GoldilocksExt2) -> Self {
+        Self {
+            v: [Goldilocksx8::from(x.v[0]), Goldilocksx8::from(x.v[1])],
+        }
+    }
+}
+
+impl Field for GoldilocksExt2x8 {
+    const NAME: &'static str = "Vectorized Goldilocks Extension 2";
+
+    const SIZE: usize = 512 / 8 * 2;
+
+    const FIELD_SIZE: usize = 64 * 2;
+
+    const ZERO: Self = Self {
+        v: [Goldilocksx8::ZERO; 2],
+    };
+
+    const ONE: Self = Self {
+        v: [Goldilocksx8::ONE, Goldilocksx8::ZERO],
+    };
+
+    const INV_2: Self = Self {
+        v: [Goldilocksx8::INV_2, Goldilocksx8::ZERO],
+    };
+
+    #[inline(always)]
+    fn zero() -> Self {
+        GoldilocksExt2x8 {
+            v: [Goldilocksx8::zero(); 2],
+        }
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v[0].is_zero() && self.v[1].is_zero()
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        GoldilocksExt2x8 {
+            v: [Goldilocksx8::one(), Goldilocksx8::zero()],
+        }
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl rand::RngCore) -> Self {
+        GoldilocksExt2x8 {
+            v: [
+                Goldilocksx8::random_unsafe(&mut rng),
+                Goldilocksx8::random_unsafe(&mut rng),
+            ],
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl rand::RngCore) -> Self {
+        GoldilocksExt2x8 {
+            v: [Goldilocksx8::random_bool(&mut rng), Goldilocksx8::zero()],
+        }
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        Self {
+            v: square_internal(&self.v),
+        }
+    }
+
+    fn exp(&self, _exponent: u128) -> Self {
+        unimplemented!()
+    }
+
+    fn inv(&self) -> Option<Self> {
+        unimplemented!()
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    fn from_uniform_bytes(_bytes: &[u8; 32]) -> Self {
+        unimplemented!("vec goldilocks: cannot convert from 32 bytes")
+    }
+}
+
+impl Mul<GoldilocksExt2> for GoldilocksExt2x8 {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: GoldilocksExt2) -> Self::Output {
+        // polynomial mod (x^2 - 7)
+        //
+        //   (a0 + a1*x) * (b0 + b1*x) mod (x^2 - 7)
+        // = a0*b0 + 7*a1*b1 + (a0*b1 + a1*b0)*x
+        Self {
+            v: [
+                self.v[0] * rhs.v[0] + self.v[1] * (rhs.v[1] * GOLDILOCKS_EXT_W),
+                self.v[0] * rhs.v[1] + self.v[1] * rhs.v[0],
+            ],
+        }
+    }
+}
+
+impl Mul<Goldilocks> for GoldilocksExt2x8 {
+    type Output = GoldilocksExt2x8;
+    #[inline(always)]
+    fn mul(self, rhs: Goldilocks) -> Self::Output {
+        GoldilocksExt2x8 {
+            // Goldilocksx8 * Goldilocks
+            v: [self.v[0] * rhs, self.v[1] * rhs],
+        }
+    }
+}
+
+impl Add<Goldilocks> for GoldilocksExt2x8 {
+    type Output = GoldilocksExt2x8;
+    #[inline(always)]
+    fn add(self, rhs: Goldilocks) -> Self::Output {
+        GoldilocksExt2x8 {
+            // Goldilocksx8 + Goldilocks
+            v: [self.v[0] + rhs, self.v[1]],
+        }
+    }
+}
+
+impl Neg for GoldilocksExt2x8 {
+    type Output = GoldilocksExt2x8;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        GoldilocksExt2x8 {
+            v: [-self.v[0], -self.v[1]],
+        }
+    }
+}
+
+impl From<u32> for GoldilocksExt2x8 {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        GoldilocksExt2x8 {
+            v: [Goldilocksx8::from(x), Goldilocksx8::zero()],
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &GoldilocksExt2x8, b: &GoldilocksExt2x8) -> GoldilocksExt2x8 {
+    GoldilocksExt2x8 {
+        v: [a.v[0] + b.v[0], a.v[1] + b.v[1]],
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &GoldilocksExt2x8, b: &GoldilocksExt2x8) -> GoldilocksExt2x8 {
+    GoldilocksExt2x8 {
+        v: [a.v[0] - b.v[0], a.v[1] - b.v[1]],
+    }
+}
+
+// polynomial mod (x^2 - 7)
+//
+//   (a0 + a1*x) * (b0 + b1*x) mod (x^2 - 7)
+// = a0*b0 + (a0*b1 + a1*b0)*x + a1*b1*x^2 mod (x^2 - 7)
+// = a0*b0 + 7*a1*b1 + (a0*b1 + a1*b0)*x
+#[inline(always)]
+fn mul_internal(a: &GoldilocksExt2x8, b: &GoldilocksExt2x8) -> GoldilocksExt2x8 {
+    let a = &a.v;
+    let b = &b.v;
+    GoldilocksExt2x8 {
+        v: [
+            a[0] * b[0] + a[1] * b[1] * GOLDILOCKS_EXT_W,
+            a[0] * b[1] + a[1] * b[0],
+        ],
+    }
+}
+
+// same as mul; merge identical terms
+#[inline(always)]
+fn square_internal(a: &[Goldilocksx8; 2]) -> [Goldilocksx8; 2] {
+    [
+        a[0].square() + a[1].square() * GOLDILOCKS_EXT_W,
+        a[0] * a[1].double(),
+    ]
+}
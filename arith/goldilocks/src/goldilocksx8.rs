@@ -0,0 +1,58 @@
+// A Goldilocksx8 stores 512 bits of data.
+// With AVX512 it stores a single __m512i element.
+// With AVX2 it stores two __m256i elements.
+// With NEON it stores four uint64x2_t elements.
+// Otherwise it stores eight Goldilocks elements.
+
+#[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
+mod goldilocks_neon;
+#[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
+pub type Goldilocksx8 = goldilocks_neon::NeonGoldilocks;
+
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx512f",
+    not(feature = "force-scalar")
+))]
+mod goldilocks_avx512;
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx512f",
+    not(feature = "force-scalar")
+))]
+pub type Goldilocksx8 = goldilocks_avx512::AVXGoldilocks;
+
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "force-scalar")
+))]
+mod goldilocks_avx256;
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "force-scalar")
+))]
+pub type Goldilocksx8 = goldilocks_avx256::AVXGoldilocks;
+
+// Fallback, no SIMD backend applies or the scalar one is forced.
+// Always built for tests, as the reference for the SIMD backends.
+#[cfg(any(
+    test,
+    feature = "force-scalar",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub(crate) mod goldilocks_scalar;
+#[cfg(any(
+    feature = "force-scalar",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub type Goldilocksx8 = goldilocks_scalar::ScalarGoldilocks;
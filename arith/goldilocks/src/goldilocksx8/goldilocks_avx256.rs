@@ -0,0 +1,379 @@
+use std::{
+    arch::x86_64::*,
+    fmt::Debug,
+    io::{Read, Write},
+    iter::{Product, Sum},
+    mem::transmute,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, FieldSerde, FieldSerdeResult, SimdField};
+use rand::RngCore;
+
+use crate::goldilocks::{Goldilocks, EPSILON, GOLDILOCKS_MOD};
+
+const GOLDILOCKS_PACK_SIZE: usize = 8;
+const PACKED_MOD: __m256i = unsafe { transmute([GOLDILOCKS_MOD; GOLDILOCKS_PACK_SIZE / 2]) };
+const PACKED_EPSILON: __m256i = unsafe { transmute([EPSILON; GOLDILOCKS_PACK_SIZE / 2]) };
+const PACKED_SIGN: __m256i = unsafe { transmute([1u64 << 63; GOLDILOCKS_PACK_SIZE / 2]) };
+const PACKED_0: __m256i = unsafe { transmute([0u64; GOLDILOCKS_PACK_SIZE / 2]) };
+const PACKED_1: __m256i = unsafe { transmute([1u64; GOLDILOCKS_PACK_SIZE / 2]) };
+const PACKED_INV_2: __m256i = unsafe { transmute([Goldilocks::INV_2.v; GOLDILOCKS_PACK_SIZE / 2]) };
+
+/// Unsigned a < b, as a mask. AVX2 only has the signed comparison,
+/// so both sides are shifted by 2^63 first.
+#[inline(always)]
+unsafe fn cmplt_epu64(a: __m256i, b: __m256i) -> __m256i {
+    _mm256_cmpgt_epi64(
+        _mm256_xor_si256(b, PACKED_SIGN),
+        _mm256_xor_si256(a, PACKED_SIGN),
+    )
+}
+
+/// Map [0, 2^64) to [0, p); a single subtraction suffices as 2p > 2^64.
+#[inline(always)]
+unsafe fn mod_reduce_epi64(x: __m256i) -> __m256i {
+    let is_canonical = cmplt_epu64(x, PACKED_MOD);
+    _mm256_sub_epi64(x, _mm256_andnot_si256(is_canonical, PACKED_MOD))
+}
+
+#[derive(Clone, Copy)]
+pub struct AVXGoldilocks {
+    pub v: [__m256i; 2],
+}
+
+impl AVXGoldilocks {
+    #[inline(always)]
+    pub(crate) fn pack_full(x: Goldilocks) -> AVXGoldilocks {
+        AVXGoldilocks {
+            v: unsafe {
+                [
+                    _mm256_set1_epi64x(x.v as i64),
+                    _mm256_set1_epi64x(x.v as i64),
+                ]
+            },
+        }
+    }
+}
+
+field_common!(AVXGoldilocks);
+
+impl FieldSerde for AVXGoldilocks {
+    const SERIALIZED_SIZE: usize = 512 / 8;
+
+    #[inline(always)]
+    /// serialize self into bytes
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        let data = unsafe { transmute::<[__m256i; 2], [u8; 64]>(self.v) };
+        writer.write_all(&data)?;
+        Ok(())
+    }
+
+    /// deserialize bytes into field
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut data = [0; Self::SERIALIZED_SIZE];
+        reader.read_exact(&mut data)?;
+        unsafe {
+            let value = transmute::<[u8; Self::SERIALIZED_SIZE], [__m256i; 2]>(data);
+            Ok(AVXGoldilocks {
+                v: [mod_reduce_epi64(value[0]), mod_reduce_epi64(value[1])],
+            })
+        }
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(reader: R) -> FieldSerdeResult<Self> {
+        Ok(Self::pack_full(
+            Goldilocks::try_deserialize_from_ecc_format(reader)?,
+        ))
+    }
+}
+
+impl Field for AVXGoldilocks {
+    const NAME: &'static str = "AVX Packed Goldilocks";
+
+    // size in bytes
+    const SIZE: usize = 512 / 8;
+
+    const ZERO: Self = Self {
+        v: [PACKED_0, PACKED_0],
+    };
+
+    const ONE: Self = Self {
+        v: [PACKED_1, PACKED_1],
+    };
+
+    const INV_2: Self = Self {
+        v: [PACKED_INV_2, PACKED_INV_2],
+    };
+
+    const FIELD_SIZE: usize = 64;
+
+    #[inline(always)]
+    fn zero() -> Self {
+        AVXGoldilocks {
+            v: unsafe { [_mm256_setzero_si256(), _mm256_setzero_si256()] },
+        }
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        unsafe {
+            let or = _mm256_or_si256(self.v[0], self.v[1]);
+            _mm256_testz_si256(or, or) != 0
+        }
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        AVXGoldilocks {
+            v: unsafe { [_mm256_set1_epi64x(1), _mm256_set1_epi64x(1)] },
+        }
+    }
+
+    #[inline(always)]
+    // this function is for internal testing only. it is not
+    // a source for uniformly random field elements and
+    // should not be used in production.
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        let data: [Goldilocks; GOLDILOCKS_PACK_SIZE] =
+            std::array::from_fn(|_| Goldilocks::random_unsafe(&mut rng));
+        Self::pack(&data)
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        let data: [Goldilocks; GOLDILOCKS_PACK_SIZE] =
+            std::array::from_fn(|_| Goldilocks::random_bool(&mut rng));
+        Self::pack(&data)
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        let mut e = exponent;
+        let mut res = Self::one();
+        let mut t = *self;
+        while e != 0 {
+            if e & 1 == 1 {
+                res *= t;
+            }
+            t = t.square();
+            e >>= 1;
+        }
+        res
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        // slow, should not be used in production
+        let mut goldilocks_vec = self.unpack();
+        if goldilocks_vec.iter().any(|x| x.is_zero()) {
+            return None;
+        }
+
+        goldilocks_vec
+            .iter_mut()
+            .for_each(|x| *x = x.inv().unwrap()); // safe unwrap
+        Some(Self::pack(&goldilocks_vec))
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        Self::pack_full(Goldilocks::from_uniform_bytes(bytes))
+    }
+}
+
+impl SimdField for AVXGoldilocks {
+    type Scalar = Goldilocks;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        GOLDILOCKS_PACK_SIZE
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == GOLDILOCKS_PACK_SIZE);
+        let ret: [Self::Scalar; GOLDILOCKS_PACK_SIZE] = base_vec.try_into().unwrap();
+        unsafe { transmute(ret) }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        let ret =
+            unsafe { transmute::<[__m256i; 2], [Self::Scalar; GOLDILOCKS_PACK_SIZE]>(self.v) };
+        ret.to_vec()
+    }
+}
+
+impl From<Goldilocks> for AVXGoldilocks {
+    #[inline(always)]
+    fn from(x: Goldilocks) -> Self {
+        AVXGoldilocks::pack_full(x)
+    }
+}
+
+impl Debug for AVXGoldilocks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = unsafe { transmute::<[__m256i; 2], [u64; GOLDILOCKS_PACK_SIZE]>(self.v) };
+        // if all data is the same, print only one
+        if data.iter().all(|&x| x == data[0]) {
+            write!(f, "mm256i<8 x {}>", data[0])
+        } else {
+            write!(f, "mm256i<{:?}>", data)
+        }
+    }
+}
+
+impl Default for AVXGoldilocks {
+    fn default() -> Self {
+        AVXGoldilocks::zero()
+    }
+}
+
+impl PartialEq for AVXGoldilocks {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            let cmp0 = _mm256_cmpeq_epi64(self.v[0], other.v[0]);
+            let cmp1 = _mm256_cmpeq_epi64(self.v[1], other.v[1]);
+            _mm256_movemask_epi8(_mm256_and_si256(cmp0, cmp1)) == -1
+        }
+    }
+}
+
+impl Mul<&Goldilocks> for AVXGoldilocks {
+    type Output = AVXGoldilocks;
+
+    #[inline(always)]
+    fn mul(self, rhs: &Goldilocks) -> Self::Output {
+        self * AVXGoldilocks::pack_full(*rhs)
+    }
+}
+
+impl Mul<Goldilocks> for AVXGoldilocks {
+    type Output = AVXGoldilocks;
+    #[inline(always)]
+    #[allow(clippy::op_ref)]
+    fn mul(self, rhs: Goldilocks) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Add<Goldilocks> for AVXGoldilocks {
+    type Output = AVXGoldilocks;
+    #[inline(always)]
+    fn add(self, rhs: Goldilocks) -> Self::Output {
+        self + AVXGoldilocks::pack_full(rhs)
+    }
+}
+
+impl From<u32> for AVXGoldilocks {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        AVXGoldilocks::pack_full(Goldilocks::from(x))
+    }
+}
+
+impl Neg for AVXGoldilocks {
+    type Output = AVXGoldilocks;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        AVXGoldilocks::zero() - self
+    }
+}
+
+#[inline(always)]
+unsafe fn add_epi64(a: __m256i, b: __m256i) -> __m256i {
+    let sum = _mm256_add_epi64(a, b);
+    // on overflow, add 2^64 mod p = EPSILON; the result is then already canonical
+    let overflow = cmplt_epu64(sum, a);
+    let sum = _mm256_add_epi64(sum, _mm256_and_si256(overflow, PACKED_EPSILON));
+    mod_reduce_epi64(sum)
+}
+
+#[inline(always)]
+unsafe fn sub_epi64(a: __m256i, b: __m256i) -> __m256i {
+    let diff = _mm256_sub_epi64(a, b);
+    // on borrow, subtract 2^64 mod p = EPSILON
+    let borrow = cmplt_epu64(a, b);
+    _mm256_sub_epi64(diff, _mm256_and_si256(borrow, PACKED_EPSILON))
+}
+
+/// Full 64 x 64 -> 128 bits multiplication, returning (hi, lo),
+/// built from the 32 x 32 -> 64 bits multiplications of the low halves of each lane.
+#[inline(always)]
+unsafe fn mul_64_64(a: __m256i, b: __m256i) -> (__m256i, __m256i) {
+    let a_hi = _mm256_srli_epi64::<32>(a);
+    let b_hi = _mm256_srli_epi64::<32>(b);
+
+    let ll = _mm256_mul_epu32(a, b);
+    let lh = _mm256_mul_epu32(a, b_hi);
+    let hl = _mm256_mul_epu32(a_hi, b);
+    let hh = _mm256_mul_epu32(a_hi, b_hi);
+
+    // none of the additions below overflows
+    let t = _mm256_add_epi64(hl, _mm256_srli_epi64::<32>(ll));
+    let u = _mm256_add_epi64(lh, _mm256_and_si256(t, PACKED_EPSILON));
+    let lo = _mm256_or_si256(
+        _mm256_slli_epi64::<32>(u),
+        _mm256_and_si256(ll, PACKED_EPSILON),
+    );
+    let hi = _mm256_add_epi64(
+        hh,
+        _mm256_add_epi64(_mm256_srli_epi64::<32>(t), _mm256_srli_epi64::<32>(u)),
+    );
+    (hi, lo)
+}
+
+/// Reduce hi * 2^64 + lo, using 2^64 = 2^32 - 1 and 2^96 = -1 mod p.
+#[inline(always)]
+unsafe fn mod_reduce_128(hi: __m256i, lo: __m256i) -> __m256i {
+    let hi_hi = _mm256_srli_epi64::<32>(hi);
+    let t0 = _mm256_sub_epi64(lo, hi_hi);
+    let borrow = cmplt_epu64(lo, hi_hi);
+    let t0 = _mm256_sub_epi64(t0, _mm256_and_si256(borrow, PACKED_EPSILON));
+
+    // (hi mod 2^32) * (2^32 - 1)
+    let t1 = _mm256_mul_epu32(hi, PACKED_EPSILON);
+    let t2 = _mm256_add_epi64(t0, t1);
+    let carry = cmplt_epu64(t2, t1);
+    let t2 = _mm256_add_epi64(t2, _mm256_and_si256(carry, PACKED_EPSILON));
+    mod_reduce_epi64(t2)
+}
+
+#[inline(always)]
+unsafe fn mul_epi64(a: __m256i, b: __m256i) -> __m256i {
+    let (hi, lo) = mul_64_64(a, b);
+    mod_reduce_128(hi, lo)
+}
+
+#[inline(always)]
+fn add_internal(a: &AVXGoldilocks, b: &AVXGoldilocks) -> AVXGoldilocks {
+    AVXGoldilocks {
+        v: unsafe { [add_epi64(a.v[0], b.v[0]), add_epi64(a.v[1], b.v[1])] },
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &AVXGoldilocks, b: &AVXGoldilocks) -> AVXGoldilocks {
+    AVXGoldilocks {
+        v: unsafe { [sub_epi64(a.v[0], b.v[0]), sub_epi64(a.v[1], b.v[1])] },
+    }
+}
+
+#[inline(always)]
+fn mul_internal(a: &AVXGoldilocks, b: &AVXGoldilocks) -> AVXGoldilocks {
+    AVXGoldilocks {
+        v: unsafe { [mul_epi64(a.v[0], b.v[0]), mul_epi64(a.v[1], b.v[1])] },
+    }
+}
@@ -0,0 +1,340 @@
+use std::{
+    arch::x86_64::*,
+    fmt::Debug,
+    io::{Read, Write},
+    iter::{Product, Sum},
+    mem::transmute,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, FieldSerde, FieldSerdeResult, SimdField};
+use rand::RngCore;
+
+use crate::goldilocks::{Goldilocks, EPSILON, GOLDILOCKS_MOD};
+
+const GOLDILOCKS_PACK_SIZE: usize = 8;
+const PACKED_MOD: __m512i = unsafe { transmute([GOLDILOCKS_MOD; GOLDILOCKS_PACK_SIZE]) };
+const PACKED_EPSILON: __m512i = unsafe { transmute([EPSILON; GOLDILOCKS_PACK_SIZE]) };
+const PACKED_0: __m512i = unsafe { transmute([0u64; GOLDILOCKS_PACK_SIZE]) };
+const PACKED_1: __m512i = unsafe { transmute([1u64; GOLDILOCKS_PACK_SIZE]) };
+const PACKED_INV_2: __m512i = unsafe { transmute([Goldilocks::INV_2.v; GOLDILOCKS_PACK_SIZE]) };
+
+/// Map [0, 2^64) to [0, p); a single subtraction suffices as 2p > 2^64.
+#[inline(always)]
+unsafe fn mod_reduce_epi64(x: __m512i) -> __m512i {
+    let mask = _mm512_cmpge_epu64_mask(x, PACKED_MOD);
+    _mm512_mask_sub_epi64(x, mask, x, PACKED_MOD)
+}
+
+#[derive(Clone, Copy)]
+pub struct AVXGoldilocks {
+    pub v: __m512i,
+}
+
+impl AVXGoldilocks {
+    #[inline(always)]
+    pub(crate) fn pack_full(x: Goldilocks) -> AVXGoldilocks {
+        AVXGoldilocks {
+            v: unsafe { _mm512_set1_epi64(x.v as i64) },
+        }
+    }
+}
+
+field_common!(AVXGoldilocks);
+
+impl FieldSerde for AVXGoldilocks {
+    const SERIALIZED_SIZE: usize = 512 / 8;
+
+    #[inline(always)]
+    /// serialize self into bytes
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        let data = unsafe { transmute::<__m512i, [u8; 64]>(self.v) };
+        writer.write_all(&data)?;
+        Ok(())
+    }
+
+    /// deserialize bytes into field
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut data = [0; Self::SERIALIZED_SIZE];
+        reader.read_exact(&mut data)?;
+        unsafe {
+            let value = transmute::<[u8; Self::SERIALIZED_SIZE], __m512i>(data);
+            Ok(AVXGoldilocks {
+                v: mod_reduce_epi64(value),
+            })
+        }
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(reader: R) -> FieldSerdeResult<Self> {
+        Ok(Self::pack_full(
+            Goldilocks::try_deserialize_from_ecc_format(reader)?,
+        ))
+    }
+}
+
+impl Field for AVXGoldilocks {
+    const NAME: &'static str = "AVX512 Packed Goldilocks";
+
+    // size in bytes
+    const SIZE: usize = 512 / 8;
+
+    const ZERO: Self = Self { v: PACKED_0 };
+
+    const ONE: Self = Self { v: PACKED_1 };
+
+    const INV_2: Self = Self { v: PACKED_INV_2 };
+
+    const FIELD_SIZE: usize = 64;
+
+    #[inline(always)]
+    fn zero() -> Self {
+        AVXGoldilocks {
+            v: unsafe { _mm512_setzero_si512() },
+        }
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        unsafe { _mm512_cmpeq_epi64_mask(self.v, PACKED_0) == 0xFF }
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        AVXGoldilocks {
+            v: unsafe { _mm512_set1_epi64(1) },
+        }
+    }
+
+    #[inline(always)]
+    // this function is for internal testing only. it is not
+    // a source for uniformly random field elements and
+    // should not be used in production.
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        let data: [Goldilocks; GOLDILOCKS_PACK_SIZE] =
+            std::array::from_fn(|_| Goldilocks::random_unsafe(&mut rng));
+        Self::pack(&data)
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        let data: [Goldilocks; GOLDILOCKS_PACK_SIZE] =
+            std::array::from_fn(|_| Goldilocks::random_bool(&mut rng));
+        Self::pack(&data)
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        let mut e = exponent;
+        let mut res = Self::one();
+        let mut t = *self;
+        while e != 0 {
+            if e & 1 == 1 {
+                res *= t;
+            }
+            t = t.square();
+            e >>= 1;
+        }
+        res
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        // slow, should not be used in production
+        let mut goldilocks_vec = self.unpack();
+        if goldilocks_vec.iter().any(|x| x.is_zero()) {
+            return None;
+        }
+
+        goldilocks_vec
+            .iter_mut()
+            .for_each(|x| *x = x.inv().unwrap()); // safe unwrap
+        Some(Self::pack(&goldilocks_vec))
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        Self::pack_full(Goldilocks::from_uniform_bytes(bytes))
+    }
+}
+
+impl SimdField for AVXGoldilocks {
+    type Scalar = Goldilocks;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        GOLDILOCKS_PACK_SIZE
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == GOLDILOCKS_PACK_SIZE);
+        let ret: [Self::Scalar; GOLDILOCKS_PACK_SIZE] = base_vec.try_into().unwrap();
+        unsafe { transmute(ret) }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        let ret = unsafe { transmute::<__m512i, [Self::Scalar; GOLDILOCKS_PACK_SIZE]>(self.v) };
+        ret.to_vec()
+    }
+}
+
+impl From<Goldilocks> for AVXGoldilocks {
+    #[inline(always)]
+    fn from(x: Goldilocks) -> Self {
+        AVXGoldilocks::pack_full(x)
+    }
+}
+
+impl Debug for AVXGoldilocks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = unsafe { transmute::<__m512i, [u64; GOLDILOCKS_PACK_SIZE]>(self.v) };
+        // if all data is the same, print only one
+        if data.iter().all(|&x| x == data[0]) {
+            write!(f, "mm512i<8 x {}>", data[0])
+        } else {
+            write!(f, "mm512i<{:?}>", data)
+        }
+    }
+}
+
+impl Default for AVXGoldilocks {
+    fn default() -> Self {
+        AVXGoldilocks::zero()
+    }
+}
+
+impl PartialEq for AVXGoldilocks {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { _mm512_cmpeq_epi64_mask(self.v, other.v) == 0xFF }
+    }
+}
+
+impl Mul<&Goldilocks> for AVXGoldilocks {
+    type Output = AVXGoldilocks;
+
+    #[inline(always)]
+    fn mul(self, rhs: &Goldilocks) -> Self::Output {
+        self * AVXGoldilocks::pack_full(*rhs)
+    }
+}
+
+impl Mul<Goldilocks> for AVXGoldilocks {
+    type Output = AVXGoldilocks;
+    #[inline(always)]
+    #[allow(clippy::op_ref)]
+    fn mul(self, rhs: Goldilocks) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Add<Goldilocks> for AVXGoldilocks {
+    type Output = AVXGoldilocks;
+    #[inline(always)]
+    fn add(self, rhs: Goldilocks) -> Self::Output {
+        self + AVXGoldilocks::pack_full(rhs)
+    }
+}
+
+impl From<u32> for AVXGoldilocks {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        AVXGoldilocks::pack_full(Goldilocks::from(x))
+    }
+}
+
+impl Neg for AVXGoldilocks {
+    type Output = AVXGoldilocks;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        AVXGoldilocks::zero() - self
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &AVXGoldilocks, b: &AVXGoldilocks) -> AVXGoldilocks {
+    unsafe {
+        let sum = _mm512_add_epi64(a.v, b.v);
+        // on overflow, add 2^64 mod p = EPSILON; the result is then already canonical
+        let overflow = _mm512_cmplt_epu64_mask(sum, a.v);
+        let sum = _mm512_mask_add_epi64(sum, overflow, sum, PACKED_EPSILON);
+        AVXGoldilocks {
+            v: mod_reduce_epi64(sum),
+        }
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &AVXGoldilocks, b: &AVXGoldilocks) -> AVXGoldilocks {
+    unsafe {
+        let diff = _mm512_sub_epi64(a.v, b.v);
+        // on borrow, subtract 2^64 mod p = EPSILON
+        let borrow = _mm512_cmplt_epu64_mask(a.v, b.v);
+        AVXGoldilocks {
+            v: _mm512_mask_sub_epi64(diff, borrow, diff, PACKED_EPSILON),
+        }
+    }
+}
+
+/// Full 64 x 64 -> 128 bits multiplication, returning (hi, lo),
+/// built from the 32 x 32 -> 64 bits multiplications of the low halves of each lane.
+#[inline(always)]
+unsafe fn mul_64_64(a: __m512i, b: __m512i) -> (__m512i, __m512i) {
+    let a_hi = _mm512_srli_epi64::<32>(a);
+    let b_hi = _mm512_srli_epi64::<32>(b);
+
+    let ll = _mm512_mul_epu32(a, b);
+    let lh = _mm512_mul_epu32(a, b_hi);
+    let hl = _mm512_mul_epu32(a_hi, b);
+    let hh = _mm512_mul_epu32(a_hi, b_hi);
+
+    // none of the additions below overflows
+    let t = _mm512_add_epi64(hl, _mm512_srli_epi64::<32>(ll));
+    let u = _mm512_add_epi64(lh, _mm512_and_si512(t, PACKED_EPSILON));
+    let lo = _mm512_or_si512(
+        _mm512_slli_epi64::<32>(u),
+        _mm512_and_si512(ll, PACKED_EPSILON),
+    );
+    let hi = _mm512_add_epi64(
+        hh,
+        _mm512_add_epi64(_mm512_srli_epi64::<32>(t), _mm512_srli_epi64::<32>(u)),
+    );
+    (hi, lo)
+}
+
+/// Reduce hi * 2^64 + lo, using 2^64 = 2^32 - 1 and 2^96 = -1 mod p.
+#[inline(always)]
+unsafe fn mod_reduce_128(hi: __m512i, lo: __m512i) -> __m512i {
+    let hi_hi = _mm512_srli_epi64::<32>(hi);
+    let t0 = _mm512_sub_epi64(lo, hi_hi);
+    let borrow = _mm512_cmplt_epu64_mask(lo, hi_hi);
+    let t0 = _mm512_mask_sub_epi64(t0, borrow, t0, PACKED_EPSILON);
+
+    // (hi mod 2^32) * (2^32 - 1)
+    let t1 = _mm512_mul_epu32(hi, PACKED_EPSILON);
+    let t2 = _mm512_add_epi64(t0, t1);
+    let carry = _mm512_cmplt_epu64_mask(t2, t1);
+    let t2 = _mm512_mask_add_epi64(t2, carry, t2, PACKED_EPSILON);
+    mod_reduce_epi64(t2)
+}
+
+#[inline(always)]
+fn mul_internal(a: &AVXGoldilocks, b: &AVXGoldilocks) -> AVXGoldilocks {
+    unsafe {
+        let (hi, lo) = mul_64_64(a.v, b.v);
+        AVXGoldilocks {
+            v: mod_reduce_128(hi, lo),
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use std::{
+    arch::aarch64::*,
+    fmt::Debug,
+    io::{Read, Write},
+    iter::{Product, Sum},
+    mem::transmute,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, FieldSerde, FieldSerdeResult, SimdField};
+use rand::RngCore;
+
+use crate::goldilocks::{Goldilocks, EPSILON, GOLDILOCKS_MOD};
+
+const GOLDILOCKS_PACK_SIZE: usize = 8;
+const PACKED_MOD: uint64x2_t = unsafe { transmute([GOLDILOCKS_MOD; 2]) };
+const PACKED_EPSILON: uint64x2_t = unsafe { transmute([EPSILON; 2]) };
+const PACKED_0: uint64x2_t = unsafe { transmute([0u64; 2]) };
+const PACKED_1: uint64x2_t = unsafe { transmute([1u64; 2]) };
+const PACKED_INV_2: uint64x2_t = unsafe { transmute([Goldilocks::INV_2.v; 2]) };
+
+/// Map [0, 2^64) to [0, p); a single subtraction suffices as 2p > 2^64.
+#[inline(always)]
+unsafe fn mod_reduce_u64x2(x: uint64x2_t) -> uint64x2_t {
+    vsubq_u64(x, vandq_u64(vcgeq_u64(x, PACKED_MOD), PACKED_MOD))
+}
+
+/// NeonGoldilocks packs 8 Goldilocks elements and operates on them in parallel
+#[derive(Clone, Copy)]
+pub struct NeonGoldilocks {
+    pub v: [uint64x2_t; 4],
+}
+
+field_common!(NeonGoldilocks);
+
+impl NeonGoldilocks {
+    #[inline(always)]
+    pub(crate) fn pack_full(x: Goldilocks) -> NeonGoldilocks {
+        NeonGoldilocks {
+            v: unsafe { [vdupq_n_u64(x.v); 4] },
+        }
+    }
+}
+
+impl FieldSerde for NeonGoldilocks {
+    const SERIALIZED_SIZE: usize = (128 / 8) * 4;
+
+    #[inline(always)]
+    /// serialize self into bytes
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        let data = unsafe { transmute::<[uint64x2_t; 4], [u8; 64]>(self.v) };
+        writer.write_all(&data)?;
+        Ok(())
+    }
+
+    /// deserialize bytes into field
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut data = [0; 64];
+        reader.read_exact(&mut data)?;
+        unsafe {
+            let value = transmute::<[u8; 64], [uint64x2_t; 4]>(data);
+            Ok(NeonGoldilocks {
+                v: [
+                    mod_reduce_u64x2(value[0]),
+                    mod_reduce_u64x2(value[1]),
+                    mod_reduce_u64x2(value[2]),
+                    mod_reduce_u64x2(value[3]),
+                ],
+            })
+        }
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(reader: R) -> FieldSerdeResult<Self> {
+        Ok(Self::pack_full(
+            Goldilocks::try_deserialize_from_ecc_format(reader)?,
+        ))
+    }
+}
+
+impl Field for NeonGoldilocks {
+    const NAME: &'static str = "Neon Packed Goldilocks";
+
+    // size in bytes
+    const SIZE: usize = 128 / 8 * 4;
+
+    const FIELD_SIZE: usize = 64;
+
+    const ZERO: Self = Self { v: [PACKED_0; 4] };
+
+    const ONE: Self = Self { v: [PACKED_1; 4] };
+
+    const INV_2: Self = Self {
+        v: [PACKED_INV_2; 4],
+    };
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self { v: [PACKED_0; 4] }
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        unsafe {
+            transmute::<[uint64x2_t; 4], [u64; GOLDILOCKS_PACK_SIZE]>(self.v)
+                .iter()
+                .all(|&x| x == 0)
+        }
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self { v: [PACKED_1; 4] }
+    }
+
+    #[inline(always)]
+    // this function is for internal testing only. it is not
+    // a source for uniformly random field elements and
+    // should not be used in production.
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        let data: [Goldilocks; GOLDILOCKS_PACK_SIZE] =
+            std::array::from_fn(|_| Goldilocks::random_unsafe(&mut rng));
+        Self::pack(&data)
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        let data: [Goldilocks; GOLDILOCKS_PACK_SIZE] =
+            std::array::from_fn(|_| Goldilocks::random_bool(&mut rng));
+        Self::pack(&data)
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        let mut e = exponent;
+        let mut res = Self::one();
+        let mut t = *self;
+        while e != 0 {
+            if e & 1 == 1 {
+                res *= t;
+            }
+            t = t.square();
+            e >>= 1;
+        }
+        res
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        // slow, should not be used in production
+        let mut goldilocks_vec = self.unpack();
+        if goldilocks_vec.iter().any(|x| x.is_zero()) {
+            return None;
+        }
+
+        goldilocks_vec
+            .iter_mut()
+            .for_each(|x| *x = x.inv().unwrap()); // safe unwrap
+        Some(Self::pack(&goldilocks_vec))
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        Self::pack_full(Goldilocks::from_uniform_bytes(bytes))
+    }
+}
+
+impl SimdField for NeonGoldilocks {
+    type Scalar = Goldilocks;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        GOLDILOCKS_PACK_SIZE
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == GOLDILOCKS_PACK_SIZE);
+        let ret: [Self::Scalar; GOLDILOCKS_PACK_SIZE] = base_vec.try_into().unwrap();
+        unsafe { transmute(ret) }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        let ret =
+            unsafe { transmute::<[uint64x2_t; 4], [Self::Scalar; GOLDILOCKS_PACK_SIZE]>(self.v) };
+        ret.to_vec()
+    }
+}
+
+impl From<Goldilocks> for NeonGoldilocks {
+    #[inline(always)]
+    fn from(x: Goldilocks) -> Self {
+        NeonGoldilocks::pack_full(x)
+    }
+}
+
+impl Debug for NeonGoldilocks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = unsafe { transmute::<[uint64x2_t; 4], [u64; GOLDILOCKS_PACK_SIZE]>(self.v) };
+        // if all data is the same, print only one
+        if data.iter().all(|&x| x == data[0]) {
+            write!(f, "uint64x2_t<8 x {}>", data[0])
+        } else {
+            write!(f, "uint64x2_t<{:?}>", data)
+        }
+    }
+}
+
+impl Default for NeonGoldilocks {
+    fn default() -> Self {
+        NeonGoldilocks::zero()
+    }
+}
+
+impl PartialEq for NeonGoldilocks {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe {
+            transmute::<[uint64x2_t; 4], [u64; GOLDILOCKS_PACK_SIZE]>(self.v)
+                == transmute::<[uint64x2_t; 4], [u64; GOLDILOCKS_PACK_SIZE]>(other.v)
+        }
+    }
+}
+
+impl Mul<&Goldilocks> for NeonGoldilocks {
+    type Output = NeonGoldilocks;
+    #[inline(always)]
+    fn mul(self, rhs: &Goldilocks) -> Self::Output {
+        self * NeonGoldilocks::pack_full(*rhs)
+    }
+}
+
+impl Mul<Goldilocks> for NeonGoldilocks {
+    type Output = NeonGoldilocks;
+    #[inline(always)]
+    #[allow(clippy::op_ref)]
+    fn mul(self, rhs: Goldilocks) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Add<Goldilocks> for NeonGoldilocks {
+    type Output = NeonGoldilocks;
+    #[inline(always)]
+    fn add(self, rhs: Goldilocks) -> Self::Output {
+        self + NeonGoldilocks::pack_full(rhs)
+    }
+}
+
+impl From<u32> for NeonGoldilocks {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        NeonGoldilocks::pack_full(Goldilocks::from(x))
+    }
+}
+
+impl Neg for NeonGoldilocks {
+    type Output = NeonGoldilocks;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        NeonGoldilocks::zero() - self
+    }
+}
+
+#[inline(always)]
+unsafe fn add_u64x2(a: uint64x2_t, b: uint64x2_t) -> uint64x2_t {
+    let sum = vaddq_u64(a, b);
+    // on overflow, add 2^64 mod p = EPSILON; the result is then already canonical
+    let overflow = vcltq_u64(sum, a);
+    mod_reduce_u64x2(vaddq_u64(sum, vandq_u64(overflow, PACKED_EPSILON)))
+}
+
+#[inline(always)]
+unsafe fn sub_u64x2(a: uint64x2_t, b: uint64x2_t) -> uint64x2_t {
+    let diff = vsubq_u64(a, b);
+    // on borrow, subtract 2^64 mod p = EPSILON
+    let borrow = vcltq_u64(a, b);
+    vsubq_u64(diff, vandq_u64(borrow, PACKED_EPSILON))
+}
+
+#[inline(always)]
+fn add_internal(a: &NeonGoldilocks, b: &NeonGoldilocks) -> NeonGoldilocks {
+    NeonGoldilocks {
+        v: unsafe {
+            [
+                add_u64x2(a.v[0], b.v[0]),
+                add_u64x2(a.v[1], b.v[1]),
+                add_u64x2(a.v[2], b.v[2]),
+                add_u64x2(a.v[3], b.v[3]),
+            ]
+        },
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &NeonGoldilocks, b: &NeonGoldilocks) -> NeonGoldilocks {
+    NeonGoldilocks {
+        v: unsafe {
+            [
+                sub_u64x2(a.v[0], b.v[0]),
+                sub_u64x2(a.v[1], b.v[1]),
+                sub_u64x2(a.v[2], b.v[2]),
+                sub_u64x2(a.v[3], b.v[3]),
+            ]
+        },
+    }
+}
+
+// NEON has no 64-bit lane multiplication, while aarch64 has a fast scalar
+// 64 x 64 -> 128 bits multiplication (mul + umulh), so multiply lane by lane.
+#[inline(always)]
+fn mul_internal(a: &NeonGoldilocks, b: &NeonGoldilocks) -> NeonGoldilocks {
+    unsafe {
+        let a = transmute::<[uint64x2_t; 4], [Goldilocks; GOLDILOCKS_PACK_SIZE]>(a.v);
+        let b = transmute::<[uint64x2_t; 4], [Goldilocks; GOLDILOCKS_PACK_SIZE]>(b.v);
+        let res: [Goldilocks; GOLDILOCKS_PACK_SIZE] = std::array::from_fn(|i| a[i] * b[i]);
+        NeonGoldilocks {
+            v: transmute::<[Goldilocks; GOLDILOCKS_PACK_SIZE], [uint64x2_t; 4]>(res),
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::{
+    fmt::Debug,
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, FieldSerde, FieldSerdeResult, SimdField};
+use rand::RngCore;
+
+use crate::goldilocks::Goldilocks;
+
+const GOLDILOCKS_PACK_SIZE: usize = 8;
+
+/// Portable Goldilocksx8, used when no SIMD backend applies.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct ScalarGoldilocks {
+    pub v: [Goldilocks; GOLDILOCKS_PACK_SIZE],
+}
+
+impl ScalarGoldilocks {
+    #[inline(always)]
+    pub(crate) fn pack_full(x: Goldilocks) -> ScalarGoldilocks {
+        ScalarGoldilocks {
+            v: [x; GOLDILOCKS_PACK_SIZE],
+        }
+    }
+}
+
+field_common!(ScalarGoldilocks);
+
+impl FieldSerde for ScalarGoldilocks {
+    const SERIALIZED_SIZE: usize = 512 / 8;
+
+    #[inline(always)]
+    /// serialize self into bytes
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.v
+            .iter()
+            .try_for_each(|x| x.serialize_into(&mut writer))
+    }
+
+    /// deserialize bytes into field
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut v = [Goldilocks::ZERO; GOLDILOCKS_PACK_SIZE];
+        for x in v.iter_mut() {
+            *x = Goldilocks::deserialize_from(&mut reader)?;
+        }
+        Ok(ScalarGoldilocks { v })
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(reader: R) -> FieldSerdeResult<Self> {
+        Ok(Self::pack_full(
+            Goldilocks::try_deserialize_from_ecc_format(reader)?,
+        ))
+    }
+}
+
+impl Field for ScalarGoldilocks {
+    const NAME: &'static str = "Scalar Packed Goldilocks";
+
+    // size in bytes
+    const SIZE: usize = 512 / 8;
+
+    const ZERO: Self = Self {
+        v: [Goldilocks::ZERO; GOLDILOCKS_PACK_SIZE],
+    };
+
+    const ONE: Self = Self {
+        v: [Goldilocks::ONE; GOLDILOCKS_PACK_SIZE],
+    };
+
+    const INV_2: Self = Self {
+        v: [Goldilocks::INV_2; GOLDILOCKS_PACK_SIZE],
+    };
+
+    const FIELD_SIZE: usize = 64;
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|x| x.is_zero())
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline(always)]
+    // this function is for internal testing only. it is not
+    // a source for uniformly random field elements and
+    // should not be used in production.
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        ScalarGoldilocks {
+            v: std::array::from_fn(|_| Goldilocks::random_unsafe(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        ScalarGoldilocks {
+            v: std::array::from_fn(|_| Goldilocks::random_bool(&mut rng)),
+        }
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        ScalarGoldilocks {
+            v: self.v.map(|x| x.exp(exponent)),
+        }
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        if self.v.iter().any(|x| x.is_zero()) {
+            return None;
+        }
+        Some(ScalarGoldilocks {
+            v: self.v.map(|x| x.inv().unwrap()), // safe unwrap
+        })
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        Self::pack_full(Goldilocks::from_uniform_bytes(bytes))
+    }
+}
+
+impl SimdField for ScalarGoldilocks {
+    type Scalar = Goldilocks;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        GOLDILOCKS_PACK_SIZE
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == GOLDILOCKS_PACK_SIZE);
+        ScalarGoldilocks {
+            v: base_vec.try_into().unwrap(),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.to_vec()
+    }
+}
+
+impl From<Goldilocks> for ScalarGoldilocks {
+    #[inline(always)]
+    fn from(x: Goldilocks) -> Self {
+        ScalarGoldilocks::pack_full(x)
+    }
+}
+
+impl Debug for ScalarGoldilocks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = self.v.map(|x| x.v);
+        // if all data is the same, print only one
+        if data.iter().all(|&x| x == data[0]) {
+            write!(f, "u64x8<8 x {}>", data[0])
+        } else {
+            write!(f, "u64x8<{:?}>", data)
+        }
+    }
+}
+
+impl Mul<&Goldilocks> for ScalarGoldilocks {
+    type Output = ScalarGoldilocks;
+
+    #[inline(always)]
+    fn mul(self, rhs: &Goldilocks) -> Self::Output {
+        ScalarGoldilocks {
+            v: self.v.map(|x| x * rhs),
+        }
+    }
+}
+
+impl Mul<Goldilocks> for ScalarGoldilocks {
+    type Output = ScalarGoldilocks;
+    #[inline(always)]
+    #[allow(clippy::op_ref)]
+    fn mul(self, rhs: Goldilocks) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Add<Goldilocks> for ScalarGoldilocks {
+    type Output = ScalarGoldilocks;
+    #[inline(always)]
+    fn add(self, rhs: Goldilocks) -> Self::Output {
+        ScalarGoldilocks {
+            v: self.v.map(|x| x + rhs),
+        }
+    }
+}
+
+impl From<u32> for ScalarGoldilocks {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        ScalarGoldilocks::pack_full(Goldilocks::from(x))
+    }
+}
+
+impl Neg for ScalarGoldilocks {
+    type Output = ScalarGoldilocks;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        ScalarGoldilocks {
+            v: self.v.map(|x| -x),
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &ScalarGoldilocks, b: &ScalarGoldilocks) -> ScalarGoldilocks {
+    ScalarGoldilocks {
+        v: std::array::from_fn(|i| a.v[i] + b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &ScalarGoldilocks, b: &ScalarGoldilocks) -> ScalarGoldilocks {
+    ScalarGoldilocks {
+        v: std::array::from_fn(|i| a.v[i] - b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn mul_internal(a: &ScalarGoldilocks, b: &ScalarGoldilocks) -> ScalarGoldilocks {
+    ScalarGoldilocks {
+        v: std::array::from_fn(|i| a.v[i] * b.v[i]),
+    }
+}
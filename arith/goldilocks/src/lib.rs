@@ -1,14 +1,20 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+#![cfg_attr(target_arch = "x86_64", feature(stdarch_x86_avx512))]
+
+/// Goldilocks, i.e., the prime field of order 2^64 - 2^32 + 1
+mod goldilocks;
+pub use goldilocks::{Goldilocks, GOLDILOCKS_MOD};
+
+/// SIMDx8 for Goldilocks
+mod goldilocksx8;
+pub use goldilocksx8::Goldilocksx8;
+
+/// Degree 2 extension field for Goldilocks
+mod goldilocks_ext;
+pub use goldilocks_ext::GoldilocksExt2;
+
+/// SIMDx8 for Degree 2 extension field for Goldilocks
+mod goldilocks_ext2x8;
+pub use goldilocks_ext2x8::GoldilocksExt2x8;
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+mod tests;
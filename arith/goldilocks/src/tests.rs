@@ -0,0 +1,169 @@
+use std::io::Cursor;
+
+use arith::{
+    random_extension_field_tests, random_field_tests, random_inversion_tests,
+    random_simd_field_tests, Field, FieldSerde, SimdField,
+};
+use ark_std::test_rng;
+
+use crate::goldilocksx8::goldilocks_scalar::ScalarGoldilocks;
+use crate::{Goldilocks, GoldilocksExt2, GoldilocksExt2x8, Goldilocksx8, GOLDILOCKS_MOD};
+
+#[test]
+fn test_base_field() {
+    random_field_tests::<Goldilocks>("Goldilocks".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<Goldilocks, _>(&mut rng, "Goldilocks".to_string());
+
+    // edge cases of the reductions
+    let minus_one = Goldilocks {
+        v: GOLDILOCKS_MOD - 1,
+    };
+    assert_eq!(minus_one, -Goldilocks::one());
+    assert_eq!(minus_one + Goldilocks::one(), Goldilocks::zero());
+    assert_eq!(minus_one + minus_one, -Goldilocks::from(2u32));
+    assert_eq!(Goldilocks::zero() - Goldilocks::one(), minus_one);
+    assert_eq!(minus_one * minus_one, Goldilocks::one());
+    assert_eq!(Goldilocks::from(u64::MAX), Goldilocks::from(u32::MAX - 1));
+    assert_eq!(
+        Goldilocks::INV_2 * Goldilocks::from(2u32),
+        Goldilocks::one()
+    );
+
+    // the ecc format pads to 32 bytes
+    let mut bytes = vec![];
+    minus_one.serialize_into_ecc_format(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 32);
+    let read = Goldilocks::try_deserialize_from_ecc_format(Cursor::new(&bytes)).unwrap();
+    assert_eq!(read, minus_one);
+}
+
+#[test]
+fn test_simd_field() {
+    random_field_tests::<Goldilocksx8>("Vectorized Goldilocks".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<Goldilocksx8, _>(&mut rng, "Vectorized Goldilocks".to_string());
+
+    random_simd_field_tests::<Goldilocksx8>("Vectorized Goldilocks".to_string());
+
+    let a = Goldilocksx8::from(256 + 2);
+    let mut buffer = vec![];
+    assert!(a.serialize_into(&mut buffer).is_ok());
+    let mut cursor = Cursor::new(buffer);
+    let b = Goldilocksx8::deserialize_from(&mut cursor);
+    assert!(b.is_ok());
+    let b = b.unwrap();
+    assert_eq!(a, b);
+}
+
+/// The packed arithmetic must agree lane by lane with the scalar one
+#[test]
+fn test_simd_against_scalar() {
+    let mut rng = test_rng();
+    let minus_one = Goldilocks {
+        v: GOLDILOCKS_MOD - 1,
+    };
+    for _ in 0..1000 {
+        let mut a = Goldilocksx8::random_unsafe(&mut rng).unpack();
+        let mut b = Goldilocksx8::random_unsafe(&mut rng).unpack();
+        a[0] = minus_one;
+        b[0] = minus_one;
+        b[1] = Goldilocks::zero();
+        let a_simd = Goldilocksx8::pack(&a);
+        let b_simd = Goldilocksx8::pack(&b);
+
+        let sum = (a_simd + b_simd).unpack();
+        let diff = (a_simd - b_simd).unpack();
+        let prod = (a_simd * b_simd).unpack();
+        let neg = (-a_simd).unpack();
+        for i in 0..Goldilocksx8::pack_size() {
+            assert_eq!(sum[i], a[i] + b[i]);
+            assert_eq!(diff[i], a[i] - b[i]);
+            assert_eq!(prod[i], a[i] * b[i]);
+            assert_eq!(neg[i], -a[i]);
+        }
+    }
+}
+
+#[test]
+fn test_scalar_simd_field() {
+    random_field_tests::<ScalarGoldilocks>("Scalar Vectorized Goldilocks".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<ScalarGoldilocks, _>(
+        &mut rng,
+        "Scalar Vectorized Goldilocks".to_string(),
+    );
+
+    random_simd_field_tests::<ScalarGoldilocks>("Scalar Vectorized Goldilocks".to_string());
+}
+
+/// The selected Goldilocksx8 backend must agree with the portable one
+#[test]
+fn test_simd_against_portable() {
+    let mut rng = test_rng();
+    for _ in 0..1000 {
+        let a = Goldilocksx8::random_unsafe(&mut rng).unpack();
+        let b = Goldilocksx8::random_unsafe(&mut rng).unpack();
+        let (a_simd, b_simd) = (Goldilocksx8::pack(&a), Goldilocksx8::pack(&b));
+        let (a_scalar, b_scalar) = (ScalarGoldilocks::pack(&a), ScalarGoldilocks::pack(&b));
+
+        assert_eq!(
+            (a_simd * b_simd - a_simd).unpack(),
+            (a_scalar * b_scalar - a_scalar).unpack()
+        );
+        assert_eq!(a_simd.square().unpack(), a_scalar.square().unpack());
+        assert_eq!((a_simd * b[3]).unpack(), (a_scalar * b[3]).unpack());
+
+        let mut buffer = vec![];
+        a_simd.serialize_into(&mut buffer).unwrap();
+        let mut scalar_buffer = vec![];
+        a_scalar.serialize_into(&mut scalar_buffer).unwrap();
+        assert_eq!(buffer, scalar_buffer);
+    }
+}
+
+#[test]
+fn test_ext_field() {
+    random_field_tests::<GoldilocksExt2>("Goldilocks Ext2".to_string());
+    random_extension_field_tests::<GoldilocksExt2>("Goldilocks Ext2".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<GoldilocksExt2, _>(&mut rng, "Goldilocks Ext2".to_string());
+
+    random_field_tests::<GoldilocksExt2x8>("Simd Goldilocks Ext2".to_string());
+    random_extension_field_tests::<GoldilocksExt2x8>("Simd Goldilocks Ext2".to_string());
+    random_simd_field_tests::<GoldilocksExt2x8>("Simd Goldilocks Ext2".to_string());
+}
+
+/// Compare to precomputed test vectors
+#[test]
+fn test_vectors() {
+    let a = GoldilocksExt2 {
+        v: [Goldilocks::from(1u32), Goldilocks::from(2u32)],
+    };
+    let b = GoldilocksExt2 {
+        v: [Goldilocks::from(3u32), Goldilocks::from(4u32)],
+    };
+    let expected_prod = GoldilocksExt2 {
+        v: [Goldilocks::from(59u32), Goldilocks::from(10u32)],
+    };
+    assert_eq!(expected_prod, a * b);
+
+    let expected_inv = GoldilocksExt2 {
+        v: [
+            Goldilocks::from(4782489203181558898u64),
+            Goldilocks::from(8881765663051466525u64),
+        ],
+    };
+    assert_eq!(expected_inv, a.inv().unwrap());
+    let a_pow_11 = GoldilocksExt2 {
+        v: [
+            Goldilocks::from(301134373u32),
+            Goldilocks::from(117254894u32),
+        ],
+    };
+    assert_eq!(a_pow_11, a.exp(11));
+}
@@ -151,12 +151,25 @@ impl From<M31Ext3> for M31Ext3x16 {
 }
 
 impl Field for M31Ext3x16 {
-    #[cfg(target_arch = "x86_64")]
+    #[cfg(all(
+        target_arch = "x86_64",
+        target_feature = "avx2",
+        not(feature = "force-scalar")
+    ))]
     const NAME: &'static str = "AVX Vectorized Mersenne 31 Extension 3";
 
-    #[cfg(target_arch = "aarch64")]
+    #[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
     const NAME: &'static str = "Neon Vectorized Mersenne 31 Extension 3";
 
+    #[cfg(any(
+        feature = "force-scalar",
+        not(any(
+            target_arch = "aarch64",
+            all(target_arch = "x86_64", target_feature = "avx2")
+        ))
+    ))]
+    const NAME: &'static str = "Scalar Vectorized Mersenne 31 Extension 3";
+
     const SIZE: usize = 512 / 8 * 3;
 
     const FIELD_SIZE: usize = 32 * 3;
@@ -1,19 +1,57 @@
 // A M31x16 stores 512 bits of data.
 // With AVX it stores a single __m512i element.
 // With NEON it stores four uint32x4_t elements.
+// Otherwise it stores sixteen M31 elements.
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
 mod m31_neon;
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(target_arch = "aarch64", not(feature = "force-scalar")))]
 pub type M31x16 = m31_neon::NeonM31;
 
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx512f",
+    not(feature = "force-scalar")
+))]
 mod m31_avx512;
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx512f",
+    not(feature = "force-scalar")
+))]
 pub type M31x16 = m31_avx512::AVXM31;
 
-// Fallback, use avx2
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "force-scalar")
+))]
 mod m31_avx256;
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "avx2",
+    not(target_feature = "avx512f"),
+    not(feature = "force-scalar")
+))]
 pub type M31x16 = m31_avx256::AVXM31;
+
+// Fallback, no SIMD backend applies or the scalar one is forced.
+// Always built for tests, as the reference for the SIMD backends.
+#[cfg(any(
+    test,
+    feature = "force-scalar",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub(crate) mod m31_scalar;
+#[cfg(any(
+    feature = "force-scalar",
+    not(any(
+        target_arch = "aarch64",
+        all(target_arch = "x86_64", target_feature = "avx2")
+    ))
+))]
+pub type M31x16 = m31_scalar::ScalarM31;
@@ -0,0 +1,284 @@
+use std::{
+    fmt::Debug,
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, FieldSerde, FieldSerdeResult, SimdField};
+use rand::RngCore;
+
+use crate::m31::{M31, M31_MOD};
+
+const M31_PACK_SIZE: usize = 16;
+
+/// Portable M31x16, used when no SIMD backend applies.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct ScalarM31 {
+    pub v: [M31; M31_PACK_SIZE],
+}
+
+impl ScalarM31 {
+    #[inline(always)]
+    pub(crate) fn pack_full(x: M31) -> ScalarM31 {
+        ScalarM31 {
+            v: [x; M31_PACK_SIZE],
+        }
+    }
+}
+
+field_common!(ScalarM31);
+
+impl FieldSerde for ScalarM31 {
+    const SERIALIZED_SIZE: usize = 512 / 8;
+
+    #[inline(always)]
+    /// serialize self into bytes
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.v
+            .iter()
+            .try_for_each(|x| x.serialize_into(&mut writer))
+    }
+
+    /// deserialize bytes into field
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut v = [M31::ZERO; M31_PACK_SIZE];
+        for x in v.iter_mut() {
+            *x = M31::deserialize_from(&mut reader)?;
+        }
+        Ok(ScalarM31 { v })
+    }
+
+    #[inline(always)]
+    fn try_deserialize_from_ecc_format<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
+        let mut buf = [0u8; 32];
+        reader.read_exact(&mut buf)?;
+        assert!(
+            buf.iter().skip(4).all(|&x| x == 0),
+            "non-zero byte found in witness byte"
+        );
+        Ok(Self::pack_full(
+            u32::from_le_bytes(buf[..4].try_into().unwrap()).into(),
+        ))
+    }
+}
+
+impl Field for ScalarM31 {
+    const NAME: &'static str = "Scalar Packed Mersenne 31";
+
+    // size in bytes
+    const SIZE: usize = 512 / 8;
+
+    const ZERO: Self = Self {
+        v: [M31::ZERO; M31_PACK_SIZE],
+    };
+
+    const ONE: Self = Self {
+        v: [M31::ONE; M31_PACK_SIZE],
+    };
+
+    const INV_2: Self = Self {
+        v: [M31::INV_2; M31_PACK_SIZE],
+    };
+
+    const FIELD_SIZE: usize = 32;
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|x| x.is_zero())
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline(always)]
+    // this function is for internal testing only. it is not
+    // a source for uniformly random field elements and
+    // should not be used in production.
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        ScalarM31 {
+            v: std::array::from_fn(|_| M31::random_unsafe(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        ScalarM31 {
+            v: std::array::from_fn(|_| M31::random_bool(&mut rng)),
+        }
+    }
+
+    fn exp(&self, exponent: u128) -> Self {
+        ScalarM31 {
+            v: self.v.map(|x| x.exp(exponent)),
+        }
+    }
+
+    #[inline(always)]
+    fn double(&self) -> Self {
+        self.mul_by_2()
+    }
+
+    #[inline(always)]
+    fn mul_by_3(&self) -> Self {
+        ScalarM31 {
+            v: self.v.map(|x| x.mul_by_3()),
+        }
+    }
+
+    #[inline(always)]
+    fn mul_by_5(&self) -> Self {
+        ScalarM31 {
+            v: self.v.map(|x| x.mul_by_5()),
+        }
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        if self.v.iter().any(|x| x.is_zero()) {
+            return None;
+        }
+        Some(ScalarM31 {
+            v: self.v.map(|x| x.inv().unwrap()), // safe unwrap
+        })
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline]
+    fn from_uniform_bytes(bytes: &[u8; 32]) -> Self {
+        Self::pack_full(M31::from_uniform_bytes(bytes))
+    }
+}
+
+impl SimdField for ScalarM31 {
+    type Scalar = M31;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_size() -> usize {
+        M31_PACK_SIZE
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == M31_PACK_SIZE);
+        ScalarM31 {
+            v: base_vec.try_into().unwrap(),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.to_vec()
+    }
+}
+
+impl From<M31> for ScalarM31 {
+    #[inline(always)]
+    fn from(x: M31) -> Self {
+        ScalarM31::pack_full(x)
+    }
+}
+
+impl Debug for ScalarM31 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = self.v.map(|x| x.v);
+        // if all data is the same, print only one
+        if data.iter().all(|&x| x == data[0]) {
+            write!(
+                f,
+                "u32x16<16 x {}>",
+                if M31_MOD - data[0] > 1024 {
+                    format!("{}", data[0])
+                } else {
+                    format!("-{}", M31_MOD - data[0])
+                }
+            )
+        } else {
+            write!(f, "u32x16<{:?}>", data)
+        }
+    }
+}
+
+impl Mul<&M31> for ScalarM31 {
+    type Output = ScalarM31;
+
+    #[inline(always)]
+    fn mul(self, rhs: &M31) -> Self::Output {
+        ScalarM31 {
+            v: self.v.map(|x| x * rhs),
+        }
+    }
+}
+
+impl Mul<M31> for ScalarM31 {
+    type Output = ScalarM31;
+    #[inline(always)]
+    #[allow(clippy::op_ref)]
+    fn mul(self, rhs: M31) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Add<M31> for ScalarM31 {
+    type Output = ScalarM31;
+    #[inline(always)]
+    fn add(self, rhs: M31) -> Self::Output {
+        ScalarM31 {
+            v: self.v.map(|x| x + rhs),
+        }
+    }
+}
+
+impl From<u32> for ScalarM31 {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        ScalarM31::pack_full(M31::from(x))
+    }
+}
+
+impl Neg for ScalarM31 {
+    type Output = ScalarM31;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        ScalarM31 {
+            v: self.v.map(|x| -x),
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &ScalarM31, b: &ScalarM31) -> ScalarM31 {
+    ScalarM31 {
+        v: std::array::from_fn(|i| a.v[i] + b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &ScalarM31, b: &ScalarM31) -> ScalarM31 {
+    ScalarM31 {
+        v: std::array::from_fn(|i| a.v[i] - b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn mul_internal(a: &ScalarM31, b: &ScalarM31) -> ScalarM31 {
+    ScalarM31 {
+        v: std::array::from_fn(|i| a.v[i] * b.v[i]),
+    }
+}
@@ -1,18 +1,20 @@
 use std::io::Cursor;
 
-use arith::Field;
 use arith::{
     random_extension_field_tests, random_field_tests, random_inversion_tests,
-    random_simd_field_tests, FieldSerde,
+    random_simd_field_tests, Field, FieldSerde, SimdField,
 };
 use ark_std::test_rng;
 
+use crate::m31x16::m31_scalar::ScalarM31;
 use crate::M31Ext3;
 use crate::M31Ext3x16;
 use crate::{M31x16, M31};
 
 fn get_avx_version() -> &'static str {
-    if cfg!(all(target_arch = "x86_64", target_feature = "avx512f")) {
+    if cfg!(feature = "force-scalar") {
+        return "No AVX (Fallback)";
+    } else if cfg!(all(target_arch = "x86_64", target_feature = "avx512f")) {
         return "AVX512";
     } else if cfg!(all(
         target_arch = "x86_64",
@@ -20,10 +22,12 @@ fn get_avx_version() -> &'static str {
         target_feature = "avx2"
     )) {
         return "AVX2 (256-bit)";
+    } else if cfg!(target_arch = "x86_64") {
+        return "No AVX (Fallback)";
     } else if cfg!(target_arch = "aarch64") {
         return "arm64";
     }
-    "Unknown"
+    "Not x86_64 architecture"
 }
 
 #[test]
@@ -68,6 +72,44 @@ fn test_simd_field() {
     assert_eq!(a, b);
 }
 
+#[test]
+fn test_scalar_simd_field() {
+    random_field_tests::<ScalarM31>("Scalar Vectorized M31".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<ScalarM31, _>(&mut rng, "Scalar Vectorized M31".to_string());
+
+    random_simd_field_tests::<ScalarM31>("Scalar Vectorized M31".to_string());
+}
+
+/// The selected M31x16 backend must agree with the portable one
+#[test]
+fn test_simd_against_scalar() {
+    let mut rng = test_rng();
+    for _ in 0..1000 {
+        let mut a = M31x16::random_unsafe(&mut rng).unpack();
+        let mut b = M31x16::random_unsafe(&mut rng).unpack();
+        a[0] = -M31::one();
+        b[0] = -M31::one();
+        b[1] = M31::zero();
+        let (a_simd, b_simd) = (M31x16::pack(&a), M31x16::pack(&b));
+        let (a_scalar, b_scalar) = (ScalarM31::pack(&a), ScalarM31::pack(&b));
+
+        assert_eq!((a_simd + b_simd).unpack(), (a_scalar + b_scalar).unpack());
+        assert_eq!((a_simd - b_simd).unpack(), (a_scalar - b_scalar).unpack());
+        assert_eq!((a_simd * b_simd).unpack(), (a_scalar * b_scalar).unpack());
+        assert_eq!((-a_simd).unpack(), (-a_scalar).unpack());
+        assert_eq!(a_simd.mul_by_5().unpack(), a_scalar.mul_by_5().unpack());
+        assert_eq!((a_simd * b[2]).unpack(), (a_scalar * b[2]).unpack());
+
+        let mut buffer = vec![];
+        a_simd.serialize_into(&mut buffer).unwrap();
+        let mut scalar_buffer = vec![];
+        a_scalar.serialize_into(&mut scalar_buffer).unwrap();
+        assert_eq!(buffer, scalar_buffer);
+    }
+}
+
 #[test]
 fn test_ext_field() {
     random_field_tests::<M31Ext3>("M31 Ext3".to_string());
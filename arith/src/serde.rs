@@ -24,6 +24,12 @@ pub trait FieldSerde: Sized {
 
     /// deserialize bytes into field following ecc format
     fn try_deserialize_from_ecc_format<R: Read>(reader: R) -> FieldSerdeResult<Self>;
+
+    /// serialize self into bytes following ecc format
+    #[inline(always)]
+    fn serialize_into_ecc_format<W: Write>(&self, writer: W) -> FieldSerdeResult<()> {
+        self.serialize_into(writer)
+    }
 }
 
 macro_rules! field_serde_for_integer {
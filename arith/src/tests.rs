@@ -21,7 +21,7 @@ fn test_mm256_const_init() {
 
     let all_equal = unsafe {
         let x = _mm256_set1_epi32(1);
-        let y = transmute::<_, __m256i>([1, 1, 1, 1, 1, 1, 1, 1]);
+        let y = transmute::<[i32; 8], __m256i>([1, 1, 1, 1, 1, 1, 1, 1]);
         let cmp = _mm256_cmpeq_epi32(x, y);
         _mm256_testc_si256(cmp, _mm256_set1_epi32(-1))
     };
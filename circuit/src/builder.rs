@@ -0,0 +1,308 @@
+use std::{cmp::max, collections::HashMap};
+
+use arith::Field;
+use config::GKRConfig;
+
+use crate::*;
+
+/// A wire of a circuit under construction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Wire(usize);
+
+/// The coefficient of a gate.
+#[derive(Debug, Clone)]
+pub enum Coef<C: GKRConfig> {
+    Constant(C::CircuitField),
+    /// Filled in by the prover from the transcript.
+    Random,
+    /// Only valid for `Term::Const`.
+    PublicInput(usize),
+}
+
+impl<C: GKRConfig> Coef<C> {
+    pub fn one() -> Self {
+        Coef::Constant(C::CircuitField::ONE)
+    }
+
+    fn split(&self) -> (CoefType, C::CircuitField) {
+        match self {
+            Coef::Constant(v) => (CoefType::Constant, *v),
+            Coef::Random => (CoefType::Random, C::CircuitField::ZERO),
+            Coef::PublicInput(idx) => (CoefType::PublicInput(*idx), C::CircuitField::ZERO),
+        }
+    }
+}
+
+/// A term of the sum defining a wire, one gate once built.
+#[derive(Debug, Clone)]
+pub enum Term<C: GKRConfig> {
+    Mul(Wire, Wire, Coef<C>),
+    Add(Wire, Coef<C>),
+    Const(Coef<C>),
+    Pow5(Wire, Coef<C>),
+}
+
+impl<C: GKRConfig> Term<C> {
+    fn inputs(&self) -> Vec<Wire> {
+        match self {
+            Term::Mul(a, b, _) => vec![*a, *b],
+            Term::Add(a, _) | Term::Pow5(a, _) => vec![*a],
+            Term::Const(_) => vec![],
+        }
+    }
+
+    fn map_inputs(self, mut f: impl FnMut(Wire) -> Wire) -> Self {
+        match self {
+            Term::Mul(a, b, coef) => Term::Mul(f(a), f(b), coef),
+            Term::Add(a, coef) => Term::Add(f(a), coef),
+            Term::Const(coef) => Term::Const(coef),
+            Term::Pow5(a, coef) => Term::Pow5(f(a), coef),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Node<C: GKRConfig> {
+    depth: usize,
+    // the inputs of the terms are all at depth - 1
+    terms: Vec<Term<C>>,
+}
+
+/// Build a layered circuit from its inputs and gates.
+///
+/// Each wire is placed one layer after the deepest of its inputs, and shallower inputs are
+/// relayed with add-by-one gates. Wire ids are assigned at build time: the inputs in their
+/// declaration order, the zero-asserted wires then the other outputs first in the output layer.
+/// Every layer, including the input layer, is padded to a power of two, at least 2.
+///
+/// A circuit with pow5 terms can only be proven with GKR^2, which has uni gates and constant
+/// gates but no mul or add gates: its add terms and relays become pow1 gates, and mul terms
+/// are rejected at build time. Other circuits are proven with vanilla GKR.
+#[derive(Debug)]
+pub struct CircuitBuilder<C: GKRConfig> {
+    nodes: Vec<Node<C>>,
+    num_public_inputs: usize,
+    relays: HashMap<(Wire, usize), Wire>,
+    zero_outputs: Vec<Wire>,
+    outputs: Vec<Wire>,
+}
+
+impl<C: GKRConfig> Default for CircuitBuilder<C> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            num_public_inputs: 0,
+            relays: HashMap::new(),
+            zero_outputs: vec![],
+            outputs: vec![],
+        }
+    }
+}
+
+impl<C: GKRConfig> CircuitBuilder<C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a private input, the next one of each witness instance.
+    pub fn input(&mut self) -> Wire {
+        self.push_node(0, vec![])
+    }
+
+    pub fn inputs(&mut self, n: usize) -> Vec<Wire> {
+        (0..n).map(|_| self.input()).collect()
+    }
+
+    /// Declare a public input, to be used with `Coef::PublicInput`.
+    pub fn public_input(&mut self) -> usize {
+        self.num_public_inputs += 1;
+        self.num_public_inputs - 1
+    }
+
+    pub fn depth(&self, wire: Wire) -> usize {
+        self.nodes[wire.0].depth
+    }
+
+    /// A wire equal to the sum of the terms.
+    ///
+    /// Panics if a public input coefficient is used on a term with inputs.
+    pub fn sum(&mut self, terms: Vec<Term<C>>) -> Wire {
+        let mut depth = 1;
+        for term in &terms {
+            if let Term::Mul(.., coef) | Term::Add(_, coef) | Term::Pow5(_, coef) = term {
+                assert!(
+                    !matches!(coef, Coef::PublicInput(_)),
+                    "public input coefficients are only supported by constant gates"
+                );
+            }
+            for wire in term.inputs() {
+                depth = max(depth, self.depth(wire) + 1);
+            }
+        }
+
+        let terms = terms
+            .into_iter()
+            .map(|term| term.map_inputs(|wire| self.relay(wire, depth - 1)))
+            .collect();
+        self.push_node(depth, terms)
+    }
+
+    pub fn mul(&mut self, a: Wire, b: Wire) -> Wire {
+        self.sum(vec![Term::Mul(a, b, Coef::one())])
+    }
+
+    pub fn add(&mut self, a: Wire, b: Wire) -> Wire {
+        self.sum(vec![Term::Add(a, Coef::one()), Term::Add(b, Coef::one())])
+    }
+
+    pub fn scale(&mut self, a: Wire, coef: Coef<C>) -> Wire {
+        self.sum(vec![Term::Add(a, coef)])
+    }
+
+    pub fn constant(&mut self, coef: Coef<C>) -> Wire {
+        self.sum(vec![Term::Const(coef)])
+    }
+
+    /// `a^5`, see the GKR^2 layout above.
+    pub fn pow5(&mut self, a: Wire) -> Wire {
+        self.sum(vec![Term::Pow5(a, Coef::one())])
+    }
+
+    /// Declare an output of the circuit.
+    pub fn output(&mut self, wire: Wire) {
+        self.outputs.push(wire);
+    }
+
+    /// Declare an output the prover checks to be zero, see `expected_num_output_zeros`.
+    pub fn assert_zero(&mut self, wire: Wire) {
+        self.zero_outputs.push(wire);
+    }
+
+    /// Build a circuit with one segment per layer.
+    ///
+    /// Fails with `Pow5WithMul` if pow5 and mul terms are mixed.
+    pub fn build_recursive(mut self) -> CircuitResult<RecursiveCircuit<C>> {
+        let terms = || self.nodes.iter().flat_map(|node| node.terms.iter());
+        let gkr_square = terms().any(|term| matches!(term, Term::Pow5(..)));
+        if gkr_square && terms().any(|term| matches!(term, Term::Mul(..))) {
+            return Err(CircuitError::Pow5WithMul);
+        }
+
+        let output_depth = self.nodes.iter().map(|node| node.depth).fold(1, max);
+
+        // relay the outputs to the output layer, and number them first, zero-asserted ones first
+        let mut layer_sizes = vec![0; output_depth + 1];
+        let mut ids = vec![None; self.nodes.len()];
+        let mut num_zero_outputs = 0;
+        let zero_outputs = std::mem::take(&mut self.zero_outputs);
+        let outputs = std::mem::take(&mut self.outputs);
+        for (i, wire) in zero_outputs.iter().chain(outputs.iter()).enumerate() {
+            let wire = self.relay(*wire, output_depth);
+            ids.resize(self.nodes.len(), None);
+            if ids[wire.0].is_none() {
+                ids[wire.0] = Some(layer_sizes[output_depth]);
+                layer_sizes[output_depth] += 1;
+                if i < zero_outputs.len() {
+                    num_zero_outputs += 1;
+                }
+            }
+        }
+        let num_outputs = layer_sizes[output_depth];
+
+        // then the other wires, layer by layer
+        for (id, node) in ids.iter_mut().zip(self.nodes.iter()) {
+            if id.is_none() {
+                *id = Some(layer_sizes[node.depth]);
+                layer_sizes[node.depth] += 1;
+            }
+        }
+        let ids = ids.into_iter().map(Option::unwrap).collect::<Vec<_>>();
+        let var_nums = layer_sizes
+            .iter()
+            .map(|&size| max(size.next_power_of_two().trailing_zeros() as usize, 1))
+            .collect::<Vec<_>>();
+
+        let mut segments = (0..output_depth)
+            .map(|depth| Segment::<C> {
+                i_var_num: var_nums[depth],
+                o_var_num: var_nums[depth + 1],
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if node.depth == 0 {
+                continue;
+            }
+            let seg = &mut segments[node.depth - 1];
+            let o_id = ids[i];
+            for term in &node.terms {
+                match term {
+                    Term::Mul(a, b, coef) => {
+                        seg.gate_muls
+                            .push(gate([ids[a.0], ids[b.0]], o_id, coef, 0))
+                    }
+                    Term::Add(a, coef) if gkr_square => {
+                        seg.gate_uni
+                            .push(gate([ids[a.0]], o_id, coef, POW1_GATE_TYPE))
+                    }
+                    Term::Add(a, coef) => seg.gate_adds.push(gate([ids[a.0]], o_id, coef, 0)),
+                    Term::Const(coef) => seg.gate_consts.push(gate([], o_id, coef, 0)),
+                    Term::Pow5(a, coef) => {
+                        seg.gate_uni
+                            .push(gate([ids[a.0]], o_id, coef, POW5_GATE_TYPE))
+                    }
+                }
+            }
+        }
+
+        Ok(RecursiveCircuit {
+            num_public_inputs: self.num_public_inputs,
+            num_outputs,
+            expected_num_output_zeros: num_zero_outputs,
+            segments,
+            layers: (0..output_depth).collect(),
+        })
+    }
+
+    /// Build a flattened circuit, ready to be proven.
+    pub fn build(self) -> CircuitResult<CircuitStructure<C>> {
+        Ok(self.build_recursive()?.flatten())
+    }
+
+    fn push_node(&mut self, depth: usize, terms: Vec<Term<C>>) -> Wire {
+        self.nodes.push(Node { depth, terms });
+        Wire(self.nodes.len() - 1)
+    }
+
+    /// The wire carrying the value of `wire` at the given depth, through add-by-one gates.
+    fn relay(&mut self, wire: Wire, depth: usize) -> Wire {
+        let wire_depth = self.depth(wire);
+        assert!(wire_depth <= depth);
+        if wire_depth == depth {
+            return wire;
+        }
+        if let Some(&relayed) = self.relays.get(&(wire, depth)) {
+            return relayed;
+        }
+        let prev = self.relay(wire, depth - 1);
+        let relayed = self.push_node(depth, vec![Term::Add(prev, Coef::one())]);
+        self.relays.insert((wire, depth), relayed);
+        relayed
+    }
+}
+
+fn gate<C: GKRConfig, const INPUT_NUM: usize>(
+    i_ids: [usize; INPUT_NUM],
+    o_id: usize,
+    coef: &Coef<C>,
+    gate_type: usize,
+) -> Gate<C, INPUT_NUM> {
+    let (coef_type, coef) = coef.split();
+    Gate {
+        i_ids,
+        o_id,
+        coef_type,
+        coef,
+        gate_type,
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+};
+
+use config::GKRConfig;
+use sha2::{Digest, Sha256};
+
+use crate::*;
+
+const FLAT_VERSION_NUM: usize = 3626606352488549446; // b'FLATCKT2'
+
+// var_num is a shift amount
+const MAX_VAR_NUM: usize = usize::BITS as usize - 1;
+
+/// SHA256 of a circuit file, identifying the source of a flattened circuit.
+pub fn circuit_source_hash(file_bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(file_bytes).into()
+}
+
+fn write_gates<C: GKRConfig, const INPUT_NUM: usize, W: Write>(
+    mut writer: W,
+    gates: &[Gate<C, INPUT_NUM>],
+) -> CircuitResult<()> {
+    write_usize(&mut writer, gates.len())?;
+    for gate in gates {
+        write_custom_gate(&mut writer, gate)?;
+    }
+    Ok(())
+}
+
+fn read_gates<C: GKRConfig, const INPUT_NUM: usize, R: Read>(
+    mut reader: R,
+) -> CircuitResult<Vec<Gate<C, INPUT_NUM>>> {
+    Ok(
+        Vec::<CustomGateWrapper<C, INPUT_NUM>>::deserialize_from(&mut reader)?
+            .into_iter()
+            .map(|wrapper| wrapper.custom_gate)
+            .collect(),
+    )
+}
+
+fn read_var_num<R: Read>(reader: R) -> CircuitResult<usize> {
+    let var_num = read_usize(reader)?;
+    if var_num > MAX_VAR_NUM {
+        return Err(CircuitError::InvalidVarNum(var_num));
+    }
+    Ok(var_num)
+}
+
+impl<C: GKRConfig> CircuitLayer<C> {
+    fn serialize_flat_into<W: Write>(&self, mut writer: W) -> CircuitResult<()> {
+        write_usize(&mut writer, self.input_var_num)?;
+        write_usize(&mut writer, self.output_var_num)?;
+        write_gates(&mut writer, &self.mul)?;
+        write_gates(&mut writer, &self.add)?;
+        write_gates(&mut writer, &self.const_)?;
+        write_gates(&mut writer, &self.uni)
+    }
+
+    fn deserialize_flat_from<R: Read>(mut reader: R) -> CircuitResult<Self> {
+        let input_var_num = read_var_num(&mut reader)?;
+        let output_var_num = read_var_num(&mut reader)?;
+        let layer = CircuitLayer {
+            input_var_num,
+            output_var_num,
+            mul: read_gates(&mut reader)?,
+            add: read_gates(&mut reader)?,
+            const_: read_gates(&mut reader)?,
+            uni: read_gates(&mut reader)?,
+            ..Default::default()
+        };
+
+        let (i_len, o_len) = (1 << input_var_num, 1 << output_var_num);
+        check_gate_ids(&layer.mul, i_len, o_len)?;
+        check_gate_ids(&layer.add, i_len, o_len)?;
+        check_gate_ids(&layer.const_, i_len, o_len)?;
+        check_gate_ids(&layer.uni, i_len, o_len)?;
+        Ok(layer)
+    }
+}
+
+impl<C: GKRConfig> CircuitStructure<C> {
+    /// Serialize the flattened circuit, tagged with the hash of the circuit file it comes from.
+    /// The random coefficients are recovered from the coefficient types of the gates.
+    pub fn serialize_flat_into<W: Write>(
+        &self,
+        mut writer: W,
+        source_hash: &[u8; 32],
+    ) -> CircuitResult<()> {
+        write_usize(&mut writer, FLAT_VERSION_NUM)?;
+        writer.write_all(source_hash)?;
+        self.serialize_flat_body_into(writer)
+    }
+
+    fn serialize_flat_body_into<W: Write>(&self, mut writer: W) -> CircuitResult<()> {
+        write_modulus::<C, _>(&mut writer)?;
+        write_usize(&mut writer, self.expected_num_output_zeros)?;
+        write_usize(&mut writer, self.num_public_inputs)?;
+
+        write_usize(&mut writer, self.layers.len())?;
+        for layer in &self.layers {
+            layer.serialize_flat_into(&mut writer)?;
+        }
+        Ok(())
+    }
+
+    /// SHA256 of the flattened circuit, identifying it whatever file it was loaded from.
+    /// Random coefficients only contribute their position, not the values filled in.
+    ///
+    /// It is not cached: the fields of the circuit are public, and a stale digest would bind
+    /// the transcript to another circuit than the one proven. Hash it once per proof.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut bytes = vec![];
+        self.serialize_flat_body_into(&mut bytes).unwrap();
+        Sha256::digest(&bytes).into()
+    }
+
+    /// Read a flattened circuit in one pass, failing with `StaleCache` if it does not come
+    /// from the circuit file with the given hash. The cache is not trusted more than the
+    /// circuit file: the circuit is validated and its structure info recomputed.
+    pub fn deserialize_flat_from<R: Read>(
+        mut reader: R,
+        source_hash: &[u8; 32],
+    ) -> CircuitResult<Self> {
+        let version_num = read_usize(&mut reader)?;
+        if version_num != FLAT_VERSION_NUM {
+            return Err(CircuitError::VersionMismatch {
+                expected: FLAT_VERSION_NUM,
+                found: version_num,
+            });
+        }
+        let mut read_hash = [0u8; 32];
+        reader.read_exact(&mut read_hash)?;
+        if read_hash != *source_hash {
+            return Err(CircuitError::StaleCache);
+        }
+        check_modulus::<C, _>(&mut reader)?;
+
+        let mut circuit = CircuitStructure::<C> {
+            expected_num_output_zeros: read_usize(&mut reader)?,
+            num_public_inputs: read_usize(&mut reader)?,
+            ..Default::default()
+        };
+        let num_layers = read_usize(&mut reader)?;
+        for _ in 0..num_layers {
+            circuit
+                .layers
+                .push(CircuitLayer::deserialize_flat_from(&mut reader)?);
+        }
+        check_diagnostics(circuit.validate())?;
+
+        circuit.identify_rnd_coefs();
+        circuit.identify_structure_info();
+        Ok(circuit)
+    }
+
+    /// Load a circuit through a cache of its flattened form.
+    /// The cache is rebuilt when missing, unreadable or made from another version of the
+    /// circuit file; failing to write it is not an error.
+    pub fn load_circuit_cached(filename: &str, cache_filename: &str) -> CircuitResult<Self> {
+        let file_bytes = fs::read(filename)?;
+        let source_hash = circuit_source_hash(&file_bytes);
+
+        let cached = File::open(cache_filename)
+            .map_err(CircuitError::from)
+            .and_then(|file| Self::deserialize_flat_from(BufReader::new(file), &source_hash));
+        match cached {
+            Ok(circuit) => return Ok(circuit),
+            Err(e) => log::info!("rebuilding circuit cache {}: {}", cache_filename, e),
+        }
+
+        let rc = RecursiveCircuit::<C>::deserialize_from(file_bytes.as_slice())?;
+        check_diagnostics(rc.validate())?;
+        let circuit = rc.flatten();
+
+        let written = File::create(cache_filename)
+            .map_err(CircuitError::from)
+            .and_then(|file| {
+                let mut writer = BufWriter::new(file);
+                circuit.serialize_flat_into(&mut writer, &source_hash)?;
+                Ok(writer.flush()?)
+            });
+        if let Err(e) = written {
+            log::warn!("unable to write circuit cache {}: {}", cache_filename, e);
+        }
+        Ok(circuit)
+    }
+}
@@ -74,16 +74,33 @@ pub struct RecursiveCircuit<C: GKRConfig> {
 }
 
 impl<C: GKRConfig> RecursiveCircuit<C> {
-    pub fn load(filename: &str) -> std::result::Result<Self, CircuitError> {
+    /// Load and validate a circuit, failing on the errors reported by `validate`.
+    pub fn load(filename: &str) -> CircuitResult<Self> {
+        let rc = Self::load_unchecked(filename)?;
+        check_diagnostics(rc.validate())?;
+        Ok(rc)
+    }
+
+    /// Load a circuit without validating it.
+    pub fn load_unchecked(filename: &str) -> CircuitResult<Self> {
         let file_bytes = fs::read(filename)?;
         let cursor = Cursor::new(file_bytes);
 
-        Ok(Self::deserialize_from(cursor))
+        Self::deserialize_from(cursor)
+    }
+
+    /// Save a circuit in the format read by `load`.
+    pub fn save(&self, filename: &str) -> CircuitResult<()> {
+        let mut bytes = vec![];
+        self.serialize_into(&mut bytes)?;
+        fs::write(filename, bytes)?;
+        Ok(())
     }
 
-    pub fn flatten(&self) -> Circuit<C> {
-        let mut ret = Circuit::<C> {
+    pub fn flatten(&self) -> CircuitStructure<C> {
+        let mut ret = CircuitStructure::<C> {
             expected_num_output_zeros: self.expected_num_output_zeros,
+            num_public_inputs: self.num_public_inputs,
             ..Default::default()
         };
         // layer-by-layer conversion
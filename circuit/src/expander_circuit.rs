@@ -1,7 +1,4 @@
-use std::fs;
-use std::io::Cursor;
-
-use arith::{Field, SimdField};
+use arith::{Field, FieldSerde};
 use ark_std::test_rng;
 use config::GKRConfig;
 use transcript::Transcript;
@@ -14,14 +11,12 @@ pub struct StructureInfo {
     pub max_degree_one: bool,
 }
 
+/// The gates of a layer. The values flowing through it are in `CircuitAssignment`.
 #[derive(Debug, Clone, Default)]
 pub struct CircuitLayer<C: GKRConfig> {
     pub input_var_num: usize,
     pub output_var_num: usize,
 
-    pub input_vals: Vec<C::SimdCircuitField>,
-    pub output_vals: Vec<C::SimdCircuitField>, // empty most time, unless in the last layer
-
     pub mul: Vec<GateMul<C>>,
     pub add: Vec<GateAdd<C>>,
     pub const_: Vec<GateConst<C>>,
@@ -33,74 +28,81 @@ pub struct CircuitLayer<C: GKRConfig> {
 impl<C: GKRConfig> CircuitLayer<C> {
     pub fn evaluate(
         &self,
+        input_vals: &[C::SimdCircuitField],
         res: &mut Vec<C::SimdCircuitField>,
         public_input: &[C::SimdCircuitField],
+        rnd_coefs: &LayerRndCoefs<C>,
     ) {
         res.clear();
         res.resize(1 << self.output_var_num, C::SimdCircuitField::zero());
-        for gate in &self.mul {
-            let i0 = &self.input_vals[gate.i_ids[0]];
-            let i1 = &self.input_vals[gate.i_ids[1]];
+        for (index, gate) in self.mul.iter().enumerate() {
+            let i0 = &input_vals[gate.i_ids[0]];
+            let i1 = &input_vals[gate.i_ids[1]];
             let o = &mut res[gate.o_id];
             let mul = *i0 * i1;
-            *o += C::circuit_field_mul_simd_circuit_field(&gate.coef, &mul);
+            let coef = rnd_coefs.coef(GateKind::Mul, index, gate);
+            *o += C::circuit_field_mul_simd_circuit_field(&coef, &mul);
         }
 
-        for gate in &self.add {
-            let i0 = self.input_vals[gate.i_ids[0]];
+        for (index, gate) in self.add.iter().enumerate() {
+            let i0 = input_vals[gate.i_ids[0]];
             let o = &mut res[gate.o_id];
-            *o += C::circuit_field_mul_simd_circuit_field(&gate.coef, &i0);
+            let coef = rnd_coefs.coef(GateKind::Add, index, gate);
+            *o += C::circuit_field_mul_simd_circuit_field(&coef, &i0);
         }
 
-        for gate in &self.const_ {
+        for (index, gate) in self.const_.iter().enumerate() {
             let o = &mut res[gate.o_id];
 
             let coef = match gate.coef_type {
                 CoefType::PublicInput(input_idx) => public_input[input_idx],
-                _ => C::circuit_field_to_simd_circuit_field(&gate.coef),
+                _ => C::circuit_field_to_simd_circuit_field(&rnd_coefs.coef(
+                    GateKind::Const,
+                    index,
+                    gate,
+                )),
             };
             *o += coef;
         }
 
-        for gate in &self.uni {
-            let i0 = &self.input_vals[gate.i_ids[0]];
+        for (index, gate) in self.uni.iter().enumerate() {
+            let i0 = &input_vals[gate.i_ids[0]];
             let o = &mut res[gate.o_id];
+            let coef = rnd_coefs.coef(GateKind::Uni, index, gate);
             match gate.gate_type {
-                12345 => {
-                    // pow5
+                POW5_GATE_TYPE => {
                     let i0_2 = i0.square();
                     let i0_4 = i0_2.square();
                     let i0_5 = i0_4 * i0;
-                    *o += C::circuit_field_mul_simd_circuit_field(&gate.coef, &i0_5);
+                    *o += C::circuit_field_mul_simd_circuit_field(&coef, &i0_5);
                 }
-                12346 => {
-                    // pow1
-                    *o += C::circuit_field_mul_simd_circuit_field(&gate.coef, i0);
+                POW1_GATE_TYPE => {
+                    *o += C::circuit_field_mul_simd_circuit_field(&coef, i0);
                 }
                 _ => panic!("Unknown gate type: {}", gate.gate_type),
             }
         }
     }
 
-    pub fn identify_rnd_coefs(&mut self, rnd_coefs: &mut Vec<*mut C::CircuitField>) {
-        for gate in &mut self.mul {
+    pub fn identify_rnd_coefs(&self, layer: usize, rnd_coefs: &mut Vec<RndCoefHandle>) {
+        for (index, gate) in self.mul.iter().enumerate() {
             if gate.coef_type == CoefType::Random {
-                rnd_coefs.push(&mut gate.coef);
+                rnd_coefs.push(RndCoefHandle::new(layer, GateKind::Mul, index));
             }
         }
-        for gate in &mut self.add {
+        for (index, gate) in self.add.iter().enumerate() {
             if gate.coef_type == CoefType::Random {
-                rnd_coefs.push(&mut gate.coef);
+                rnd_coefs.push(RndCoefHandle::new(layer, GateKind::Add, index));
             }
         }
-        for gate in &mut self.const_ {
+        for (index, gate) in self.const_.iter().enumerate() {
             if gate.coef_type == CoefType::Random {
-                rnd_coefs.push(&mut gate.coef);
+                rnd_coefs.push(RndCoefHandle::new(layer, GateKind::Const, index));
             }
         }
-        for gate in &mut self.uni {
+        for (index, gate) in self.uni.iter().enumerate() {
             if gate.coef_type == CoefType::Random {
-                rnd_coefs.push(&mut gate.coef);
+                rnd_coefs.push(RndCoefHandle::new(layer, GateKind::Uni, index));
             }
         }
     }
@@ -110,151 +112,263 @@ impl<C: GKRConfig> CircuitLayer<C> {
     }
 }
 
-#[derive(Debug, Default)]
-pub struct Circuit<C: GKRConfig> {
-    pub layers: Vec<CircuitLayer<C>>,
-    pub public_input: Vec<C::SimdCircuitField>,
-    pub expected_num_output_zeros: usize,
+/// The position of a random coefficient: the gate of the given kind and index in a layer.
+/// Handles stay valid when the circuit is cloned or moved, and are ordered as
+/// `CircuitStructure::identify_rnd_coefs` lists them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RndCoefHandle {
+    pub layer: usize,
+    pub kind: GateKind,
+    pub index: usize,
+}
 
-    pub rnd_coefs_identified: bool,
-    pub rnd_coefs: Vec<*mut C::CircuitField>, // unsafe
+impl RndCoefHandle {
+    pub fn new(layer: usize, kind: GateKind, index: usize) -> Self {
+        Self { layer, kind, index }
+    }
+}
+
+/// The random coefficients of one proof, indexed by their handles. The gates are left
+/// untouched, so that a circuit can be shared by concurrent proofs.
+#[derive(Debug, Clone, Default)]
+pub struct RndCoefs<C: GKRConfig> {
+    handles: Vec<RndCoefHandle>,
+    values: Vec<C::CircuitField>,
 }
 
-impl<C: GKRConfig> Clone for Circuit<C> {
-    fn clone(&self) -> Circuit<C> {
-        let mut ret = Circuit::<C> {
-            layers: self.layers.clone(),
-            public_input: self.public_input.clone(),
-            ..Default::default()
-        };
+impl<C: GKRConfig> RndCoefs<C> {
+    pub fn get(&self, handle: RndCoefHandle) -> Option<C::CircuitField> {
+        let i = self.handles.binary_search(&handle).ok()?;
+        Some(self.values[i])
+    }
 
-        if self.rnd_coefs_identified {
-            ret.identify_rnd_coefs();
+    /// The random coefficients of the given layer.
+    pub fn layer(&self, layer: usize) -> LayerRndCoefs<'_, C> {
+        let start = self.handles.partition_point(|handle| handle.layer < layer);
+        let end = self.handles.partition_point(|handle| handle.layer <= layer);
+        LayerRndCoefs {
+            handles: &self.handles[start..end],
+            values: &self.values[start..end],
         }
-        ret
     }
 }
 
-unsafe impl<C> Send for Circuit<C> where C: GKRConfig {}
+/// The random coefficients of one layer, see `RndCoefs`.
+#[derive(Debug)]
+pub struct LayerRndCoefs<'a, C: GKRConfig> {
+    handles: &'a [RndCoefHandle],
+    values: &'a [C::CircuitField],
+}
 
-impl<C: GKRConfig> Circuit<C> {
-    pub fn load_circuit(filename: &str) -> Self {
-        let rc = RecursiveCircuit::<C>::load(filename).unwrap();
-        rc.flatten()
+impl<C: GKRConfig> LayerRndCoefs<'_, C> {
+    /// The coefficient of the gate of the given kind and index in the layer:
+    /// the value drawn for the proof if it is random, the one of the gate otherwise.
+    #[inline]
+    pub fn coef<const INPUT_NUM: usize>(
+        &self,
+        kind: GateKind,
+        index: usize,
+        gate: &Gate<C, INPUT_NUM>,
+    ) -> C::CircuitField {
+        if gate.coef_type != CoefType::Random {
+            return gate.coef;
+        }
+        let i = self
+            .handles
+            .binary_search_by_key(&(kind, index), |handle| (handle.kind, handle.index))
+            .expect("the random coefficient is not drawn");
+        self.values[i]
     }
+}
 
-    pub fn load_witness_file(&mut self, filename: &str) {
-        let file_bytes = fs::read(filename).unwrap();
-        let cursor = Cursor::new(file_bytes);
-        let witness = Witness::<C>::deserialize_from(cursor);
-
-        let private_input_size = 1 << self.log_input_size();
-        let public_input_size = witness.num_public_inputs_per_witness;
-        let total_size = private_input_size + public_input_size;
-
-        assert_eq!(witness.num_private_inputs_per_witness, private_input_size);
-        #[allow(clippy::comparison_chain)]
-        if witness.num_witnesses < C::get_field_pack_size() {
-            panic!("Not enough witness");
-        } else if witness.num_witnesses > C::get_field_pack_size() {
-            println!("Warning: dropping additional witnesses");
-        }
+/// The immutable part of a circuit, shared by every proof of it.
+#[derive(Debug, Clone, Default)]
+pub struct CircuitStructure<C: GKRConfig> {
+    pub layers: Vec<CircuitLayer<C>>,
+    pub expected_num_output_zeros: usize,
+    pub num_public_inputs: usize,
 
-        let input = &witness.values;
-        let private_input = &mut self.layers[0].input_vals;
-        let public_input = &mut self.public_input;
+    pub rnd_coefs_identified: bool,
+    pub rnd_coefs: Vec<RndCoefHandle>,
+}
 
-        private_input.clear();
-        public_input.clear();
+impl<C: GKRConfig> CircuitStructure<C> {
+    /// Load, validate and flatten a circuit.
+    pub fn load_circuit(filename: &str) -> CircuitResult<Self> {
+        let rc = RecursiveCircuit::<C>::load(filename)?;
+        Ok(rc.flatten())
+    }
 
-        for i in 0..private_input_size {
-            let mut private_wit_i = vec![];
-            for j in 0..C::get_field_pack_size() {
-                private_wit_i.push(input[j * total_size + i]);
-            }
-            private_input.push(C::SimdCircuitField::pack(&private_wit_i));
+    /// Load and flatten a circuit without validating it.
+    pub fn load_circuit_unchecked(filename: &str) -> CircuitResult<Self> {
+        let rc = RecursiveCircuit::<C>::load_unchecked(filename)?;
+        Ok(rc.flatten())
+    }
+
+    pub fn log_input_size(&self) -> usize {
+        self.layers[0].input_var_num
+    }
+
+    pub fn identify_rnd_coefs(&mut self) {
+        self.rnd_coefs.clear();
+        for (i, layer) in self.layers.iter().enumerate() {
+            layer.identify_rnd_coefs(i, &mut self.rnd_coefs);
         }
+        self.rnd_coefs_identified = true;
+    }
 
-        for i in 0..public_input_size {
-            let mut public_wit_i = vec![];
-            for j in 0..C::get_field_pack_size() {
-                public_wit_i.push(input[j * total_size + private_input_size + i]);
-            }
-            public_input.push(C::SimdCircuitField::pack(&public_wit_i));
+    /// Draw the random coefficients of a proof from the transcript, in the order of the handles.
+    pub fn draw_rnd_coefs(
+        &self,
+        transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    ) -> RndCoefs<C> {
+        assert!(self.rnd_coefs_identified);
+        transcript.absorb_label("random coefficients");
+        RndCoefs {
+            handles: self.rnd_coefs.clone(),
+            values: self
+                .rnd_coefs
+                .iter()
+                .map(|_| transcript.generate_challenge::<C::CircuitField>())
+                .collect(),
         }
     }
+
+    /// Bind the transcript to the statement, the circuit digest and the public inputs of all
+    /// the MPI processes, so that a proof only verifies for the circuit and inputs it was
+    /// produced for. Both sides absorb it before any prover message.
+    pub fn absorb_statement(
+        &self,
+        public_input: &[C::SimdCircuitField],
+        transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    ) {
+        let mut buffer = vec![];
+        for x in public_input {
+            x.serialize_into(&mut buffer).unwrap();
+        }
+        transcript.absorb_labelled("circuit", &self.digest());
+        transcript.absorb_labelled("public inputs", &buffer);
+    }
+
+    pub fn identify_structure_info(&mut self) {
+        for layer in &mut self.layers {
+            layer.identify_structure_info();
+        }
+    }
+}
+
+/// The values of a circuit for one proof: the inputs of each layer, the outputs of the last
+/// one and the public inputs, all SIMD packed.
+#[derive(Debug, Clone, Default)]
+pub struct CircuitAssignment<C: GKRConfig> {
+    /// `input_vals[0]` holds the private inputs, the other layers are filled by `evaluate`.
+    pub input_vals: Vec<Vec<C::SimdCircuitField>>,
+    pub output_vals: Vec<C::SimdCircuitField>,
+    pub public_input: Vec<C::SimdCircuitField>,
 }
 
-impl<C: GKRConfig> Circuit<C> {
+impl<C: GKRConfig> CircuitAssignment<C> {
+    /// An assignment of the circuit with zero inputs.
+    pub fn new(structure: &CircuitStructure<C>) -> Self {
+        let mut input_vals = vec![vec![]; structure.layers.len()];
+        input_vals[0] = vec![C::SimdCircuitField::zero(); 1 << structure.log_input_size()];
+        Self {
+            input_vals,
+            output_vals: vec![],
+            public_input: vec![C::SimdCircuitField::zero(); structure.num_public_inputs],
+        }
+    }
+
     pub fn log_input_size(&self) -> usize {
-        self.layers[0].input_var_num
+        self.input_vals[0].len().trailing_zeros() as usize
+    }
+
+    /// Load a witness file of a single SIMD batch.
+    /// Use `Witness::load` and `load_witness_batch` for files with several batches.
+    pub fn load_witness_file(&mut self, filename: &str) -> CircuitResult<()> {
+        let witness = Witness::<C>::load(filename)?;
+        self.load_single_witness_batch(&witness)
+    }
+
+    /// Load a serialized witness of a single SIMD batch.
+    pub fn load_witness_bytes(&mut self, file_bytes: &[u8]) -> CircuitResult<()> {
+        let witness = Witness::<C>::from_bytes(file_bytes)?;
+        self.load_single_witness_batch(&witness)
+    }
+
+    fn load_single_witness_batch(&mut self, witness: &Witness<C>) -> CircuitResult<()> {
+        if witness.num_batches() > 1 {
+            return Err(CircuitError::MultipleWitnessBatches {
+                num_batches: witness.num_batches(),
+            });
+        }
+        self.load_witness_batch(witness, 0)
+    }
+
+    /// Load the witnesses `batch * pack_size..(batch + 1) * pack_size` into the SIMD lanes
+    /// of the input layer and the public inputs.
+    pub fn load_witness_batch(&mut self, witness: &Witness<C>, batch: usize) -> CircuitResult<()> {
+        let private_input_size = self.input_vals[0].len();
+        if witness.num_private_inputs_per_witness != private_input_size {
+            return Err(CircuitError::WitnessSizeMismatch {
+                expected: private_input_size,
+                found: witness.num_private_inputs_per_witness,
+            });
+        }
+        if witness.num_public_inputs_per_witness != self.public_input.len() {
+            return Err(CircuitError::PublicInputSizeMismatch {
+                expected: self.public_input.len(),
+                found: witness.num_public_inputs_per_witness,
+            });
+        }
+        if batch >= witness.num_batches() {
+            return Err(CircuitError::WitnessBatchOutOfRange {
+                batch,
+                num_batches: witness.num_batches(),
+            });
+        }
+
+        self.input_vals[0] = witness.pack_private_inputs(batch);
+        self.public_input = witness.pack_public_inputs(batch);
+        Ok(())
     }
 
-    // Build a random mock circuit with binary inputs
+    // Random inputs for a mock circuit
     pub fn set_random_input_for_test(&mut self) {
         let mut rng = test_rng();
-        self.layers[0].input_vals = (0..(1 << self.log_input_size()))
-            .map(|_| C::SimdCircuitField::random_unsafe(&mut rng))
-            .collect();
+        for v in self.input_vals[0].iter_mut() {
+            *v = C::SimdCircuitField::random_unsafe(&mut rng);
+        }
     }
 
-    pub fn evaluate(&mut self) {
-        for i in 0..self.layers.len() - 1 {
-            let (layer_p_1, layer_p_2) = self.layers.split_at_mut(i + 1);
-            layer_p_1
-                .last()
-                .unwrap()
-                .evaluate(&mut layer_p_2[0].input_vals, &self.public_input);
+    /// Evaluate the layers with the random coefficients of the proof.
+    pub fn evaluate(&mut self, structure: &CircuitStructure<C>, rnd_coefs: &RndCoefs<C>) {
+        assert_eq!(self.input_vals.len(), structure.layers.len());
+        for i in 0..structure.layers.len() - 1 {
+            let (layer_p_1, layer_p_2) = self.input_vals.split_at_mut(i + 1);
+            structure.layers[i].evaluate(
+                &layer_p_1[i],
+                &mut layer_p_2[0],
+                &self.public_input,
+                &rnd_coefs.layer(i),
+            );
             log::trace!(
                 "layer {} evaluated - First 10 values: {:?}",
                 i,
-                self.layers[i + 1]
-                    .input_vals
-                    .iter()
-                    .take(10)
-                    .collect::<Vec<_>>()
+                self.input_vals[i + 1].iter().take(10).collect::<Vec<_>>()
             );
         }
-        let mut output = vec![];
-        self.layers
-            .last()
-            .unwrap()
-            .evaluate(&mut output, &self.public_input);
-        self.layers.last_mut().unwrap().output_vals = output;
+        structure.layers.last().unwrap().evaluate(
+            self.input_vals.last().unwrap(),
+            &mut self.output_vals,
+            &self.public_input,
+            &rnd_coefs.layer(structure.layers.len() - 1),
+        );
 
         log::trace!("output evaluated");
         log::trace!(
             "First ten values: {:?}",
-            self.layers
-                .last()
-                .unwrap()
-                .output_vals
-                .iter()
-                .take(10)
-                .collect::<Vec<_>>()
+            self.output_vals.iter().take(10).collect::<Vec<_>>()
         );
     }
-
-    pub fn identify_rnd_coefs(&mut self) {
-        self.rnd_coefs.clear();
-        for layer in &mut self.layers {
-            layer.identify_rnd_coefs(&mut self.rnd_coefs);
-        }
-        self.rnd_coefs_identified = true;
-    }
-
-    pub fn fill_rnd_coefs(&mut self, transcript: &mut TranscriptInstance<C::FiatShamirHashType>) {
-        assert!(self.rnd_coefs_identified);
-        for &rnd_coef_ptr in &self.rnd_coefs {
-            unsafe {
-                *rnd_coef_ptr = transcript.generate_challenge::<C::CircuitField>();
-            }
-        }
-    }
-
-    pub fn identify_structure_info(&mut self) {
-        for layer in &mut self.layers {
-            layer.identify_structure_info();
-        }
-    }
 }
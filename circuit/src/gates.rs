@@ -1,6 +1,6 @@
 use config::GKRConfig;
 
-#[derive(Debug, Clone, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub enum CoefType {
     #[default]
     Constant,
@@ -8,6 +8,21 @@ pub enum CoefType {
     PublicInput(usize),
 }
 
+/// The kinds of gates of a layer, one vector each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GateKind {
+    Mul,
+    Add,
+    Const,
+    Uni,
+}
+
+/// Gate type of the unary gate `coef * x^5`.
+pub const POW5_GATE_TYPE: usize = 12345;
+
+/// Gate type of the unary gate `coef * x`.
+pub const POW1_GATE_TYPE: usize = 12346;
+
 #[derive(Debug, Clone)]
 pub struct Gate<C: GKRConfig, const INPUT_NUM: usize> {
     pub i_ids: [usize; INPUT_NUM],
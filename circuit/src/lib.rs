@@ -12,3 +12,15 @@ pub use witness::*;
 
 mod serde;
 pub use serde::*;
+
+mod cache;
+pub use cache::*;
+
+mod validate;
+pub use validate::*;
+
+mod builder;
+pub use builder::*;
+
+#[cfg(test)]
+mod tests;
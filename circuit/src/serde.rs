@@ -1,87 +1,245 @@
 use arith::{Field, FieldForECC, FieldSerde, FieldSerdeError};
 use config::GKRConfig;
-use std::{io::Read, vec};
+use std::io::{ErrorKind, Read, Write};
 use thiserror::Error;
 
 use super::{Allocation, CoefType, Gate, RecursiveCircuit, Segment, Witness};
-use crate::{GateAdd, GateConst, GateMul, SegmentId};
+use crate::{CircuitDiagnostic, GateAdd, GateConst, GateMul, SegmentId};
 
 #[derive(Debug, Error)]
 pub enum CircuitError {
     #[error("field serde error: {0:?}")]
-    FieldSerdeError(#[from] FieldSerdeError),
+    FieldSerdeError(FieldSerdeError),
 
     #[error("other error: {0:?}")]
-    OtherError(#[from] std::io::Error),
+    OtherError(std::io::Error),
+
+    #[error("version mismatch: expected {expected:#x}, found {found:#x}")]
+    VersionMismatch { expected: usize, found: usize },
+
+    #[error("modulus mismatch: expected {expected}, found {found}")]
+    ModulusMismatch {
+        expected: ethnum::U256,
+        found: ethnum::U256,
+    },
+
+    #[error("bad coefficient type {coef_type} for a gate with {input_num} inputs")]
+    BadCoefType { coef_type: u8, input_num: usize },
+
+    #[error("custom gate expects {expected} inputs, found {found}")]
+    GateInputNumMismatch { expected: usize, found: usize },
+
+    #[error("gate id {id} out of range, the segment has {len} slots")]
+    GateIdOutOfRange { id: usize, len: usize },
+
+    #[error("segment id {id} out of range, the circuit has {len} segments")]
+    SegmentIdOutOfRange { id: usize, len: usize },
+
+    #[error("segment size {0} is not a power of two")]
+    InvalidSegmentSize(usize),
+
+    #[error("variable number {0} too large")]
+    InvalidVarNum(usize),
+
+    #[error("the flattened circuit comes from another circuit file")]
+    StaleCache,
+
+    #[error("truncated input")]
+    TruncatedInput,
+
+    #[error("{num_witnesses} witnesses do not fill whole batches of {pack_size}")]
+    WitnessCountMismatch {
+        num_witnesses: usize,
+        pack_size: usize,
+    },
+
+    #[error("witness has {found} private inputs, the circuit expects {expected}")]
+    WitnessSizeMismatch { expected: usize, found: usize },
+
+    #[error("witness has {found} public inputs, expected {expected}")]
+    PublicInputSizeMismatch { expected: usize, found: usize },
+
+    #[error("witness sizes overflow: {num_witnesses} witnesses of {num_private_inputs} private and {num_public_inputs} public inputs")]
+    WitnessSizeOverflow {
+        num_witnesses: usize,
+        num_private_inputs: usize,
+        num_public_inputs: usize,
+    },
+
+    #[error("witness batch {batch} out of range, {num_batches} batches")]
+    WitnessBatchOutOfRange { batch: usize, num_batches: usize },
+
+    #[error(
+        "witness has {num_batches} batches, load it with Witness::load and load_witness_batch"
+    )]
+    MultipleWitnessBatches { num_batches: usize },
+
+    #[error("invalid circuit: {0:?}")]
+    InvalidCircuit(Vec<CircuitDiagnostic>),
+
+    #[error("pow5 gates are only proven by GKR^2, which has no mul gates")]
+    Pow5WithMul,
+}
+
+impl From<std::io::Error> for CircuitError {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            ErrorKind::UnexpectedEof => CircuitError::TruncatedInput,
+            _ => CircuitError::OtherError(e),
+        }
+    }
+}
+
+impl From<FieldSerdeError> for CircuitError {
+    fn from(e: FieldSerdeError) -> Self {
+        match e {
+            FieldSerdeError::IOError(e) => e.into(),
+            _ => CircuitError::FieldSerdeError(e),
+        }
+    }
+}
+
+pub type CircuitResult<T> = std::result::Result<T, CircuitError>;
+
+pub trait FromEccSerde: Sized {
+    fn deserialize_from<R: Read>(reader: R) -> CircuitResult<Self>;
 }
-pub trait FromEccSerde {
-    fn deserialize_from<R: Read>(reader: R) -> Self;
+
+/// Serialization into the ECC format, the inverse of `FromEccSerde`.
+pub trait ToEccSerde {
+    fn serialize_into<W: Write>(&self, writer: W) -> CircuitResult<()>;
 }
 
 impl<T: FromEccSerde> FromEccSerde for Vec<T> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        let vec_len = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
+    fn deserialize_from<R: Read>(mut reader: R) -> CircuitResult<Self> {
+        let vec_len = read_usize(&mut reader)?;
         let mut ret = vec![];
         for _ in 0..vec_len {
-            ret.push(T::deserialize_from(&mut reader));
+            ret.push(T::deserialize_from(&mut reader)?);
         }
-        ret
+        Ok(ret)
     }
 }
 
 impl<T1: FromEccSerde, T2: FromEccSerde> FromEccSerde for (T1, T2) {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        (
-            T1::deserialize_from(&mut reader),
-            T2::deserialize_from(&mut reader),
-        )
+    fn deserialize_from<R: Read>(mut reader: R) -> CircuitResult<Self> {
+        Ok((
+            T1::deserialize_from(&mut reader)?,
+            T2::deserialize_from(&mut reader)?,
+        ))
+    }
+}
+
+impl<T: ToEccSerde> ToEccSerde for Vec<T> {
+    fn serialize_into<W: Write>(&self, mut writer: W) -> CircuitResult<()> {
+        write_usize(&mut writer, self.len())?;
+        for v in self {
+            v.serialize_into(&mut writer)?;
+        }
+        Ok(())
     }
 }
 
+impl<T1: ToEccSerde, T2: ToEccSerde> ToEccSerde for (T1, T2) {
+    fn serialize_into<W: Write>(&self, mut writer: W) -> CircuitResult<()> {
+        self.0.serialize_into(&mut writer)?;
+        self.1.serialize_into(&mut writer)
+    }
+}
+
+#[inline]
+pub(crate) fn read_usize<R: Read>(reader: R) -> CircuitResult<usize> {
+    Ok(<usize as FieldSerde>::deserialize_from(reader)?)
+}
+
+#[inline]
+pub(crate) fn write_usize<W: Write>(writer: W, v: usize) -> CircuitResult<()> {
+    Ok(<usize as FieldSerde>::serialize_into(&v, writer)?)
+}
+
 impl FromEccSerde for usize {
-    fn deserialize_from<R: Read>(reader: R) -> Self {
-        <usize as FieldSerde>::deserialize_from(reader).unwrap()
+    fn deserialize_from<R: Read>(reader: R) -> CircuitResult<Self> {
+        read_usize(reader)
     }
 }
 
+impl ToEccSerde for usize {
+    fn serialize_into<W: Write>(&self, writer: W) -> CircuitResult<()> {
+        write_usize(writer, *self)
+    }
+}
+
+/// Read the coefficient of a gate with INPUT_NUM inputs, tagged by its coefficient type.
+fn read_coef<C: GKRConfig, const INPUT_NUM: usize, R: Read>(
+    mut reader: R,
+) -> CircuitResult<(CoefType, C::CircuitField)> {
+    let coef_type_u8 = u8::deserialize_from(&mut reader)?;
+    match coef_type_u8 {
+        1 => Ok((
+            CoefType::Constant,
+            C::CircuitField::try_deserialize_from_ecc_format(&mut reader)?,
+        )),
+        2 => Ok((CoefType::Random, C::CircuitField::ZERO)),
+        // Public Input can only be used with constant gates
+        3 if INPUT_NUM == 0 => Ok((
+            CoefType::PublicInput(read_usize(&mut reader)?),
+            C::CircuitField::ZERO,
+        )),
+        _ => Err(CircuitError::BadCoefType {
+            coef_type: coef_type_u8,
+            input_num: INPUT_NUM,
+        }),
+    }
+}
+
+/// Write the coefficient of a gate, tagged by its coefficient type.
+fn write_coef<C: GKRConfig, W: Write>(
+    mut writer: W,
+    coef_type: &CoefType,
+    coef: &C::CircuitField,
+) -> CircuitResult<()> {
+    match coef_type {
+        CoefType::Constant => {
+            1u8.serialize_into(&mut writer)?;
+            coef.serialize_into_ecc_format(&mut writer)?;
+        }
+        CoefType::Random => 2u8.serialize_into(&mut writer)?,
+        CoefType::PublicInput(idx) => {
+            3u8.serialize_into(&mut writer)?;
+            write_usize(&mut writer, *idx)?;
+        }
+    }
+    Ok(())
+}
+
 impl<C: GKRConfig, const INPUT_NUM: usize> FromEccSerde for Gate<C, INPUT_NUM> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
+    fn deserialize_from<R: Read>(mut reader: R) -> CircuitResult<Self> {
         let mut i_ids = [0usize; INPUT_NUM];
         for id in &mut i_ids {
-            *id = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        }
-
-        let o_id = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-
-        let coef_type_u8 = u8::deserialize_from(&mut reader).unwrap();
-        let (coef_type, coef) = match coef_type_u8 {
-            1 => (
-                CoefType::Constant,
-                C::CircuitField::try_deserialize_from_ecc_format(&mut reader).unwrap(),
-            ),
-            2 => (CoefType::Random, C::CircuitField::ZERO),
-            3 => {
-                if INPUT_NUM > 0 {
-                    panic!("Public Input can only be used with constant gates")
-                };
-
-                (
-                    CoefType::PublicInput(
-                        <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
-                    ),
-                    C::CircuitField::ZERO,
-                )
-            }
-            _ => unreachable!(),
-        };
+            *id = read_usize(&mut reader)?;
+        }
+
+        let o_id = read_usize(&mut reader)?;
 
-        Self {
+        let (coef_type, coef) = read_coef::<C, INPUT_NUM, _>(&mut reader)?;
+
+        Ok(Self {
             i_ids,
             o_id,
             coef_type,
             coef,
             gate_type: 0,
+        })
+    }
+}
+
+impl<C: GKRConfig, const INPUT_NUM: usize> ToEccSerde for Gate<C, INPUT_NUM> {
+    fn serialize_into<W: Write>(&self, mut writer: W) -> CircuitResult<()> {
+        for &id in &self.i_ids {
+            write_usize(&mut writer, id)?;
         }
+        write_usize(&mut writer, self.o_id)?;
+        write_coef::<C, _>(&mut writer, &self.coef_type, &self.coef)
     }
 }
 
@@ -90,37 +248,20 @@ pub struct CustomGateWrapper<C: GKRConfig, const INPUT_NUM: usize> {
 }
 
 impl<C: GKRConfig, const INPUT_NUM: usize> FromEccSerde for CustomGateWrapper<C, INPUT_NUM> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        let gate_type = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        let i_ids: [usize; INPUT_NUM] = Vec::<usize>::deserialize_from(&mut reader)
+    fn deserialize_from<R: Read>(mut reader: R) -> CircuitResult<Self> {
+        let gate_type = read_usize(&mut reader)?;
+        let i_ids: [usize; INPUT_NUM] = Vec::<usize>::deserialize_from(&mut reader)?
             .try_into()
-            .unwrap();
-
-        let o_id = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-
-        let coef_type_u8 = u8::deserialize_from(&mut reader).unwrap();
-        let (coef_type, coef) = match coef_type_u8 {
-            1 => (
-                CoefType::Constant,
-                C::CircuitField::try_deserialize_from_ecc_format(&mut reader).unwrap(),
-            ),
-            2 => (CoefType::Random, C::CircuitField::ZERO),
-            3 => {
-                if INPUT_NUM > 0 {
-                    panic!("Public Input can only be used with constant gates")
-                };
-
-                (
-                    CoefType::PublicInput(
-                        <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
-                    ),
-                    C::CircuitField::ZERO,
-                )
-            }
-            _ => unreachable!(),
-        };
+            .map_err(|v: Vec<usize>| CircuitError::GateInputNumMismatch {
+                expected: INPUT_NUM,
+                found: v.len(),
+            })?;
+
+        let o_id = read_usize(&mut reader)?;
 
-        Self {
+        let (coef_type, coef) = read_coef::<C, INPUT_NUM, _>(&mut reader)?;
+
+        Ok(Self {
             custom_gate: Gate::<C, INPUT_NUM> {
                 i_ids,
                 o_id,
@@ -128,39 +269,98 @@ impl<C: GKRConfig, const INPUT_NUM: usize> FromEccSerde for CustomGateWrapper<C,
                 coef,
                 gate_type,
             },
-        }
+        })
+    }
+}
+
+/// Write a gate in the format of `CustomGateWrapper`, with its gate type and input count.
+pub(crate) fn write_custom_gate<C: GKRConfig, const INPUT_NUM: usize, W: Write>(
+    mut writer: W,
+    gate: &Gate<C, INPUT_NUM>,
+) -> CircuitResult<()> {
+    write_usize(&mut writer, gate.gate_type)?;
+    write_usize(&mut writer, INPUT_NUM)?;
+    for &id in &gate.i_ids {
+        write_usize(&mut writer, id)?;
+    }
+    write_usize(&mut writer, gate.o_id)?;
+    write_coef::<C, _>(&mut writer, &gate.coef_type, &gate.coef)
+}
+
+impl<C: GKRConfig, const INPUT_NUM: usize> ToEccSerde for CustomGateWrapper<C, INPUT_NUM> {
+    fn serialize_into<W: Write>(&self, writer: W) -> CircuitResult<()> {
+        write_custom_gate(writer, &self.custom_gate)
     }
 }
 
 impl FromEccSerde for Allocation {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        Self {
-            i_offset: <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
-            o_offset: <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
+    fn deserialize_from<R: Read>(mut reader: R) -> CircuitResult<Self> {
+        Ok(Self {
+            i_offset: read_usize(&mut reader)?,
+            o_offset: read_usize(&mut reader)?,
+        })
+    }
+}
+
+impl ToEccSerde for Allocation {
+    fn serialize_into<W: Write>(&self, mut writer: W) -> CircuitResult<()> {
+        write_usize(&mut writer, self.i_offset)?;
+        write_usize(&mut writer, self.o_offset)
+    }
+}
+
+/// Check that the gates only touch the `i_len` inputs and `o_len` outputs of their segment.
+pub(crate) fn check_gate_ids<C: GKRConfig, const INPUT_NUM: usize>(
+    gates: &[Gate<C, INPUT_NUM>],
+    i_len: usize,
+    o_len: usize,
+) -> CircuitResult<()> {
+    for gate in gates {
+        if let Some(&id) = gate.i_ids.iter().find(|&&id| id >= i_len) {
+            return Err(CircuitError::GateIdOutOfRange { id, len: i_len });
+        }
+        if gate.o_id >= o_len {
+            return Err(CircuitError::GateIdOutOfRange {
+                id: gate.o_id,
+                len: o_len,
+            });
         }
     }
+    Ok(())
+}
+
+/// Read a segment size, which must be a power of two.
+fn read_segment_size<R: Read>(reader: R) -> CircuitResult<usize> {
+    let len = read_usize(reader)?;
+    if !len.is_power_of_two() {
+        return Err(CircuitError::InvalidSegmentSize(len));
+    }
+    Ok(len)
 }
 
 impl<C: GKRConfig> FromEccSerde for Segment<C> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        let i_len = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        let o_len = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        assert!(i_len.is_power_of_two());
-        assert!(o_len.is_power_of_two());
+    fn deserialize_from<R: Read>(mut reader: R) -> CircuitResult<Self> {
+        let i_len = read_segment_size(&mut reader)?;
+        let o_len = read_segment_size(&mut reader)?;
 
-        let child_segs = Vec::<(SegmentId, Vec<Allocation>)>::deserialize_from(&mut reader);
-        let gate_muls = Vec::<GateMul<C>>::deserialize_from(&mut reader);
-        let gate_adds = Vec::<GateAdd<C>>::deserialize_from(&mut reader);
-        let gate_consts = Vec::<GateConst<C>>::deserialize_from(&mut reader);
+        let child_segs = Vec::<(SegmentId, Vec<Allocation>)>::deserialize_from(&mut reader)?;
+        let gate_muls = Vec::<GateMul<C>>::deserialize_from(&mut reader)?;
+        let gate_adds = Vec::<GateAdd<C>>::deserialize_from(&mut reader)?;
+        let gate_consts = Vec::<GateConst<C>>::deserialize_from(&mut reader)?;
 
         let mut gate_uni = vec![];
-        let len = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
+        let len = read_usize(&mut reader)?;
         for _ in 0..len {
-            let uni = CustomGateWrapper::<C, 1>::deserialize_from(&mut reader).custom_gate;
+            let uni = CustomGateWrapper::<C, 1>::deserialize_from(&mut reader)?.custom_gate;
             gate_uni.push(uni);
         }
 
-        Segment {
+        check_gate_ids(&gate_muls, i_len, o_len)?;
+        check_gate_ids(&gate_adds, i_len, o_len)?;
+        check_gate_ids(&gate_consts, i_len, o_len)?;
+        check_gate_ids(&gate_uni, i_len, o_len)?;
+
+        Ok(Segment {
             i_var_num: i_len.trailing_zeros() as usize,
             o_var_num: o_len.trailing_zeros() as usize,
             child_segs,
@@ -168,54 +368,144 @@ impl<C: GKRConfig> FromEccSerde for Segment<C> {
             gate_adds,
             gate_consts,
             gate_uni,
+        })
+    }
+}
+
+impl<C: GKRConfig> ToEccSerde for Segment<C> {
+    fn serialize_into<W: Write>(&self, mut writer: W) -> CircuitResult<()> {
+        write_usize(&mut writer, 1 << self.i_var_num)?;
+        write_usize(&mut writer, 1 << self.o_var_num)?;
+
+        self.child_segs.serialize_into(&mut writer)?;
+        self.gate_muls.serialize_into(&mut writer)?;
+        self.gate_adds.serialize_into(&mut writer)?;
+        self.gate_consts.serialize_into(&mut writer)?;
+
+        write_usize(&mut writer, self.gate_uni.len())?;
+        for uni in &self.gate_uni {
+            write_custom_gate(&mut writer, uni)?;
         }
+        Ok(())
     }
 }
 
 const VERSION_NUM: usize = 3914834606642317635; // b'CIRCUIT6'
 
+/// Read the modulus of the circuit field, which must be the one of C.
+pub(crate) fn check_modulus<C: GKRConfig, R: Read>(mut reader: R) -> CircuitResult<()> {
+    let expected_mod = <C::CircuitField as FieldForECC>::modulus();
+    let mut field_mod = [0u8; 32];
+    reader.read_exact(&mut field_mod)?;
+    let read_mod = ethnum::U256::from_le_bytes(field_mod);
+    if expected_mod != read_mod {
+        return Err(CircuitError::ModulusMismatch {
+            expected: expected_mod,
+            found: read_mod,
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn write_modulus<C: GKRConfig, W: Write>(mut writer: W) -> CircuitResult<()> {
+    let modulus = <C::CircuitField as FieldForECC>::modulus();
+    writer.write_all(&modulus.to_le_bytes())?;
+    Ok(())
+}
+
 impl<C: GKRConfig> FromEccSerde for RecursiveCircuit<C> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        let version_num = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        assert_eq!(version_num, VERSION_NUM);
-        let expected_mod = <C::CircuitField as FieldForECC>::modulus();
-        let mut field_mod = [0u8; 32];
-        reader.read_exact(&mut field_mod).unwrap();
-        let read_mod = ethnum::U256::from_le_bytes(field_mod);
-        assert_eq!(expected_mod, read_mod);
+    fn deserialize_from<R: Read>(mut reader: R) -> CircuitResult<Self> {
+        let version_num = read_usize(&mut reader)?;
+        if version_num != VERSION_NUM {
+            return Err(CircuitError::VersionMismatch {
+                expected: VERSION_NUM,
+                found: version_num,
+            });
+        }
+        check_modulus::<C, _>(&mut reader)?;
+
+        let rc = RecursiveCircuit {
+            num_public_inputs: read_usize(&mut reader)?,
+            num_outputs: read_usize(&mut reader)?,
+            expected_num_output_zeros: read_usize(&mut reader)?,
 
-        RecursiveCircuit {
-            num_public_inputs: <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
-            num_outputs: <usize as FieldSerde>::deserialize_from(&mut reader).unwrap(),
-            expected_num_output_zeros: <usize as FieldSerde>::deserialize_from(&mut reader)
-                .unwrap(),
+            segments: Vec::<Segment<C>>::deserialize_from(&mut reader)?,
+            layers: Vec::<usize>::deserialize_from(&mut reader)?,
+        };
 
-            segments: Vec::<Segment<C>>::deserialize_from(&mut reader),
-            layers: Vec::<usize>::deserialize_from(&mut reader),
+        let num_segments = rc.segments.len();
+        let segment_ids = rc
+            .segments
+            .iter()
+            .flat_map(|seg| seg.child_segs.iter().map(|(id, _)| id))
+            .chain(rc.layers.iter());
+        for &id in segment_ids {
+            if id >= num_segments {
+                return Err(CircuitError::SegmentIdOutOfRange {
+                    id,
+                    len: num_segments,
+                });
+            }
         }
+
+        Ok(rc)
+    }
+}
+
+impl<C: GKRConfig> ToEccSerde for RecursiveCircuit<C> {
+    fn serialize_into<W: Write>(&self, mut writer: W) -> CircuitResult<()> {
+        write_usize(&mut writer, VERSION_NUM)?;
+        write_modulus::<C, _>(&mut writer)?;
+
+        write_usize(&mut writer, self.num_public_inputs)?;
+        write_usize(&mut writer, self.num_outputs)?;
+        write_usize(&mut writer, self.expected_num_output_zeros)?;
+
+        self.segments.serialize_into(&mut writer)?;
+        self.layers.serialize_into(&mut writer)
     }
 }
 
 impl<C: GKRConfig> FromEccSerde for Witness<C> {
-    fn deserialize_from<R: Read>(mut reader: R) -> Self {
-        let num_witnesses = <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        let num_private_inputs_per_witness =
-            <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        let num_public_inputs_per_witness =
-            <usize as FieldSerde>::deserialize_from(&mut reader).unwrap();
-        let _modulus = <[u64; 4]>::deserialize_from(&mut reader).unwrap();
+    fn deserialize_from<R: Read>(mut reader: R) -> CircuitResult<Self> {
+        let num_witnesses = read_usize(&mut reader)?;
+        let num_private_inputs_per_witness = read_usize(&mut reader)?;
+        let num_public_inputs_per_witness = read_usize(&mut reader)?;
+        check_modulus::<C, _>(&mut reader)?;
+
+        let num_values = num_private_inputs_per_witness
+            .checked_add(num_public_inputs_per_witness)
+            .and_then(|instance_size| num_witnesses.checked_mul(instance_size))
+            .ok_or(CircuitError::WitnessSizeOverflow {
+                num_witnesses,
+                num_private_inputs: num_private_inputs_per_witness,
+                num_public_inputs: num_public_inputs_per_witness,
+            })?;
 
         let mut values = vec![];
-        for _ in 0..num_witnesses * (num_private_inputs_per_witness + num_public_inputs_per_witness)
-        {
-            values.push(C::CircuitField::deserialize_from(&mut reader).unwrap());
+        for _ in 0..num_values {
+            values.push(C::CircuitField::deserialize_from(&mut reader)?);
         }
 
-        Self {
+        Ok(Self {
             num_witnesses,
             num_private_inputs_per_witness,
             num_public_inputs_per_witness,
             values,
+        })
+    }
+}
+
+impl<C: GKRConfig> ToEccSerde for Witness<C> {
+    fn serialize_into<W: Write>(&self, mut writer: W) -> CircuitResult<()> {
+        write_usize(&mut writer, self.num_witnesses)?;
+        write_usize(&mut writer, self.num_private_inputs_per_witness)?;
+        write_usize(&mut writer, self.num_public_inputs_per_witness)?;
+        write_modulus::<C, _>(&mut writer)?;
+
+        for v in &self.values {
+            v.serialize_into(&mut writer)?;
         }
+        Ok(())
     }
 }
@@ -0,0 +1,5 @@
+mod builder;
+mod cache;
+mod serde;
+mod validate;
+mod witness;
@@ -0,0 +1,149 @@
+use std::io::Cursor;
+
+use arith::{Field, SimdField};
+use config::{GKRConfig, M31ExtConfigSha2};
+use transcript::{Transcript, TranscriptInstance};
+
+use crate::{
+    CircuitAssignment, CircuitBuilder, CircuitError, Coef, FromEccSerde, GateKind,
+    RecursiveCircuit, RndCoefHandle, RndCoefs, Term, ToEccSerde, Witness, POW1_GATE_TYPE,
+};
+
+type C = M31ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+// w = x0 * x1 * x2 + 2 * x0 + p, asserted to be zero
+fn test_builder() -> CircuitBuilder<C> {
+    let mut builder = CircuitBuilder::<C>::new();
+    let x = builder.inputs(3);
+    let p = builder.public_input();
+
+    let y = builder.mul(x[0], x[1]);
+    let z = builder.mul(y, x[2]);
+    let w = builder.sum(vec![
+        Term::Add(z, Coef::one()),
+        Term::Add(x[0], Coef::Constant(F::from(2))),
+        Term::Const(Coef::PublicInput(p)),
+    ]);
+    builder.assert_zero(w);
+    assert_eq!(builder.depth(w), 3);
+    builder
+}
+
+// The same private and public inputs in every SIMD lane
+fn uniform_witness(private_inputs: &[F], public_inputs: &[F]) -> Witness<C> {
+    let mut witness = Witness::<C>::new(private_inputs, public_inputs);
+    for _ in 1..C::get_field_pack_size() {
+        witness.push(private_inputs, public_inputs).unwrap();
+    }
+    witness
+}
+
+#[test]
+fn test_circuit_builder() {
+    let rc = test_builder().build_recursive().unwrap();
+    assert!(rc.validate().is_empty());
+    assert_eq!(rc.layers.len(), 3);
+    assert_eq!(rc.num_public_inputs, 1);
+    assert_eq!((rc.num_outputs, rc.expected_num_output_zeros), (1, 1));
+
+    // the circuit can be saved and loaded back
+    let mut bytes = vec![];
+    rc.serialize_into(&mut bytes).unwrap();
+    let rc = RecursiveCircuit::<C>::deserialize_from(Cursor::new(&bytes)).unwrap();
+
+    let circuit = rc.flatten();
+    assert!(circuit.validate().is_empty());
+    assert_eq!(circuit.log_input_size(), 2);
+    let witness = uniform_witness(&[2u32, 3, 5, 0].map(F::from), &[-F::from(34)]);
+    let mut assignment = CircuitAssignment::new(&circuit);
+    assignment.load_witness_batch(&witness, 0).unwrap();
+    assignment.evaluate(&circuit, &RndCoefs::default());
+
+    assert!(assignment.output_vals[0].is_zero());
+
+    // no GKR scheme proves both pow5 and mul gates
+    let mut builder = test_builder();
+    let x = builder.input();
+    let u = builder.pow5(x);
+    builder.output(u);
+    assert!(matches!(
+        builder.build_recursive(),
+        Err(CircuitError::Pow5WithMul)
+    ));
+}
+
+#[test]
+fn test_pow5_circuit_builder() {
+    // v = (x0^5)^5 + 3 * x1 + p, asserted to be zero, and u = x0^5
+    let mut builder = CircuitBuilder::<C>::new();
+    let x = builder.inputs(2);
+    let p = builder.public_input();
+    let u = builder.pow5(x[0]);
+    let v = builder.sum(vec![
+        Term::Pow5(u, Coef::one()),
+        Term::Add(x[1], Coef::Constant(F::from(3))),
+        Term::Const(Coef::PublicInput(p)),
+    ]);
+    builder.assert_zero(v);
+    builder.output(u);
+    let circuit = builder.build().unwrap();
+
+    // the add term and the relays of x1 and u are pow1 gates
+    let mut num_pow1 = 0;
+    for layer in &circuit.layers {
+        assert!(layer.mul.is_empty() && layer.add.is_empty());
+        num_pow1 += layer
+            .uni
+            .iter()
+            .filter(|gate| gate.gate_type == POW1_GATE_TYPE)
+            .count();
+    }
+    assert_eq!(num_pow1, 3);
+
+    let witness = uniform_witness(&[F::from(2), F::from(5)], &[-F::from((1 << 25) + 15)]);
+    let mut assignment = CircuitAssignment::new(&circuit);
+    assignment.load_witness_batch(&witness, 0).unwrap();
+    assignment.evaluate(&circuit, &RndCoefs::default());
+    assert!(assignment.output_vals[0].is_zero());
+    assert_eq!(assignment.output_vals[1].unpack()[0], F::from(32));
+}
+
+#[test]
+fn test_rnd_coefs() {
+    let mut builder = CircuitBuilder::<C>::new();
+    let x = builder.inputs(2);
+    let y = builder.sum(vec![
+        Term::Mul(x[0], x[1], Coef::Random),
+        Term::Const(Coef::Random),
+    ]);
+    builder.output(y);
+    let circuit = builder.build().unwrap();
+    assert_eq!(
+        circuit.rnd_coefs,
+        vec![
+            RndCoefHandle::new(0, GateKind::Mul, 0),
+            RndCoefHandle::new(0, GateKind::Const, 0),
+        ]
+    );
+
+    // the coefficients of a proof are drawn into a table, the gates are left untouched
+    let rnd_coefs = circuit.draw_rnd_coefs(&mut TranscriptInstance::new());
+    let mut transcript = TranscriptInstance::<<C as GKRConfig>::FiatShamirHashType>::new();
+    transcript.absorb_label("random coefficients");
+    let r_mul = transcript.generate_challenge::<F>();
+    let r_const = transcript.generate_challenge::<F>();
+    assert_eq!(rnd_coefs.get(circuit.rnd_coefs[0]), Some(r_mul));
+    assert_eq!(rnd_coefs.get(circuit.rnd_coefs[1]), Some(r_const));
+    assert_eq!(rnd_coefs.get(RndCoefHandle::new(0, GateKind::Add, 0)), None);
+    assert!(circuit.layers[0].mul[0].coef.is_zero());
+
+    let mut assignment = CircuitAssignment::new(&circuit);
+    assignment.input_vals[0][0] = C::circuit_field_to_simd_circuit_field(&F::from(3));
+    assignment.input_vals[0][1] = C::circuit_field_to_simd_circuit_field(&F::from(5));
+    assignment.evaluate(&circuit, &rnd_coefs);
+    assert_eq!(
+        assignment.output_vals[0],
+        C::circuit_field_to_simd_circuit_field(&(F::from(15) * r_mul + r_const))
+    );
+}
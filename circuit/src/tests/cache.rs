@@ -0,0 +1,118 @@
+use std::{fs, io::Cursor};
+
+use arith::Field;
+use config::{GKRConfig, M31ExtConfigSha2};
+
+use crate::{
+    circuit_source_hash, CircuitBuilder, CircuitError, CircuitStructure, Coef, CoefType, Gate,
+    RecursiveCircuit, Term,
+};
+
+type C = M31ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+fn test_circuit(constant: u32) -> RecursiveCircuit<C> {
+    let mut builder = CircuitBuilder::<C>::new();
+    let x = builder.inputs(3);
+    let y = builder.mul(x[0], x[1]);
+    let z = builder.sum(vec![
+        Term::Mul(y, x[2], Coef::Random),
+        Term::Add(x[0], Coef::Constant(F::from(constant))),
+        Term::Const(Coef::Random),
+    ]);
+    builder.output(z);
+    builder.build_recursive().unwrap()
+}
+
+fn assert_same_circuit(a: &CircuitStructure<C>, b: &CircuitStructure<C>) {
+    assert_eq!(a.layers.len(), b.layers.len());
+    assert_eq!(a.expected_num_output_zeros, b.expected_num_output_zeros);
+    assert_eq!(a.rnd_coefs.len(), b.rnd_coefs.len());
+    assert_eq!(a.digest(), b.digest());
+    for (a, b) in a.layers.iter().zip(b.layers.iter()) {
+        assert_eq!(a.input_var_num, b.input_var_num);
+        assert_eq!(a.output_var_num, b.output_var_num);
+        assert_eq!(
+            a.structure_info.max_degree_one,
+            b.structure_info.max_degree_one
+        );
+        assert_eq!(format!("{:?}", a.mul), format!("{:?}", b.mul));
+        assert_eq!(format!("{:?}", a.add), format!("{:?}", b.add));
+        assert_eq!(format!("{:?}", a.const_), format!("{:?}", b.const_));
+        assert_eq!(format!("{:?}", a.uni), format!("{:?}", b.uni));
+    }
+}
+
+#[test]
+fn test_flat_circuit_serde() {
+    let circuit = test_circuit(7).flatten();
+    assert_eq!(circuit.rnd_coefs.len(), 2);
+
+    let hash = [1u8; 32];
+    let mut bytes = vec![];
+    circuit.serialize_flat_into(&mut bytes, &hash).unwrap();
+    let read = CircuitStructure::<C>::deserialize_flat_from(Cursor::new(&bytes), &hash).unwrap();
+    assert_same_circuit(&circuit, &read);
+
+    assert!(matches!(
+        CircuitStructure::<C>::deserialize_flat_from(Cursor::new(&bytes), &[2u8; 32]),
+        Err(CircuitError::StaleCache)
+    ));
+    for len in 0..bytes.len() {
+        assert!(matches!(
+            CircuitStructure::<C>::deserialize_flat_from(Cursor::new(&bytes[..len]), &hash),
+            Err(CircuitError::TruncatedInput)
+        ));
+    }
+
+    // a cached circuit is validated like a circuit file
+    let mut invalid = circuit.clone();
+    invalid.layers[0].uni.push(Gate {
+        i_ids: [0],
+        o_id: 0,
+        coef_type: CoefType::Constant,
+        coef: F::one(),
+        gate_type: 7,
+    });
+    let mut bytes = vec![];
+    invalid.serialize_flat_into(&mut bytes, &hash).unwrap();
+    assert!(matches!(
+        CircuitStructure::<C>::deserialize_flat_from(Cursor::new(&bytes), &hash),
+        Err(CircuitError::InvalidCircuit(_))
+    ));
+}
+
+#[test]
+fn test_load_circuit_cached() {
+    let dir = std::env::temp_dir().join(format!("expander-circuit-cache-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let circuit_file = dir.join("circuit.txt");
+    let cache_file = dir.join("circuit.flat");
+    let (circuit_file, cache_file) = (circuit_file.to_str().unwrap(), cache_file.to_str().unwrap());
+
+    test_circuit(7).save(circuit_file).unwrap();
+    let loaded = CircuitStructure::<C>::load_circuit(circuit_file).unwrap();
+
+    // the first load writes the cache, the second one reads it
+    let built = CircuitStructure::<C>::load_circuit_cached(circuit_file, cache_file).unwrap();
+    let cache_bytes = fs::read(cache_file).unwrap();
+    let cached = CircuitStructure::<C>::load_circuit_cached(circuit_file, cache_file).unwrap();
+    assert_same_circuit(&loaded, &built);
+    assert_same_circuit(&loaded, &cached);
+
+    // a new version of the circuit file invalidates the cache
+    test_circuit(8).save(circuit_file).unwrap();
+    let hash = circuit_source_hash(&fs::read(circuit_file).unwrap());
+    assert!(matches!(
+        CircuitStructure::<C>::deserialize_flat_from(Cursor::new(&cache_bytes), &hash),
+        Err(CircuitError::StaleCache)
+    ));
+    let rebuilt = CircuitStructure::<C>::load_circuit_cached(circuit_file, cache_file).unwrap();
+    assert_same_circuit(
+        &CircuitStructure::<C>::load_circuit(circuit_file).unwrap(),
+        &rebuilt,
+    );
+    assert_ne!(fs::read(cache_file).unwrap(), cache_bytes);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
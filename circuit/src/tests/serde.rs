@@ -0,0 +1,174 @@
+use std::io::Cursor;
+
+use config::{BN254ConfigSha2, GKRConfig, M31ExtConfigSha2, SENTINEL_M31};
+
+use crate::{
+    Allocation, CircuitError, CoefType, FromEccSerde, Gate, RecursiveCircuit, Segment, ToEccSerde,
+    POW5_GATE_TYPE,
+};
+
+const VERSION_NUM: usize = 3914834606642317635; // b'CIRCUIT6'
+
+fn push_usize(bytes: &mut Vec<u8>, v: usize) {
+    bytes.extend_from_slice(&(v as u64).to_le_bytes());
+}
+
+// A single layer with one add gate: out[o_id] = 4 * in[0]
+fn add_gate_circuit_bytes(coef_type: u8, o_id: usize, layer: usize) -> Vec<u8> {
+    let mut bytes = vec![];
+    push_usize(&mut bytes, VERSION_NUM);
+    bytes.extend_from_slice(&SENTINEL_M31);
+    push_usize(&mut bytes, 0); // num_public_inputs
+    push_usize(&mut bytes, 2); // num_outputs
+    push_usize(&mut bytes, 2); // expected_num_output_zeros
+
+    push_usize(&mut bytes, 1); // segments
+    push_usize(&mut bytes, 2); // i_len
+    push_usize(&mut bytes, 2); // o_len
+    push_usize(&mut bytes, 0); // child_segs
+    push_usize(&mut bytes, 0); // gate_muls
+    push_usize(&mut bytes, 1); // gate_adds
+    push_usize(&mut bytes, 0); // i_id
+    push_usize(&mut bytes, o_id);
+    bytes.push(coef_type);
+    bytes.extend_from_slice(&4u32.to_le_bytes());
+    push_usize(&mut bytes, 0); // gate_consts
+    push_usize(&mut bytes, 0); // gate_uni
+
+    push_usize(&mut bytes, 1); // layers
+    push_usize(&mut bytes, layer);
+    bytes
+}
+
+fn load(bytes: &[u8]) -> Result<RecursiveCircuit<M31ExtConfigSha2>, CircuitError> {
+    RecursiveCircuit::<M31ExtConfigSha2>::deserialize_from(Cursor::new(bytes))
+}
+
+#[test]
+fn test_circuit_deserialize() {
+    let bytes = add_gate_circuit_bytes(1, 1, 0);
+    let rc = load(&bytes).unwrap();
+    assert_eq!(rc.segments.len(), 1);
+    assert_eq!(rc.segments[0].gate_adds.len(), 1);
+
+    let circuit = rc.flatten();
+    assert_eq!(circuit.layers.len(), 1);
+    assert_eq!(circuit.layers[0].add[0].o_id, 1);
+}
+
+#[test]
+fn test_circuit_deserialize_errors() {
+    let bytes = add_gate_circuit_bytes(1, 1, 0);
+    for len in 0..bytes.len() {
+        assert!(matches!(
+            load(&bytes[..len]),
+            Err(CircuitError::TruncatedInput)
+        ));
+    }
+
+    let mut bad_version = bytes.clone();
+    bad_version[0] ^= 1;
+    assert!(matches!(
+        load(&bad_version),
+        Err(CircuitError::VersionMismatch { .. })
+    ));
+
+    assert!(matches!(
+        RecursiveCircuit::<BN254ConfigSha2>::deserialize_from(Cursor::new(&bytes)),
+        Err(CircuitError::ModulusMismatch { .. })
+    ));
+
+    assert!(matches!(
+        load(&add_gate_circuit_bytes(3, 1, 0)),
+        Err(CircuitError::BadCoefType {
+            coef_type: 3,
+            input_num: 1
+        })
+    ));
+    assert!(matches!(
+        load(&add_gate_circuit_bytes(7, 1, 0)),
+        Err(CircuitError::BadCoefType { coef_type: 7, .. })
+    ));
+    assert!(matches!(
+        load(&add_gate_circuit_bytes(1, 2, 0)),
+        Err(CircuitError::GateIdOutOfRange { id: 2, len: 2 })
+    ));
+    assert!(matches!(
+        load(&add_gate_circuit_bytes(1, 1, 1)),
+        Err(CircuitError::SegmentIdOutOfRange { id: 1, len: 1 })
+    ));
+}
+
+fn gate<const INPUT_NUM: usize>(
+    i_ids: [usize; INPUT_NUM],
+    o_id: usize,
+    coef_type: CoefType,
+    coef: u32,
+    gate_type: usize,
+) -> Gate<M31ExtConfigSha2, INPUT_NUM> {
+    Gate {
+        i_ids,
+        o_id,
+        coef_type,
+        coef: <M31ExtConfigSha2 as GKRConfig>::CircuitField::from(coef),
+        gate_type,
+    }
+}
+
+fn save(rc: &RecursiveCircuit<M31ExtConfigSha2>) -> Vec<u8> {
+    let mut bytes = vec![];
+    rc.serialize_into(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn test_circuit_serialize() {
+    let bytes = add_gate_circuit_bytes(1, 1, 0);
+    assert_eq!(save(&load(&bytes).unwrap()), bytes);
+
+    // every gate and coefficient type, and a segment used twice by another one
+    let leaf = Segment::<M31ExtConfigSha2> {
+        i_var_num: 1,
+        o_var_num: 1,
+        gate_muls: vec![gate([0, 1], 0, CoefType::Constant, 2, 0)],
+        gate_adds: vec![gate([1], 1, CoefType::Random, 0, 0)],
+        gate_consts: vec![gate([], 0, CoefType::PublicInput(0), 0, 0)],
+        gate_uni: vec![gate([0], 1, CoefType::Constant, 5, POW5_GATE_TYPE)],
+        ..Default::default()
+    };
+    let root = Segment::<M31ExtConfigSha2> {
+        i_var_num: 2,
+        o_var_num: 2,
+        child_segs: vec![(
+            0,
+            vec![
+                Allocation {
+                    i_offset: 0,
+                    o_offset: 0,
+                },
+                Allocation {
+                    i_offset: 2,
+                    o_offset: 2,
+                },
+            ],
+        )],
+        ..Default::default()
+    };
+    let rc = RecursiveCircuit::<M31ExtConfigSha2> {
+        num_public_inputs: 1,
+        num_outputs: 4,
+        expected_num_output_zeros: 2,
+        segments: vec![leaf, root],
+        layers: vec![1, 1],
+    };
+
+    let bytes = save(&rc);
+    let loaded = load(&bytes).unwrap();
+    assert!(loaded.validate().is_empty());
+    assert_eq!(loaded.segments[0].gate_uni[0].gate_type, POW5_GATE_TYPE);
+    assert_eq!(
+        loaded.segments[0].gate_consts[0].coef_type,
+        CoefType::PublicInput(0)
+    );
+    assert_eq!(save(&loaded), bytes);
+}
@@ -0,0 +1,181 @@
+use config::{GKRConfig, M31ExtConfigSha2};
+
+use crate::{
+    Allocation, CircuitDiagnostic, CircuitLayer, CircuitStructure, CoefType, GateAdd, GateConst,
+    GateUni, Location, RecursiveCircuit, Segment,
+};
+
+type C = M31ExtConfigSha2;
+
+fn add_gate(i_id: usize, o_id: usize) -> GateAdd<C> {
+    GateAdd::<C> {
+        i_ids: [i_id],
+        o_id,
+        coef_type: CoefType::Constant,
+        coef: <C as GKRConfig>::CircuitField::from(3),
+        gate_type: 0,
+    }
+}
+
+fn two_layer_circuit() -> CircuitStructure<C> {
+    let mut circuit = CircuitStructure::<C>::default();
+    for _ in 0..2 {
+        let mut layer = CircuitLayer::<C> {
+            input_var_num: 2,
+            output_var_num: 2,
+            ..Default::default()
+        };
+        layer.add = (0..4).map(|i| add_gate(i, 3 - i)).collect();
+        circuit.layers.push(layer);
+    }
+    circuit
+}
+
+#[test]
+fn test_circuit_validate() {
+    let circuit = two_layer_circuit();
+    assert!(circuit.validate().is_empty());
+
+    let mut circuit = two_layer_circuit();
+    circuit.layers[0].add.push(add_gate(4, 0));
+    circuit.layers[0].add.push(add_gate(0, 4));
+    circuit.layers[1].add.push(add_gate(1, 2));
+    circuit.layers[1].uni.push(GateUni::<C> {
+        gate_type: 7,
+        ..add_gate(0, 0)
+    });
+    circuit.layers[1].const_.push(GateConst::<C> {
+        i_ids: [],
+        o_id: 0,
+        coef_type: CoefType::PublicInput(0),
+        coef: <C as GKRConfig>::CircuitField::from(0),
+        gate_type: 0,
+    });
+    circuit.layers[1].output_var_num = 1;
+    circuit.layers.push(CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 1,
+        ..Default::default()
+    });
+
+    let layer = |i| Location::Layer(i);
+    assert_eq!(
+        circuit.validate(),
+        vec![
+            CircuitDiagnostic::InputIdOutOfRange {
+                location: layer(0),
+                kind: "add",
+                id: 4,
+                len: 4
+            },
+            CircuitDiagnostic::OutputIdOutOfRange {
+                location: layer(0),
+                kind: "add",
+                id: 4,
+                len: 4
+            },
+            CircuitDiagnostic::OutputIdOutOfRange {
+                location: layer(1),
+                kind: "add",
+                id: 3,
+                len: 2
+            },
+            CircuitDiagnostic::OutputIdOutOfRange {
+                location: layer(1),
+                kind: "add",
+                id: 2,
+                len: 2
+            },
+            CircuitDiagnostic::OutputIdOutOfRange {
+                location: layer(1),
+                kind: "add",
+                id: 2,
+                len: 2
+            },
+            CircuitDiagnostic::DuplicateGate {
+                location: layer(1),
+                kind: "add",
+                i_ids: vec![1],
+                o_id: 2
+            },
+            CircuitDiagnostic::PublicInputOutOfRange {
+                location: layer(1),
+                idx: 0,
+                len: 0
+            },
+            CircuitDiagnostic::UnknownGateType {
+                location: layer(1),
+                gate_type: 7
+            },
+            CircuitDiagnostic::LayerSizeMismatch {
+                layer: 1,
+                output_var_num: 1,
+                next_input_var_num: 2
+            },
+        ]
+    );
+    assert!(!circuit.validate()[5].is_error());
+
+    assert_eq!(
+        CircuitStructure::<C>::default().validate(),
+        vec![CircuitDiagnostic::EmptyCircuit]
+    );
+}
+
+#[test]
+fn test_recursive_circuit_validate() {
+    let leaf = Segment::<C> {
+        i_var_num: 1,
+        o_var_num: 1,
+        gate_adds: vec![add_gate(0, 1), add_gate(1, 0)],
+        ..Default::default()
+    };
+    let root = Segment::<C> {
+        i_var_num: 2,
+        o_var_num: 2,
+        child_segs: vec![(
+            0,
+            vec![
+                Allocation {
+                    i_offset: 0,
+                    o_offset: 0,
+                },
+                Allocation {
+                    i_offset: 2,
+                    o_offset: 2,
+                },
+            ],
+        )],
+        ..Default::default()
+    };
+    let mut rc = RecursiveCircuit::<C> {
+        segments: vec![leaf, root],
+        layers: vec![1, 1],
+        ..Default::default()
+    };
+    assert!(rc.validate().is_empty());
+    assert!(rc.flatten().validate().is_empty());
+
+    // the second allocation overflows the root segment
+    rc.segments[1].child_segs[0].1[1].i_offset = 3;
+    // the leaf contains the root, which contains the leaf
+    rc.segments[0].child_segs.push((1, vec![]));
+    rc.layers.push(2);
+    assert_eq!(
+        rc.validate(),
+        vec![
+            CircuitDiagnostic::AllocationOutOfRange {
+                segment: 1,
+                child: 0,
+                i_offset: 3,
+                o_offset: 2
+            },
+            CircuitDiagnostic::SegmentCycle { segment: 0 },
+            CircuitDiagnostic::SegmentIdOutOfRange {
+                location: Location::Layer(2),
+                id: 2,
+                len: 2
+            },
+        ]
+    );
+}
@@ -0,0 +1,184 @@
+use arith::{Field, FieldForECC, SimdField};
+use config::{GKRConfig, M31ExtConfigSha2, MPIConfig};
+
+use crate::{
+    CircuitAssignment, CircuitError, CircuitLayer, CircuitStructure, FromEccSerde, ToEccSerde,
+    Witness,
+};
+
+type C = M31ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+fn push_usize(bytes: &mut Vec<u8>, v: usize) {
+    bytes.extend_from_slice(&(v as u64).to_le_bytes());
+}
+
+// Witness j has the private inputs [100 * j, 100 * j + 1] and the public input 100 * j + 2
+fn witness_bytes(num_witnesses: usize, num_private_inputs: usize) -> Vec<u8> {
+    let mut bytes = vec![];
+    push_usize(&mut bytes, num_witnesses);
+    push_usize(&mut bytes, num_private_inputs);
+    push_usize(&mut bytes, 1);
+    bytes.extend_from_slice(&F::modulus().to_le_bytes());
+    for j in 0..num_witnesses {
+        for i in 0..num_private_inputs + 1 {
+            bytes.extend_from_slice(&((100 * j + i) as u32).to_le_bytes());
+        }
+    }
+    bytes
+}
+
+fn one_layer_assignment() -> CircuitAssignment<C> {
+    let mut circuit = CircuitStructure::<C> {
+        num_public_inputs: 1,
+        ..Default::default()
+    };
+    circuit.layers.push(CircuitLayer::<C> {
+        input_var_num: 1,
+        output_var_num: 1,
+        ..Default::default()
+    });
+    CircuitAssignment::new(&circuit)
+}
+
+fn lanes(v: &<C as GKRConfig>::SimdCircuitField) -> Vec<u32> {
+    v.unpack().iter().map(|x| x.v).collect()
+}
+
+#[test]
+fn test_witness_batches() {
+    let pack_size = C::get_field_pack_size();
+    let witness = Witness::<C>::from_bytes(&witness_bytes(3 * pack_size, 2)).unwrap();
+    assert_eq!(witness.num_batches(), 3);
+
+    let mut assignment = one_layer_assignment();
+    assignment.load_witness_batch(&witness, 1).unwrap();
+    let expected = |i: usize| {
+        (pack_size..2 * pack_size)
+            .map(|j| (100 * j + i) as u32)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(lanes(&assignment.input_vals[0][0]), expected(0));
+    assert_eq!(lanes(&assignment.input_vals[0][1]), expected(1));
+    assert_eq!(lanes(&assignment.public_input[0]), expected(2));
+
+    assert!(matches!(
+        assignment.load_witness_batch(&witness, 3),
+        Err(CircuitError::WitnessBatchOutOfRange {
+            batch: 3,
+            num_batches: 3
+        })
+    ));
+
+    assert!(matches!(
+        assignment.load_witness_bytes(&witness_bytes(3 * pack_size, 2)),
+        Err(CircuitError::MultipleWitnessBatches { num_batches: 3 })
+    ));
+
+    let mpi_config = MPIConfig::new_for_verifier(3);
+    assert_eq!(witness.num_mpi_rounds(&mpi_config).unwrap(), 1);
+    assert_eq!(Witness::<C>::mpi_batch(1, &mpi_config), 3);
+    assert!(witness
+        .num_mpi_rounds(&MPIConfig::new_for_verifier(2))
+        .is_err());
+}
+
+#[test]
+fn test_witness_errors() {
+    let pack_size = C::get_field_pack_size();
+    let mut assignment = one_layer_assignment();
+
+    for num_witnesses in [0, pack_size - 1, pack_size + 1] {
+        assert!(matches!(
+            assignment.load_witness_bytes(&witness_bytes(num_witnesses, 2)),
+            Err(CircuitError::WitnessCountMismatch { .. })
+        ));
+    }
+
+    assert!(matches!(
+        assignment.load_witness_bytes(&witness_bytes(pack_size, 4)),
+        Err(CircuitError::WitnessSizeMismatch {
+            expected: 2,
+            found: 4
+        })
+    ));
+
+    let mut no_public_input = one_layer_assignment();
+    no_public_input.public_input.clear();
+    assert!(matches!(
+        no_public_input.load_witness_bytes(&witness_bytes(pack_size, 2)),
+        Err(CircuitError::PublicInputSizeMismatch {
+            expected: 0,
+            found: 1
+        })
+    ));
+
+    let mut bytes = witness_bytes(pack_size, 2);
+    bytes[24] ^= 1;
+    assert!(matches!(
+        assignment.load_witness_bytes(&bytes),
+        Err(CircuitError::ModulusMismatch { .. })
+    ));
+
+    let bytes = witness_bytes(pack_size, 2);
+    assert!(matches!(
+        assignment.load_witness_bytes(&bytes[..bytes.len() - 1]),
+        Err(CircuitError::TruncatedInput)
+    ));
+
+    // the number of values does not fit in a usize
+    for num_private_inputs in [usize::MAX, usize::MAX / 2] {
+        let mut bytes = witness_bytes(0, num_private_inputs);
+        bytes[..8].copy_from_slice(&(pack_size as u64).to_le_bytes());
+        assert!(matches!(
+            assignment.load_witness_bytes(&bytes),
+            Err(CircuitError::WitnessSizeOverflow { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_witness_builder() {
+    let pack_size = C::get_field_pack_size();
+    let instance = |j: usize| {
+        let v = |i: usize| F::from((100 * j + i) as u32);
+        ([v(0), v(1)], [v(2)])
+    };
+
+    let (private_inputs, public_inputs) = instance(0);
+    let mut witness = Witness::<C>::new(&private_inputs, &public_inputs);
+    for j in 1..2 * pack_size {
+        let (private_inputs, public_inputs) = instance(j);
+        witness.push(&private_inputs, &public_inputs).unwrap();
+    }
+    assert_eq!(witness.num_batches(), 2);
+    assert_eq!(witness.private_inputs(3), &instance(3).0);
+    assert_eq!(witness.public_inputs(3), &instance(3).1);
+    assert_eq!(
+        witness.pack_public_inputs(1)[0].unpack(),
+        (pack_size..2 * pack_size)
+            .map(|j| instance(j).1[0])
+            .collect::<Vec<_>>()
+    );
+
+    let mut bytes = vec![];
+    witness.serialize_into(&mut bytes).unwrap();
+    assert_eq!(bytes, witness_bytes(2 * pack_size, 2));
+    assert_eq!(Witness::<C>::deserialize_from(&bytes[..]).unwrap(), witness);
+
+    assert!(matches!(
+        witness.push(&[F::ZERO], &[F::ZERO]),
+        Err(CircuitError::WitnessSizeMismatch {
+            expected: 2,
+            found: 1
+        })
+    ));
+    assert!(matches!(
+        witness.push(&[F::ZERO; 2], &[]),
+        Err(CircuitError::PublicInputSizeMismatch {
+            expected: 1,
+            found: 0
+        })
+    ));
+    assert_eq!(witness.num_witnesses, 2 * pack_size);
+}
@@ -0,0 +1,370 @@
+use std::{cmp::max, collections::HashSet, fmt};
+
+use config::GKRConfig;
+use thiserror::Error;
+
+use crate::*;
+
+const KNOWN_UNI_GATE_TYPES: [usize; 2] = [POW5_GATE_TYPE, POW1_GATE_TYPE];
+
+/// Where a diagnostic was found: a layer of a flattened circuit,
+/// or a segment of a recursive circuit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Layer(usize),
+    Segment(SegmentId),
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Layer(i) => write!(f, "layer {}", i),
+            Location::Segment(i) => write!(f, "segment {}", i),
+        }
+    }
+}
+
+/// A problem found when validating a circuit.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CircuitDiagnostic {
+    #[error("{location}: {kind} gate input {id} out of range, {len} inputs")]
+    InputIdOutOfRange {
+        location: Location,
+        kind: &'static str,
+        id: usize,
+        len: usize,
+    },
+
+    #[error("{location}: {kind} gate output {id} out of range, {len} outputs")]
+    OutputIdOutOfRange {
+        location: Location,
+        kind: &'static str,
+        id: usize,
+        len: usize,
+    },
+
+    #[error("{location}: public input {idx} out of range, {len} public inputs")]
+    PublicInputOutOfRange {
+        location: Location,
+        idx: usize,
+        len: usize,
+    },
+
+    #[error("{location}: unknown uni gate type {gate_type}")]
+    UnknownGateType {
+        location: Location,
+        gate_type: usize,
+    },
+
+    #[error("{location}: duplicate {kind} gate {i_ids:?} -> {o_id}")]
+    DuplicateGate {
+        location: Location,
+        kind: &'static str,
+        i_ids: Vec<usize>,
+        o_id: usize,
+    },
+
+    #[error("layer {layer} has {output_var_num} output vars, the next layer has {next_input_var_num} input vars")]
+    LayerSizeMismatch {
+        layer: usize,
+        output_var_num: usize,
+        next_input_var_num: usize,
+    },
+
+    #[error("{location}: segment id {id} out of range, {len} segments")]
+    SegmentIdOutOfRange {
+        location: Location,
+        id: SegmentId,
+        len: usize,
+    },
+
+    #[error("segment {segment}: child segment {child} at ({i_offset}, {o_offset}) does not fit")]
+    AllocationOutOfRange {
+        segment: SegmentId,
+        child: SegmentId,
+        i_offset: usize,
+        o_offset: usize,
+    },
+
+    #[error("segment {segment} contains itself")]
+    SegmentCycle { segment: SegmentId },
+
+    #[error("the circuit has no layers")]
+    EmptyCircuit,
+}
+
+impl CircuitDiagnostic {
+    /// Whether the circuit cannot be evaluated or proven.
+    /// Duplicate gates are only reported as warnings.
+    pub fn is_error(&self) -> bool {
+        !matches!(self, CircuitDiagnostic::DuplicateGate { .. })
+    }
+}
+
+/// Log the warnings and fail on the errors among the diagnostics.
+pub(crate) fn check_diagnostics(diagnostics: Vec<CircuitDiagnostic>) -> CircuitResult<()> {
+    let (errors, warnings): (Vec<_>, Vec<_>) = diagnostics.into_iter().partition(|d| d.is_error());
+    if let Some(first) = warnings.first() {
+        log::warn!("{} circuit warnings, first: {}", warnings.len(), first);
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CircuitError::InvalidCircuit(errors))
+    }
+}
+
+fn validate_gates<C: GKRConfig, const INPUT_NUM: usize>(
+    location: Location,
+    kind: &'static str,
+    gates: &[Gate<C, INPUT_NUM>],
+    i_len: usize,
+    o_len: usize,
+    num_public_inputs: usize,
+    diagnostics: &mut Vec<CircuitDiagnostic>,
+) {
+    let mut seen = HashSet::new();
+    for gate in gates {
+        for &id in gate.i_ids.iter().filter(|&&id| id >= i_len) {
+            diagnostics.push(CircuitDiagnostic::InputIdOutOfRange {
+                location,
+                kind,
+                id,
+                len: i_len,
+            });
+        }
+        if gate.o_id >= o_len {
+            diagnostics.push(CircuitDiagnostic::OutputIdOutOfRange {
+                location,
+                kind,
+                id: gate.o_id,
+                len: o_len,
+            });
+        }
+        if let CoefType::PublicInput(idx) = gate.coef_type {
+            if idx >= num_public_inputs {
+                diagnostics.push(CircuitDiagnostic::PublicInputOutOfRange {
+                    location,
+                    idx,
+                    len: num_public_inputs,
+                });
+            }
+        }
+        if kind == "uni" && !KNOWN_UNI_GATE_TYPES.contains(&gate.gate_type) {
+            diagnostics.push(CircuitDiagnostic::UnknownGateType {
+                location,
+                gate_type: gate.gate_type,
+            });
+        }
+        if !seen.insert((
+            gate.i_ids,
+            gate.o_id,
+            gate.gate_type,
+            gate.coef_type.clone(),
+        )) {
+            diagnostics.push(CircuitDiagnostic::DuplicateGate {
+                location,
+                kind,
+                i_ids: gate.i_ids.to_vec(),
+                o_id: gate.o_id,
+            });
+        }
+    }
+}
+
+/// The gates of a layer, or of a segment.
+struct GateLists<'a, C: GKRConfig> {
+    mul: &'a [GateMul<C>],
+    add: &'a [GateAdd<C>],
+    const_: &'a [GateConst<C>],
+    uni: &'a [GateUni<C>],
+}
+
+impl<C: GKRConfig> GateLists<'_, C> {
+    fn validate(
+        &self,
+        location: Location,
+        input_var_num: usize,
+        output_var_num: usize,
+        num_public_inputs: usize,
+        diagnostics: &mut Vec<CircuitDiagnostic>,
+    ) {
+        let (i_len, o_len, n) = (1 << input_var_num, 1 << output_var_num, num_public_inputs);
+        validate_gates(location, "mul", self.mul, i_len, o_len, n, diagnostics);
+        validate_gates(location, "add", self.add, i_len, o_len, n, diagnostics);
+        validate_gates(location, "const", self.const_, i_len, o_len, n, diagnostics);
+        validate_gates(location, "uni", self.uni, i_len, o_len, n, diagnostics);
+    }
+}
+
+impl<C: GKRConfig> CircuitStructure<C> {
+    /// Check that the circuit can be evaluated: gate ids within their layer,
+    /// consecutive layers of matching sizes, public inputs in range and known gate types.
+    /// Duplicate gates are reported too, see `CircuitDiagnostic::is_error`.
+    pub fn validate(&self) -> Vec<CircuitDiagnostic> {
+        let mut diagnostics = vec![];
+        if self.layers.is_empty() {
+            diagnostics.push(CircuitDiagnostic::EmptyCircuit);
+        }
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            let gates = GateLists {
+                mul: &layer.mul,
+                add: &layer.add,
+                const_: &layer.const_,
+                uni: &layer.uni,
+            };
+            gates.validate(
+                Location::Layer(i),
+                layer.input_var_num,
+                layer.output_var_num,
+                self.num_public_inputs,
+                &mut diagnostics,
+            );
+        }
+
+        for (i, pair) in self.layers.windows(2).enumerate() {
+            if pair[0].output_var_num != pair[1].input_var_num {
+                diagnostics.push(CircuitDiagnostic::LayerSizeMismatch {
+                    layer: i,
+                    output_var_num: pair[0].output_var_num,
+                    next_input_var_num: pair[1].input_var_num,
+                });
+            }
+        }
+
+        diagnostics
+    }
+}
+
+impl<C: GKRConfig> RecursiveCircuit<C> {
+    /// Check that the circuit can be flattened into a valid `CircuitStructure`: segment ids in range,
+    /// no segment containing itself, child segments fitting in their parents,
+    /// and the checks of `CircuitStructure::validate` on each segment.
+    pub fn validate(&self) -> Vec<CircuitDiagnostic> {
+        let mut diagnostics = vec![];
+        let num_segments = self.segments.len();
+        if self.layers.is_empty() {
+            diagnostics.push(CircuitDiagnostic::EmptyCircuit);
+        }
+
+        for (seg_id, seg) in self.segments.iter().enumerate() {
+            let location = Location::Segment(seg_id);
+            let gates = GateLists {
+                mul: &seg.gate_muls,
+                add: &seg.gate_adds,
+                const_: &seg.gate_consts,
+                uni: &seg.gate_uni,
+            };
+            gates.validate(
+                location,
+                seg.i_var_num,
+                seg.o_var_num,
+                self.num_public_inputs,
+                &mut diagnostics,
+            );
+
+            for (child_id, allocs) in &seg.child_segs {
+                let child = match self.segments.get(*child_id) {
+                    Some(child) => child,
+                    None => {
+                        diagnostics.push(CircuitDiagnostic::SegmentIdOutOfRange {
+                            location,
+                            id: *child_id,
+                            len: num_segments,
+                        });
+                        continue;
+                    }
+                };
+                let fits = |offset: usize, child_var_num: usize, var_num: usize| {
+                    offset
+                        .checked_add(1 << child_var_num)
+                        .is_some_and(|end| end <= 1 << var_num)
+                };
+                for alloc in allocs {
+                    if !fits(alloc.i_offset, child.i_var_num, seg.i_var_num)
+                        || !fits(alloc.o_offset, child.o_var_num, seg.o_var_num)
+                    {
+                        diagnostics.push(CircuitDiagnostic::AllocationOutOfRange {
+                            segment: seg_id,
+                            child: *child_id,
+                            i_offset: alloc.i_offset,
+                            o_offset: alloc.o_offset,
+                        });
+                    }
+                }
+            }
+        }
+
+        self.find_segment_cycles(&mut diagnostics);
+
+        for (i, &seg_id) in self.layers.iter().enumerate() {
+            if seg_id >= num_segments {
+                diagnostics.push(CircuitDiagnostic::SegmentIdOutOfRange {
+                    location: Location::Layer(i),
+                    id: seg_id,
+                    len: num_segments,
+                });
+            }
+        }
+
+        for (i, pair) in self.layers.windows(2).enumerate() {
+            if let (Some(seg), Some(next_seg)) =
+                (self.segments.get(pair[0]), self.segments.get(pair[1]))
+            {
+                // var_num >= 1 once flattened
+                let output_var_num = max(seg.o_var_num, 1);
+                let next_input_var_num = max(next_seg.i_var_num, 1);
+                if output_var_num != next_input_var_num {
+                    diagnostics.push(CircuitDiagnostic::LayerSizeMismatch {
+                        layer: i,
+                        output_var_num,
+                        next_input_var_num,
+                    });
+                }
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Depth-first search over the child segments, without recursion so that
+    /// deeply nested circuits cannot overflow the stack.
+    fn find_segment_cycles(&self, diagnostics: &mut Vec<CircuitDiagnostic>) {
+        const UNVISITED: u8 = 0;
+        const ON_STACK: u8 = 1;
+        const DONE: u8 = 2;
+
+        let num_segments = self.segments.len();
+        let mut state = vec![UNVISITED; num_segments];
+        for root in 0..num_segments {
+            if state[root] != UNVISITED {
+                continue;
+            }
+            state[root] = ON_STACK;
+            let mut stack = vec![(root, 0usize)];
+            while let Some(&(seg_id, next)) = stack.last() {
+                let child_segs = &self.segments[seg_id].child_segs;
+                if next == child_segs.len() {
+                    state[seg_id] = DONE;
+                    stack.pop();
+                    continue;
+                }
+                stack.last_mut().unwrap().1 += 1;
+
+                let child_id = child_segs[next].0;
+                match state.get(child_id) {
+                    Some(&UNVISITED) => {
+                        state[child_id] = ON_STACK;
+                        stack.push((child_id, 0));
+                    }
+                    Some(&ON_STACK) => {
+                        diagnostics.push(CircuitDiagnostic::SegmentCycle { segment: child_id });
+                    }
+                    // done, or out of range and reported separately
+                    _ => (),
+                }
+            }
+        }
+    }
+}
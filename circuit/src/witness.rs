@@ -1,9 +1,140 @@
-use config::GKRConfig;
+use std::fs;
+use std::io::Cursor;
+use std::ops::Range;
+
+use arith::SimdField;
+use config::{GKRConfig, MPIConfig};
+
+use crate::*;
 
 // A direct copy of the witness struct from ecc
+#[derive(Debug, Clone, PartialEq)]
 pub struct Witness<C: GKRConfig> {
     pub num_witnesses: usize,
     pub num_private_inputs_per_witness: usize,
     pub num_public_inputs_per_witness: usize,
     pub values: Vec<C::CircuitField>,
 }
+
+impl<C: GKRConfig> Witness<C> {
+    /// A witness with a single instance, more can be added with `push`.
+    pub fn new(private_inputs: &[C::CircuitField], public_inputs: &[C::CircuitField]) -> Self {
+        let mut values = private_inputs.to_vec();
+        values.extend_from_slice(public_inputs);
+        Self {
+            num_witnesses: 1,
+            num_private_inputs_per_witness: private_inputs.len(),
+            num_public_inputs_per_witness: public_inputs.len(),
+            values,
+        }
+    }
+
+    /// Append an instance with as many inputs as the previous ones.
+    pub fn push(
+        &mut self,
+        private_inputs: &[C::CircuitField],
+        public_inputs: &[C::CircuitField],
+    ) -> CircuitResult<()> {
+        if private_inputs.len() != self.num_private_inputs_per_witness {
+            return Err(CircuitError::WitnessSizeMismatch {
+                expected: self.num_private_inputs_per_witness,
+                found: private_inputs.len(),
+            });
+        }
+        if public_inputs.len() != self.num_public_inputs_per_witness {
+            return Err(CircuitError::PublicInputSizeMismatch {
+                expected: self.num_public_inputs_per_witness,
+                found: public_inputs.len(),
+            });
+        }
+        self.values.extend_from_slice(private_inputs);
+        self.values.extend_from_slice(public_inputs);
+        self.num_witnesses += 1;
+        Ok(())
+    }
+
+    /// Load a witness file, checking that it holds whole SIMD batches.
+    pub fn load(filename: &str) -> CircuitResult<Self> {
+        let file_bytes = fs::read(filename)?;
+        Self::from_bytes(&file_bytes)
+    }
+
+    /// Deserialize a witness, checking that it holds whole SIMD batches.
+    pub fn from_bytes(file_bytes: &[u8]) -> CircuitResult<Self> {
+        let cursor = Cursor::new(file_bytes);
+        let witness = Self::deserialize_from(cursor)?;
+
+        let pack_size = C::get_field_pack_size();
+        if witness.num_witnesses == 0 || witness.num_witnesses % pack_size != 0 {
+            return Err(CircuitError::WitnessCountMismatch {
+                num_witnesses: witness.num_witnesses,
+                pack_size,
+            });
+        }
+        Ok(witness)
+    }
+
+    /// Number of SIMD batches, each filling the `C::get_field_pack_size()` lanes of one proof.
+    pub fn num_batches(&self) -> usize {
+        self.num_witnesses / C::get_field_pack_size()
+    }
+
+    /// Number of rounds when the batches are split across MPI ranks,
+    /// every round proving one batch per rank.
+    pub fn num_mpi_rounds(&self, mpi_config: &MPIConfig) -> CircuitResult<usize> {
+        let world_size = mpi_config.world_size();
+        if self.num_batches() % world_size != 0 {
+            return Err(CircuitError::WitnessCountMismatch {
+                num_witnesses: self.num_witnesses,
+                pack_size: C::get_field_pack_size() * world_size,
+            });
+        }
+        Ok(self.num_batches() / world_size)
+    }
+
+    /// The batch proven by this rank in the given round.
+    pub fn mpi_batch(round: usize, mpi_config: &MPIConfig) -> usize {
+        round * mpi_config.world_size() + mpi_config.world_rank()
+    }
+
+    pub fn private_inputs(&self, instance: usize) -> &[C::CircuitField] {
+        let start = instance * self.instance_size();
+        &self.values[start..start + self.num_private_inputs_per_witness]
+    }
+
+    pub fn public_inputs(&self, instance: usize) -> &[C::CircuitField] {
+        let start = instance * self.instance_size() + self.num_private_inputs_per_witness;
+        &self.values[start..start + self.num_public_inputs_per_witness]
+    }
+
+    /// Pack the private inputs of a batch: input i of the j-th instance
+    /// of the batch goes to the j-th lane of the i-th element.
+    pub fn pack_private_inputs(&self, batch: usize) -> Vec<C::SimdCircuitField> {
+        self.pack_batch(batch, 0..self.num_private_inputs_per_witness)
+    }
+
+    /// Pack the public inputs of a batch, see `pack_private_inputs`.
+    pub fn pack_public_inputs(&self, batch: usize) -> Vec<C::SimdCircuitField> {
+        let start = self.num_private_inputs_per_witness;
+        self.pack_batch(batch, start..start + self.num_public_inputs_per_witness)
+    }
+
+    #[inline]
+    fn instance_size(&self) -> usize {
+        self.num_private_inputs_per_witness + self.num_public_inputs_per_witness
+    }
+
+    fn pack_batch(&self, batch: usize, inputs: Range<usize>) -> Vec<C::SimdCircuitField> {
+        let pack_size = C::get_field_pack_size();
+        let instance_size = self.instance_size();
+        let batch_values = &self.values[batch * pack_size * instance_size..];
+        inputs
+            .map(|i| {
+                let lanes = (0..pack_size)
+                    .map(|j| batch_values[j * instance_size + i])
+                    .collect::<Vec<_>>();
+                C::SimdCircuitField::pack(&lanes)
+            })
+            .collect()
+    }
+}
@@ -1,18 +1,28 @@
 mod bn254_keccak;
+mod bn254_mimc7;
+mod bn254_poseidon;
 mod bn254_sha2;
 mod gf2_ext_keccak;
 mod gf2_ext_sha2;
+mod goldilocks_ext_keccak;
+mod goldilocks_ext_sha2;
 mod m31_ext_keccak;
+mod m31_ext_poseidon;
 mod m31_ext_sha2;
 
 use arith::{ExtensionField, Field, FieldForECC, FieldSerde, SimdField};
 use transcript::FiatShamirHash;
 
 pub use bn254_keccak::BN254ConfigKeccak;
+pub use bn254_mimc7::BN254ConfigMiMC7;
+pub use bn254_poseidon::BN254ConfigPoseidon;
 pub use bn254_sha2::BN254ConfigSha2;
 pub use gf2_ext_keccak::GF2ExtConfigKeccak;
 pub use gf2_ext_sha2::GF2ExtConfigSha2;
+pub use goldilocks_ext_keccak::GoldilocksExtConfigKeccak;
+pub use goldilocks_ext_sha2::GoldilocksExtConfigSha2;
 pub use m31_ext_keccak::M31ExtConfigKeccak;
+pub use m31_ext_poseidon::M31ExtConfigPoseidon;
 pub use m31_ext_sha2::M31ExtConfigSha2;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -20,6 +30,7 @@ pub enum FieldType {
     M31,
     BN254,
     GF2,
+    Goldilocks,
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -34,7 +45,7 @@ pub enum FiatShamirHashType {
 
 pub trait GKRConfig: Default + Clone + Send + Sync + 'static {
     /// Field type for the circuit, e.g., M31
-    type CircuitField: Field + FieldSerde + FieldForECC + Send;
+    type CircuitField: Field + FieldSerde + FieldForECC + Send + Sync;
 
     /// Field type for the challenge, e.g., M31Ext3
     type ChallengeField: ExtensionField<BaseField = Self::CircuitField> + Send;
@@ -45,7 +56,7 @@ pub trait GKRConfig: Default + Clone + Send + Sync + 'static {
         + Send;
 
     /// Simd field for circuit
-    type SimdCircuitField: SimdField<Scalar = Self::CircuitField> + FieldSerde + Send;
+    type SimdCircuitField: SimdField<Scalar = Self::CircuitField> + FieldSerde + Send + Sync;
 
     /// Fiat Shamir hash type
     type FiatShamirHashType: FiatShamirHash;
@@ -53,6 +64,9 @@ pub trait GKRConfig: Default + Clone + Send + Sync + 'static {
     /// Enum type for Self::Field
     const FIELD_TYPE: FieldType;
 
+    /// Enum type for Self::FiatShamirHashType
+    const FIAT_SHAMIR_HASH: FiatShamirHashType;
+
     /// API to allow for multiplications between the challenge and the circuit field
     fn challenge_mul_circuit_field(
         a: &Self::ChallengeField,
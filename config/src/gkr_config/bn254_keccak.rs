@@ -1,7 +1,7 @@
 use halo2curves::bn256::Fr;
 use transcript::Keccak256hasher;
 
-use super::{FieldType, GKRConfig};
+use super::{FiatShamirHashType, FieldType, GKRConfig};
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct BN254ConfigKeccak;
@@ -19,6 +19,8 @@ impl GKRConfig for BN254ConfigKeccak {
 
     const FIELD_TYPE: FieldType = FieldType::BN254;
 
+    const FIAT_SHAMIR_HASH: FiatShamirHashType = FiatShamirHashType::Keccak256;
+
     #[inline(always)]
     fn challenge_mul_circuit_field(
         a: &Self::ChallengeField,
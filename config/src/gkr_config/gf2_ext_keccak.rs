@@ -3,7 +3,7 @@ use gf2::{GF2x8, GF2};
 use gf2_128::{GF2_128x8, GF2_128};
 use transcript::Keccak256hasher;
 
-use super::{FieldType, GKRConfig};
+use super::{FiatShamirHashType, FieldType, GKRConfig};
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct GF2ExtConfigKeccak;
@@ -21,6 +21,8 @@ impl GKRConfig for GF2ExtConfigKeccak {
 
     const FIELD_TYPE: FieldType = FieldType::GF2;
 
+    const FIAT_SHAMIR_HASH: FiatShamirHashType = FiatShamirHashType::Keccak256;
+
     #[inline(always)]
     fn challenge_mul_circuit_field(
         a: &Self::ChallengeField,
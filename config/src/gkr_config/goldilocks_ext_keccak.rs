@@ -0,0 +1,95 @@
+use arith::ExtensionField;
+use goldilocks::{Goldilocks, GoldilocksExt2, GoldilocksExt2x8, Goldilocksx8};
+use transcript::Keccak256hasher;
+
+use super::{FiatShamirHashType, FieldType, GKRConfig};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GoldilocksExtConfigKeccak;
+
+impl GKRConfig for GoldilocksExtConfigKeccak {
+    type CircuitField = Goldilocks;
+
+    type SimdCircuitField = Goldilocksx8;
+
+    type ChallengeField = GoldilocksExt2;
+
+    type Field = GoldilocksExt2x8;
+
+    type FiatShamirHashType = Keccak256hasher;
+
+    const FIELD_TYPE: FieldType = FieldType::Goldilocks;
+
+    const FIAT_SHAMIR_HASH: FiatShamirHashType = FiatShamirHashType::Keccak256;
+
+    #[inline(always)]
+    fn challenge_mul_circuit_field(
+        a: &Self::ChallengeField,
+        b: &Self::CircuitField,
+    ) -> Self::ChallengeField {
+        a.mul_by_base_field(b)
+    }
+
+    #[inline(always)]
+    fn field_mul_circuit_field(a: &Self::Field, b: &Self::CircuitField) -> Self::Field {
+        // directly multiply GoldilocksExt2 with Goldilocks
+        // skipping the conversion Goldilocks -> GoldilocksExt2
+        *a * *b
+    }
+
+    #[inline(always)]
+    fn field_add_circuit_field(a: &Self::Field, b: &Self::CircuitField) -> Self::Field {
+        // directly add GoldilocksExt2 with Goldilocks
+        // skipping the conversion Goldilocks -> GoldilocksExt2
+        *a + *b
+    }
+
+    #[inline(always)]
+    fn field_add_simd_circuit_field(a: &Self::Field, b: &Self::SimdCircuitField) -> Self::Field {
+        a.add_by_base_field(b)
+    }
+
+    #[inline(always)]
+    fn field_mul_simd_circuit_field(a: &Self::Field, b: &Self::SimdCircuitField) -> Self::Field {
+        a.mul_by_base_field(b)
+    }
+
+    #[inline(always)]
+    fn challenge_mul_field(a: &Self::ChallengeField, b: &Self::Field) -> Self::Field {
+        let a_simd = Self::Field::from(*a);
+        a_simd * b
+    }
+
+    #[inline(always)]
+    fn circuit_field_into_field(a: &Self::SimdCircuitField) -> Self::Field {
+        Self::Field::from(*a)
+    }
+
+    #[inline(always)]
+    fn circuit_field_mul_simd_circuit_field(
+        a: &Self::CircuitField,
+        b: &Self::SimdCircuitField,
+    ) -> Self::SimdCircuitField {
+        Self::SimdCircuitField::from(*a) * *b
+    }
+    #[inline(always)]
+    fn circuit_field_to_simd_circuit_field(a: &Self::CircuitField) -> Self::SimdCircuitField {
+        Self::SimdCircuitField::from(*a)
+    }
+
+    #[inline(always)]
+    fn simd_circuit_field_into_field(a: &Self::SimdCircuitField) -> Self::Field {
+        Self::Field::from(*a)
+    }
+
+    #[inline(always)]
+    fn simd_circuit_field_mul_challenge_field(
+        a: &Self::SimdCircuitField,
+        b: &Self::ChallengeField,
+    ) -> Self::Field {
+        let b_simd_ext = Self::Field::from(*b);
+        Self::Field {
+            v: [b_simd_ext.v[0] * a, b_simd_ext.v[1] * a],
+        }
+    }
+}
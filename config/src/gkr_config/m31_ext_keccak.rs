@@ -2,7 +2,7 @@ use arith::ExtensionField;
 use mersenne31::{M31Ext3, M31Ext3x16, M31x16, M31};
 use transcript::Keccak256hasher;
 
-use super::{FieldType, GKRConfig};
+use super::{FiatShamirHashType, FieldType, GKRConfig};
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct M31ExtConfigKeccak;
@@ -20,6 +20,8 @@ impl GKRConfig for M31ExtConfigKeccak {
 
     const FIELD_TYPE: FieldType = FieldType::M31;
 
+    const FIAT_SHAMIR_HASH: FiatShamirHashType = FiatShamirHashType::Keccak256;
+
     #[inline(always)]
     fn challenge_mul_circuit_field(
         a: &Self::ChallengeField,
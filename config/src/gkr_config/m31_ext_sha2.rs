@@ -2,7 +2,7 @@ use arith::ExtensionField;
 use mersenne31::{M31Ext3, M31Ext3x16, M31x16, M31};
 use transcript::SHA256hasher;
 
-use super::{FieldType, GKRConfig};
+use super::{FiatShamirHashType, FieldType, GKRConfig};
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct M31ExtConfigSha2;
@@ -20,6 +20,8 @@ impl GKRConfig for M31ExtConfigSha2 {
 
     const FIELD_TYPE: FieldType = FieldType::M31;
 
+    const FIAT_SHAMIR_HASH: FiatShamirHashType = FiatShamirHashType::SHA256;
+
     #[inline(always)]
     fn challenge_mul_circuit_field(
         a: &Self::ChallengeField,
@@ -25,6 +25,15 @@ pub const SENTINEL_BN254: [u8; 32] = [
     69, 80, 184, 41, 160, 49, 225, 114, 78, 100, 48,
 ];
 
+pub const SENTINEL_GOLDILOCKS: [u8; 32] = [
+    1, 0, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0, 0,
+];
+
+pub const SENTINEL_GF2: [u8; 32] = [
+    2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum GKRScheme {
     #[default]
@@ -38,9 +47,6 @@ pub struct Config<C: GKRConfig> {
     pub field_size: usize,
     // Targeted security level for the scheme
     pub security_bits: usize,
-    #[cfg(feature = "grinding")]
-    // Grinding bits to achieve the target security level
-    pub grinding_bits: usize,
     // Polynomial commitment scheme
     pub polynomial_commitment_type: PolynomialCommitmentType,
     // Field configuration for GKR
@@ -49,19 +55,42 @@ pub struct Config<C: GKRConfig> {
     pub gkr_scheme: GKRScheme,
     // mpi config
     pub mpi_config: MPIConfig,
+    // SRS file of the KZG commitment, written by a trusted setup ceremony
+    pub kzg_srs_file: Option<String>,
 }
 
 impl<C: GKRConfig> Config<C> {
     pub fn new(gkr_scheme: GKRScheme, mpi_config: MPIConfig) -> Self {
+        let field_size = C::ChallengeField::FIELD_SIZE;
+        let security_bits = 100;
         Config {
-            field_size: C::ChallengeField::FIELD_SIZE,
-            security_bits: 100,
-            #[cfg(feature = "grinding")]
-            grinding_bits: 10,
+            field_size,
+            security_bits,
             polynomial_commitment_type: PolynomialCommitmentType::Raw,
             gkr_config: C::default(),
             gkr_scheme,
             mpi_config,
+            kzg_srs_file: None,
         }
     }
+
+    /// Number of proof-of-work bits needed on top of the soundness of the protocol
+    /// to reach the targeted security level.
+    ///
+    /// A sumcheck round with polynomials of degree `sumcheck_degree` fails with probability at
+    /// most `sumcheck_degree / |F|`, so all the rounds fail with probability at most
+    /// `num_sumcheck_rounds * sumcheck_degree / |F|`, and the queries of the polynomial
+    /// commitment opening with probability `2^-pcs_query_bits`.
+    #[cfg(feature = "grinding")]
+    pub fn grinding_bits_for(
+        &self,
+        sumcheck_degree: usize,
+        num_sumcheck_rounds: usize,
+        pcs_query_bits: f64,
+    ) -> usize {
+        let sumcheck_error =
+            (sumcheck_degree * num_sumcheck_rounds) as f64 * (-(self.field_size as f64)).exp2();
+        let soundness_bits = -(sumcheck_error + (-pcs_query_bits).exp2()).log2();
+        (self.security_bits as f64 - soundness_bits).ceil().max(0.0) as usize
+    }
 }
@@ -156,6 +156,20 @@ impl MPIConfig {
         }
     }
 
+    /// Gather the bytes of all the processes on the root, in the order of the ranks.
+    /// Every process must send the same number of bytes.
+    pub fn gather_bytes(&self, local_bytes: &[u8], global_bytes: &mut Vec<u8>) {
+        if self.world_size == 1 {
+            *global_bytes = local_bytes.to_vec()
+        } else if self.world_rank == Self::ROOT_RANK {
+            global_bytes.resize(local_bytes.len() * (self.world_size as usize), 0);
+            self.root_process()
+                .gather_into_root(local_bytes, &mut global_bytes[..]);
+        } else {
+            self.root_process().gather_into(local_bytes);
+        }
+    }
+
     /// broadcast root transcript state. incurs an additional hash if self.world_size > 1
     pub fn transcript_sync_up<H: FiatShamirHash>(&self, transcript: &mut TranscriptInstance<H>) {
         if self.world_size == 1 {
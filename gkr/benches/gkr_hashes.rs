@@ -1,4 +1,4 @@
-use circuit::Circuit;
+use circuit::{CircuitAssignment, CircuitStructure};
 use config::{BN254ConfigSha2, Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig};
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use gkr::{
@@ -10,34 +10,39 @@ use gkr::{
 };
 use std::hint::black_box;
 
-fn prover_run<C: GKRConfig>(config: &Config<C>, circuit: &mut Circuit<C>) {
+fn prover_run<C: GKRConfig>(
+    config: &Config<C>,
+    circuit: &CircuitStructure<C>,
+    assignment: &mut CircuitAssignment<C>,
+) {
     let mut prover = Prover::new(config);
-    prover.prepare_mem(circuit);
-    prover.prove(circuit);
+    prover.prepare_mem(circuit).unwrap();
+    prover.prove(circuit, assignment);
 }
 
 fn benchmark_setup<C: GKRConfig>(
     scheme: GKRScheme,
     circuit_file: &str,
     witness_file: Option<&str>,
-) -> (Config<C>, Circuit<C>) {
+) -> (Config<C>, CircuitStructure<C>, CircuitAssignment<C>) {
     let config = Config::<C>::new(scheme, MPIConfig::new());
-    let mut circuit = Circuit::<C>::load_circuit(circuit_file);
-    if witness_file.is_some() {
-        circuit.load_witness_file(witness_file.unwrap());
+    let circuit = CircuitStructure::<C>::load_circuit(circuit_file).unwrap();
+    let mut assignment = CircuitAssignment::new(&circuit);
+    if let Some(witness_file) = witness_file {
+        assignment.load_witness_file(witness_file).unwrap();
     } else {
-        circuit.set_random_input_for_test();
+        assignment.set_random_input_for_test();
     }
-    (config, circuit)
+    (config, circuit, assignment)
 }
 
 fn criterion_gkr_keccak(c: &mut Criterion) {
-    let (m31_config, mut m31_circuit) = benchmark_setup::<M31ExtConfigSha2>(
+    let (m31_config, m31_circuit, mut m31_assignment) = benchmark_setup::<M31ExtConfigSha2>(
         GKRScheme::Vanilla,
         KECCAK_M31_CIRCUIT,
         Some(KECCAK_M31_WITNESS),
     );
-    let (bn254_config, mut bn254_circuit) = benchmark_setup::<BN254ConfigSha2>(
+    let (bn254_config, bn254_circuit, mut bn254_assignment) = benchmark_setup::<BN254ConfigSha2>(
         GKRScheme::Vanilla,
         KECCAK_BN254_CIRCUIT,
         Some(KECCAK_BN254_WITNESS),
@@ -58,7 +63,7 @@ fn criterion_gkr_keccak(c: &mut Criterion) {
         |b| {
             b.iter(|| {
                 {
-                    prover_run(&m31_config, &mut m31_circuit);
+                    prover_run(&m31_config, &m31_circuit, &mut m31_assignment);
                     black_box(())
                 };
             })
@@ -76,7 +81,7 @@ fn criterion_gkr_keccak(c: &mut Criterion) {
         |b| {
             b.iter(|| {
                 {
-                    prover_run(&bn254_config, &mut bn254_circuit);
+                    prover_run(&bn254_config, &bn254_circuit, &mut bn254_assignment);
                     black_box(())
                 };
             })
@@ -85,9 +90,9 @@ fn criterion_gkr_keccak(c: &mut Criterion) {
 }
 
 fn criterion_gkr_poseidon(c: &mut Criterion) {
-    let (m31_config, mut m31_circuit) =
+    let (m31_config, m31_circuit, mut m31_assignment) =
         benchmark_setup::<M31ExtConfigSha2>(GKRScheme::GkrSquare, POSEIDON_M31_CIRCUIT, None);
-    let (bn254_config, mut bn254_circuit) =
+    let (bn254_config, bn254_circuit, mut bn254_assignment) =
         benchmark_setup::<BN254ConfigSha2>(GKRScheme::GkrSquare, POSEIDON_BN254_CIRCUIT, None);
 
     let mut group = c.benchmark_group("single thread proving poseidon by GKR^2");
@@ -104,7 +109,7 @@ fn criterion_gkr_poseidon(c: &mut Criterion) {
         |b| {
             b.iter(|| {
                 {
-                    prover_run(&m31_config, &mut m31_circuit);
+                    prover_run(&m31_config, &m31_circuit, &mut m31_assignment);
                     black_box(())
                 };
             })
@@ -122,7 +127,7 @@ fn criterion_gkr_poseidon(c: &mut Criterion) {
         |b| {
             b.iter(|| {
                 {
-                    prover_run(&bn254_config, &mut bn254_circuit);
+                    prover_run(&bn254_config, &bn254_circuit, &mut bn254_assignment);
                     black_box(())
                 };
             })
@@ -0,0 +1,292 @@
+//! A self-describing container for proofs, recording the configuration and the circuit
+//! they were produced with.
+
+use std::{
+    fmt::Debug,
+    io::{Read, Write},
+};
+
+use arith::{FieldSerde, FieldSerdeError};
+use circuit::CircuitStructure;
+use config::{
+    Config, FiatShamirHashType, FieldType, GKRConfig, GKRScheme, PolynomialCommitmentType,
+};
+use thiserror::Error;
+use transcript::Proof;
+
+pub const PROOF_MAGIC: [u8; 8] = *b"EXPANDER";
+
+pub const PROOF_VERSION: u64 = 1;
+
+#[derive(Debug, Error)]
+pub enum ProofError {
+    #[error("not an Expander proof")]
+    BadMagic,
+
+    #[error("unsupported proof version {0}, expected {PROOF_VERSION}")]
+    UnsupportedVersion(u64),
+
+    #[error("unknown {kind} identifier {id}")]
+    UnknownId { kind: &'static str, id: u8 },
+
+    #[error("the proof uses {kind} {found}, expected {expected}")]
+    ConfigMismatch {
+        kind: &'static str,
+        expected: String,
+        found: String,
+    },
+
+    #[error("the proof is for another circuit")]
+    CircuitMismatch,
+
+    #[error("proof body of {expected} bytes is truncated to {found} bytes")]
+    TruncatedBody { expected: u64, found: u64 },
+
+    #[error("serde error: {0}")]
+    Serde(#[from] FieldSerdeError),
+}
+
+impl From<std::io::Error> for ProofError {
+    fn from(e: std::io::Error) -> Self {
+        ProofError::Serde(e.into())
+    }
+}
+
+pub type ProofResult<T> = Result<T, ProofError>;
+
+/// The identifier of a variant is its position in `IDS`, new variants are appended.
+trait HeaderId: Clone + PartialEq + 'static {
+    const KIND: &'static str;
+    const IDS: &'static [Self];
+
+    fn id(&self) -> u8 {
+        Self::IDS.iter().position(|v| v == self).unwrap() as u8
+    }
+
+    fn from_id(id: u8) -> ProofResult<Self> {
+        Self::IDS
+            .get(id as usize)
+            .cloned()
+            .ok_or(ProofError::UnknownId {
+                kind: Self::KIND,
+                id,
+            })
+    }
+}
+
+macro_rules! header_id {
+    ($ty: ident, $kind: expr, [$($variant: ident),*]) => {
+        impl HeaderId for $ty {
+            const KIND: &'static str = $kind;
+            const IDS: &'static [Self] = &[$($ty::$variant),*];
+        }
+    };
+}
+
+header_id!(FieldType, "field", [M31, BN254, GF2, Goldilocks]);
+header_id!(
+    FiatShamirHashType,
+    "fiat-shamir hash",
+    [SHA256, Keccak256, Poseidon, Animoe, MIMC7]
+);
+header_id!(GKRScheme, "GKR scheme", [Vanilla, GkrSquare]);
+header_id!(
+    PolynomialCommitmentType,
+    "polynomial commitment",
+    [Raw, KZG, Orion, FRI]
+);
+
+fn write_id<T: HeaderId, W: Write>(mut writer: W, v: &T) -> ProofResult<()> {
+    Ok(v.id().serialize_into(&mut writer)?)
+}
+
+fn read_id<T: HeaderId, R: Read>(reader: R) -> ProofResult<T> {
+    T::from_id(u8::deserialize_from(reader)?)
+}
+
+fn check_eq<T: PartialEq + Debug>(kind: &'static str, expected: &T, found: &T) -> ProofResult<()> {
+    if expected != found {
+        return Err(ProofError::ConfigMismatch {
+            kind,
+            expected: format!("{:?}", expected),
+            found: format!("{:?}", found),
+        });
+    }
+    Ok(())
+}
+
+/// What a verifier needs to know before reading the proof body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofHeader {
+    pub field_type: FieldType,
+    pub fiat_shamir_hash: FiatShamirHashType,
+    pub gkr_scheme: GKRScheme,
+    pub polynomial_commitment_type: PolynomialCommitmentType,
+    pub mpi_world_size: usize,
+    /// Zero without the `grinding` feature.
+    pub grinding_bits: usize,
+    /// `CircuitStructure::digest` of the proven circuit.
+    pub circuit_digest: [u8; 32],
+}
+
+impl ProofHeader {
+    pub fn new<C: GKRConfig>(config: &Config<C>, circuit: &CircuitStructure<C>) -> Self {
+        #[cfg(feature = "grinding")]
+        let grinding_bits = crate::grinding_bits(config, circuit);
+        #[cfg(not(feature = "grinding"))]
+        let grinding_bits = 0;
+
+        Self {
+            field_type: C::FIELD_TYPE,
+            fiat_shamir_hash: C::FIAT_SHAMIR_HASH,
+            gkr_scheme: config.gkr_scheme.clone(),
+            polynomial_commitment_type: config.polynomial_commitment_type.clone(),
+            mpi_world_size: config.mpi_config.world_size(),
+            grinding_bits,
+            circuit_digest: circuit.digest(),
+        }
+    }
+
+    /// Check that a proof with this header can be verified with the expected one,
+    /// reporting the first difference.
+    pub fn check(&self, expected: &Self) -> ProofResult<()> {
+        check_eq("field", &expected.field_type, &self.field_type)?;
+        check_eq(
+            "fiat-shamir hash",
+            &expected.fiat_shamir_hash,
+            &self.fiat_shamir_hash,
+        )?;
+        check_eq("GKR scheme", &expected.gkr_scheme, &self.gkr_scheme)?;
+        check_eq(
+            "polynomial commitment",
+            &expected.polynomial_commitment_type,
+            &self.polynomial_commitment_type,
+        )?;
+        check_eq(
+            "MPI world size",
+            &expected.mpi_world_size,
+            &self.mpi_world_size,
+        )?;
+        check_eq(
+            "grinding bits",
+            &expected.grinding_bits,
+            &self.grinding_bits,
+        )?;
+        if expected.circuit_digest != self.circuit_digest {
+            return Err(ProofError::CircuitMismatch);
+        }
+        Ok(())
+    }
+
+    pub fn serialize_into<W: Write>(&self, mut writer: W) -> ProofResult<()> {
+        writer.write_all(&PROOF_MAGIC)?;
+        PROOF_VERSION.serialize_into(&mut writer)?;
+        write_id(&mut writer, &self.field_type)?;
+        write_id(&mut writer, &self.fiat_shamir_hash)?;
+        write_id(&mut writer, &self.gkr_scheme)?;
+        write_id(&mut writer, &self.polynomial_commitment_type)?;
+        self.mpi_world_size.serialize_into(&mut writer)?;
+        self.grinding_bits.serialize_into(&mut writer)?;
+        writer.write_all(&self.circuit_digest)?;
+        Ok(())
+    }
+
+    pub fn deserialize_from<R: Read>(mut reader: R) -> ProofResult<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != PROOF_MAGIC {
+            return Err(ProofError::BadMagic);
+        }
+        let version = u64::deserialize_from(&mut reader)?;
+        if version != PROOF_VERSION {
+            return Err(ProofError::UnsupportedVersion(version));
+        }
+
+        let field_type = read_id(&mut reader)?;
+        let fiat_shamir_hash = read_id(&mut reader)?;
+        let gkr_scheme = read_id(&mut reader)?;
+        let polynomial_commitment_type = read_id(&mut reader)?;
+        let mpi_world_size = usize::deserialize_from(&mut reader)?;
+        let grinding_bits = usize::deserialize_from(&mut reader)?;
+        let mut circuit_digest = [0u8; 32];
+        reader.read_exact(&mut circuit_digest)?;
+        Ok(Self {
+            field_type,
+            fiat_shamir_hash,
+            gkr_scheme,
+            polynomial_commitment_type,
+            mpi_world_size,
+            grinding_bits,
+            circuit_digest,
+        })
+    }
+}
+
+/// A proof with its header and claimed value, as written by `expander-exec prove`.
+#[derive(Debug, Clone)]
+pub struct ProofEnvelope<C: GKRConfig> {
+    pub header: ProofHeader,
+    pub claimed_v: C::ChallengeField,
+    pub proof: Proof,
+}
+
+impl<C: GKRConfig> ProofEnvelope<C> {
+    pub fn new(
+        config: &Config<C>,
+        circuit: &CircuitStructure<C>,
+        claimed_v: C::ChallengeField,
+        proof: Proof,
+    ) -> Self {
+        Self {
+            header: ProofHeader::new(config, circuit),
+            claimed_v,
+            proof,
+        }
+    }
+
+    /// Check that the proof was produced with this configuration and circuit.
+    pub fn check(&self, config: &Config<C>, circuit: &CircuitStructure<C>) -> ProofResult<()> {
+        self.header.check(&ProofHeader::new(config, circuit))
+    }
+
+    pub fn serialize_into<W: Write>(&self, mut writer: W) -> ProofResult<()> {
+        self.header.serialize_into(&mut writer)?;
+        self.claimed_v.serialize_into(&mut writer)?;
+        (self.proof.bytes.len() as u64).serialize_into(&mut writer)?;
+        writer.write_all(&self.proof.bytes)?;
+        Ok(())
+    }
+
+    /// Read a proof, failing early if it is not over the field and hash of `C`,
+    /// which are needed to read the claimed value.
+    pub fn deserialize_from<R: Read>(mut reader: R) -> ProofResult<Self> {
+        let header = ProofHeader::deserialize_from(&mut reader)?;
+        check_eq("field", &C::FIELD_TYPE, &header.field_type)?;
+        check_eq(
+            "fiat-shamir hash",
+            &C::FIAT_SHAMIR_HASH,
+            &header.fiat_shamir_hash,
+        )?;
+        let claimed_v = C::ChallengeField::deserialize_from(&mut reader)?;
+
+        // the length is not trusted for the allocation
+        let proof_len = u64::deserialize_from(&mut reader)?;
+        let mut proof = Proof::default();
+        reader
+            .by_ref()
+            .take(proof_len)
+            .read_to_end(&mut proof.bytes)?;
+        if (proof.bytes.len() as u64) < proof_len {
+            return Err(ProofError::TruncatedBody {
+                expected: proof_len,
+                found: proof.bytes.len() as u64,
+            });
+        }
+
+        Ok(Self {
+            header,
+            claimed_v,
+            proof,
+        })
+    }
+}
@@ -1,210 +1,408 @@
-// use std::{
-//     fs,
-//     io::Cursor,
-//     process::exit,
-//     sync::{Arc, Mutex},
-// };
-
-// use arith::{Field, FieldSerde};
-// use expander_rs::{
-//     BN254ConfigSha2, Circuit, Config, FieldType, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig,
-//     Proof, Prover, Verifier, SENTINEL_BN254, SENTINEL_M31,
-// };
-// use log::{debug, info};
-// use warp::{http::StatusCode, reply, Filter};
-
-// fn dump_proof_and_claimed_v<F: Field + FieldSerde>(proof: &Proof, claimed_v: &F) -> Vec<u8> {
-//     let mut bytes = Vec::new();
-
-//     proof.serialize_into(&mut bytes).unwrap(); // TODO: error propagation
-//     claimed_v.serialize_into(&mut bytes).unwrap(); // TODO: error propagation
-
-//     bytes
-// }
-
-// fn load_proof_and_claimed_v<F: Field + FieldSerde>(bytes: &[u8]) -> (Proof, F) {
-//     let mut cursor = Cursor::new(bytes);
-
-//     let proof = Proof::deserialize_from(&mut cursor).unwrap(); // TODO: error propagation
-//     let claimed_v = F::deserialize_from(&mut cursor).unwrap(); // TODO: error propagation
-
-//     (proof, claimed_v)
-// }
-
-// fn detect_field_type_from_circuit_file(circuit_file: &str) -> FieldType {
-//     // read last 32 byte of sentinel field element to determine field type
-//     let bytes = fs::read(circuit_file).expect("Unable to read circuit file.");
-//     let field_bytes = &bytes[8..8 + 32];
-//     match field_bytes.try_into().unwrap() {
-//         SENTINEL_M31 => FieldType::M31,
-//         SENTINEL_BN254 => FieldType::BN254,
-//         _ => {
-//             println!("Unknown field type. Field byte value: {:?}", field_bytes);
-//             exit(1);
-//         }
-//     }
-// }
-
-// async fn run_command<'a, C: GKRConfig>(
-//     command: &str,
-//     circuit_file: &str,
-//     config: Config<C>,
-//     args: &[String],
-// ) {
-//     match command {
-//         "prove" => {
-//             let witness_file = &args[3];
-//             let output_file = &args[4];
-//             let mut circuit = Circuit::<C>::load_circuit(circuit_file);
-//             circuit.load_witness_file(witness_file);
-//             circuit.evaluate();
-//             let mut prover = Prover::new(&config);
-//             prover.prepare_mem(&circuit);
-//             let (claimed_v, proof) = prover.prove(&mut circuit);
-//             let bytes = dump_proof_and_claimed_v(&proof, &claimed_v);
-//             fs::write(output_file, bytes).expect("Unable to write proof to file.");
-//         }
-//         "verify" => {
-//             let witness_file = &args[3];
-//             let output_file = &args[4];
-//             let mut circuit = Circuit::<C>::load_circuit(circuit_file);
-//             circuit.load_witness_file(witness_file);
-//             let bytes = fs::read(output_file).expect("Unable to read proof from file.");
-//             let (proof, claimed_v) = load_proof_and_claimed_v(&bytes);
-//             let verifier = Verifier::new(&config);
-//             assert!(verifier.verify(&mut circuit, &claimed_v, &proof));
-//             println!("success");
-//         }
-//         "serve" => {
-//             let host: [u8; 4] = args[3]
-//                 .split('.')
-//                 .map(|s| s.parse().unwrap())
-//                 .collect::<Vec<u8>>()
-//                 .try_into()
-//                 .unwrap();
-//             let port = args[4].parse().unwrap();
-//             let circuit = Circuit::<C>::load_circuit(circuit_file);
-//             let mut prover = Prover::new(&config);
-//             prover.prepare_mem(&circuit);
-//             let verifier = Verifier::new(&config);
-//             let circuit = Arc::new(Mutex::new(circuit));
-//             let circuit_clone_for_verifier = circuit.clone();
-//             let prover = Arc::new(Mutex::new(prover));
-//             let verifier = Arc::new(Mutex::new(verifier));
-//             let ready_time = chrono::offset::Utc::now();
-//             let ready = warp::path("ready").map(move || {
-//                 info!("Received ready request.");
-//                 reply::with_status(format!("Ready since {:?}", ready_time), StatusCode::OK)
-//             });
-//             let prove =
-//                 warp::path("prove")
-//                     .and(warp::body::bytes())
-//                     .map(move |bytes: bytes::Bytes| {
-//                         info!("Received prove request.");
-//                         let witness_bytes: Vec<u8> = bytes.to_vec();
-//                         let mut circuit = circuit.lock().unwrap();
-//                         let mut prover = prover.lock().unwrap();
-//                         if circuit.load_witness_bytes(&witness_bytes).is_err() {
-//                             reply::with_status(vec![], StatusCode::BAD_REQUEST)
-//                         } else {
-//                             circuit.evaluate();
-//                             let (claimed_v, proof) = prover.prove(&mut circuit);
-//                             reply::with_status(
-//                                 dump_proof_and_claimed_v(&proof, &claimed_v),
-//                                 StatusCode::OK,
-//                             )
-//                         }
-//                     });
-//             let verify =
-//                 warp::path("verify")
-//                     .and(warp::body::bytes())
-//                     .map(move |bytes: bytes::Bytes| {
-//                         info!("Received verify request.");
-//                         let witness_and_proof_bytes: Vec<u8> = bytes.to_vec();
-//                         let length_of_witness_bytes =
-//                             u64::from_le_bytes(witness_and_proof_bytes[0..8].try_into().unwrap())
-//                                 as usize;
-//                         let length_of_proof_bytes =
-//                             u64::from_le_bytes(witness_and_proof_bytes[8..16].try_into().unwrap())
-//                                 as usize;
-//                         let witness_bytes =
-//                             &witness_and_proof_bytes[16..16 + length_of_witness_bytes];
-//                         let proof_bytes = &witness_and_proof_bytes[16 + length_of_witness_bytes
-//                             ..16 + length_of_witness_bytes + length_of_proof_bytes];
-
-//                         let mut circuit = circuit_clone_for_verifier.lock().unwrap();
-//                         let verifier = verifier.lock().unwrap();
-//                         if circuit.load_witness_bytes(witness_bytes).is_err() {
-//                             "failure".to_string()
-//                         } else {
-//                             let (proof, claimed_v) = load_proof_and_claimed_v(proof_bytes);
-//                             if verifier.verify(&mut circuit, &claimed_v, &proof) {
-//                                 "success".to_string()
-//                             } else {
-//                                 "failure".to_string()
-//                             }
-//                         }
-//                     });
-//             warp::serve(
-//                 warp::post()
-//                     .and(prove.or(verify))
-//                     .or(warp::get().and(ready)),
-//             )
-//             .run((host, port))
-//             .await;
-//         }
-//         _ => {
-//             println!("Invalid command.");
-//         }
-//     }
-// }
-
-// #[tokio::main]
-// async fn main() {
-//     // examples:
-//     // expander-exec prove <input:circuit_file> <input:witness_file> <output:proof>
-//     // expander-exec verify <input:circuit_file> <input:witness_file> <input:proof>
-//     // expander-exec serve <input:circuit_file> <input:ip> <input:port>
-//     let mpi_config = MPIConfig::new();
-
-//     let args = std::env::args().collect::<Vec<String>>();
-//     if args.len() < 4 {
-//         println!(
-//             "Usage: expander-exec prove <input:circuit_file> <input:witness_file> <output:proof>"
-//         );
-//         println!(
-//             "Usage: expander-exec verify <input:circuit_file> <input:witness_file> <input:proof>"
-//         );
-//         println!("Usage: expander-exec serve <input:circuit_file> <input:host> <input:port>");
-//         return;
-//     }
-//     let command = &args[1];
-//     let circuit_file = &args[2];
-//     let field_type = detect_field_type_from_circuit_file(circuit_file);
-//     debug!("field type: {:?}", field_type);
-//     match field_type {
-//         FieldType::M31 => {
-//             run_command::<M31ExtConfigSha2>(
-//                 command,
-//                 circuit_file,
-//                 Config::<M31ExtConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone()),
-//                 &args,
-//             )
-//             .await;
-//         }
-//         FieldType::BN254 => {
-//             run_command::<BN254ConfigSha2>(
-//                 command,
-//                 circuit_file,
-//                 Config::<BN254ConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone()),
-//                 &args,
-//             )
-//             .await;
-//         }
-//         _ => unreachable!(),
-//     }
-
-//     MPIConfig::finalize();
-// }
-
-fn main() {}
+use std::{
+    fs,
+    panic::{self, AssertUnwindSafe},
+    process::ExitCode,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use circuit::{CircuitAssignment, CircuitError, CircuitResult, CircuitStructure, Witness};
+use config::{
+    BN254ConfigSha2, Config, FieldType, GF2ExtConfigSha2, GKRConfig, GKRScheme,
+    GoldilocksExtConfigSha2, M31ExtConfigSha2, MPIConfig, SENTINEL_BN254, SENTINEL_GF2,
+    SENTINEL_GOLDILOCKS, SENTINEL_M31,
+};
+use gkr::{ProofEnvelope, ProofHeader, Prover, Verifier};
+use log::{debug, info};
+use warp::{http::StatusCode, reply, Filter};
+
+const USAGE: &str = "\
+Usage: expander-exec prove <input:circuit_file> <input:witness_file> <output:proof>
+       expander-exec verify <input:circuit_file> <input:witness_file> <input:proof>
+       expander-exec serve <input:circuit_file> <input:host> <input:port>";
+
+// Proving and verifying assume a circuit and inputs of matching sizes and may still panic.
+// When serving, catch it so that the request fails alone instead of taking the service down.
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Option<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).ok()
+}
+
+// A witness holds several SIMD batches. They are proven in rounds, each rank proving one batch
+// per round, and the proof file holds the envelopes of the rounds one after the other.
+fn load_assignments<C: GKRConfig>(
+    circuit: &CircuitStructure<C>,
+    witness: &Witness<C>,
+    mpi_config: &MPIConfig,
+) -> CircuitResult<Vec<CircuitAssignment<C>>> {
+    (0..witness.num_mpi_rounds(mpi_config)?)
+        .map(|round| {
+            let mut assignment = CircuitAssignment::new(circuit);
+            assignment.load_witness_batch(witness, Witness::<C>::mpi_batch(round, mpi_config))?;
+            Ok(assignment)
+        })
+        .collect()
+}
+
+fn prove_rounds<C: GKRConfig>(
+    header: &ProofHeader,
+    prover: &mut Prover<C>,
+    circuit: &CircuitStructure<C>,
+    assignments: &mut [CircuitAssignment<C>],
+) -> Vec<ProofEnvelope<C>> {
+    assignments
+        .iter_mut()
+        .map(|assignment| {
+            let (claimed_v, proof) = prover.prove(circuit, assignment);
+            ProofEnvelope {
+                header: header.clone(),
+                claimed_v,
+                proof,
+            }
+        })
+        .collect()
+}
+
+fn dump_proofs<C: GKRConfig>(envelopes: &[ProofEnvelope<C>]) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    for envelope in envelopes {
+        envelope
+            .serialize_into(&mut bytes)
+            .map_err(|e| format!("Unable to serialize proof: {}", e))?;
+    }
+    Ok(bytes)
+}
+
+// Mismatched proofs are reported as errors, unlike proofs failing verification.
+fn load_proof<C: GKRConfig>(
+    header: &ProofHeader,
+    reader: &mut &[u8],
+) -> Result<ProofEnvelope<C>, String> {
+    let envelope = ProofEnvelope::<C>::deserialize_from(reader)
+        .map_err(|e| format!("Unable to deserialize proof: {}", e))?;
+    envelope
+        .header
+        .check(header)
+        .map_err(|e| format!("Proof does not match: {}", e))?;
+    Ok(envelope)
+}
+
+fn detect_field_type_from_circuit_file(circuit_file: &str) -> Result<FieldType, String> {
+    // the field modulus follows the 8-byte version number in the circuit header
+    let bytes = fs::read(circuit_file)
+        .map_err(|e| format!("Unable to read circuit file {}: {}", circuit_file, e))?;
+    let field_bytes: [u8; 32] = bytes
+        .get(8..8 + 32)
+        .ok_or("Circuit file is truncated.")?
+        .try_into()
+        .unwrap();
+    match field_bytes {
+        SENTINEL_M31 => Ok(FieldType::M31),
+        SENTINEL_BN254 => Ok(FieldType::BN254),
+        SENTINEL_GF2 => Ok(FieldType::GF2),
+        SENTINEL_GOLDILOCKS => Ok(FieldType::Goldilocks),
+        _ => Err(format!(
+            "Unknown field type. Field byte value: {:?}",
+            field_bytes
+        )),
+    }
+}
+
+fn load_circuit<C: GKRConfig>(circuit_file: &str) -> Result<CircuitStructure<C>, String> {
+    CircuitStructure::<C>::load_circuit(circuit_file)
+        .map_err(|e| format!("Unable to load circuit file {}: {}", circuit_file, e))
+}
+
+// The proof of each round is checked against the public inputs of the batches of all the ranks.
+fn verify_proofs<C: GKRConfig>(
+    header: &ProofHeader,
+    verifier: &Verifier<C>,
+    circuit: &CircuitStructure<C>,
+    witness: &Witness<C>,
+    proof_bytes: &[u8],
+) -> Result<bool, String> {
+    if witness.num_public_inputs_per_witness != circuit.num_public_inputs {
+        return Err(format!(
+            "Unable to load witness: {}",
+            CircuitError::PublicInputSizeMismatch {
+                expected: circuit.num_public_inputs,
+                found: witness.num_public_inputs_per_witness,
+            }
+        ));
+    }
+    let world_size = header.mpi_world_size;
+    if witness.num_batches() % world_size != 0 {
+        return Err(format!(
+            "Unable to load witness: {} batches for {} processes",
+            witness.num_batches(),
+            world_size
+        ));
+    }
+
+    let mut reader = proof_bytes;
+    for round in 0..witness.num_batches() / world_size {
+        let envelope = load_proof::<C>(header, &mut reader)?;
+        let public_input = (round * world_size..(round + 1) * world_size)
+            .flat_map(|batch| witness.pack_public_inputs(batch))
+            .collect::<Vec<_>>();
+        let verified = verifier
+            .verify(circuit, &public_input, &envelope.claimed_v, &envelope.proof)
+            .map_err(|e| format!("Unable to read proof: {}", e))?;
+        if !verified {
+            return Ok(false);
+        }
+    }
+    if !reader.is_empty() {
+        return Err(format!("{} trailing bytes after the proofs", reader.len()));
+    }
+    Ok(true)
+}
+
+fn load_witness<C: GKRConfig>(witness_file: &str) -> Result<Witness<C>, String> {
+    Witness::<C>::load(witness_file)
+        .map_err(|e| format!("Unable to load witness file {}: {}", witness_file, e))
+}
+
+fn parse_host(host: &str) -> Result<[u8; 4], String> {
+    host.split('.')
+        .map(|s| s.parse::<u8>())
+        .collect::<Result<Vec<u8>, _>>()
+        .ok()
+        .and_then(|octets| octets.try_into().ok())
+        .ok_or_else(|| format!("Invalid host {}, expecting an IPv4 address.", host))
+}
+
+async fn run_command<C: GKRConfig>(
+    command: &str,
+    circuit_file: &str,
+    config: Config<C>,
+    args: &[String],
+) -> Result<(), String> {
+    match command {
+        "prove" => {
+            let witness_file = &args[3];
+            let output_file = &args[4];
+            let circuit = load_circuit::<C>(circuit_file)?;
+            let witness = load_witness::<C>(witness_file)?;
+            let mut assignments = load_assignments(&circuit, &witness, &config.mpi_config)
+                .map_err(|e| format!("Unable to load witness file {}: {}", witness_file, e))?;
+            let mut prover = Prover::new(&config);
+            prover
+                .prepare_mem(&circuit)
+                .map_err(|e| format!("Unable to set up the commitment: {}", e))?;
+            let header = ProofHeader::new(&config, &circuit);
+            let envelopes = prove_rounds(&header, &mut prover, &circuit, &mut assignments);
+            if config.mpi_config.is_root() {
+                let bytes = dump_proofs(&envelopes)?;
+                fs::write(output_file, bytes)
+                    .map_err(|e| format!("Unable to write proof to {}: {}", output_file, e))?;
+            }
+        }
+        "verify" => {
+            let witness_file = &args[3];
+            let proof_file = &args[4];
+            let circuit = load_circuit::<C>(circuit_file)?;
+            let witness = load_witness::<C>(witness_file)?;
+            let bytes = fs::read(proof_file)
+                .map_err(|e| format!("Unable to read proof from {}: {}", proof_file, e))?;
+            let mut verifier = Verifier::new(&config);
+            verifier
+                .setup(&circuit)
+                .map_err(|e| format!("Unable to set up the commitment: {}", e))?;
+            let header = ProofHeader::new(&config, &circuit);
+            if !verify_proofs(&header, &verifier, &circuit, &witness, &bytes)? {
+                return Err("Verification failed.".to_string());
+            }
+            println!("success");
+        }
+        "serve" => {
+            let host = parse_host(&args[3])?;
+            let port = args[4]
+                .parse::<u16>()
+                .map_err(|e| format!("Invalid port {}: {}", args[4], e))?;
+            let circuit = load_circuit::<C>(circuit_file)?;
+            let mut prover = Prover::new(&config);
+            prover
+                .prepare_mem(&circuit)
+                .map_err(|e| format!("Unable to set up the commitment: {}", e))?;
+            let mut verifier = Verifier::new(&config);
+            verifier
+                .setup(&circuit)
+                .map_err(|e| format!("Unable to set up the commitment: {}", e))?;
+            let mpi_config = config.mpi_config.clone();
+            // the header is computed once
+            let header = ProofHeader::new(&config, &circuit);
+            let header_clone_for_verifier = header.clone();
+            let circuit = Arc::new(circuit);
+            let circuit_clone_for_verifier = circuit.clone();
+            // the verifier is only read, the prover is reused after a failed request
+            let prover = Arc::new(Mutex::new(prover));
+            let verifier = Arc::new(verifier);
+            let ready_time = chrono::offset::Utc::now();
+            let ready = warp::path("ready").map(move || {
+                info!("Received ready request.");
+                reply::with_status(format!("Ready since {:?}", ready_time), StatusCode::OK)
+            });
+            let prove =
+                warp::path("prove")
+                    .and(warp::body::bytes())
+                    .map(move |bytes: bytes::Bytes| {
+                        info!("Received prove request.");
+                        let witness_bytes: Vec<u8> = bytes.to_vec();
+                        let assignments = Witness::<C>::from_bytes(&witness_bytes)
+                            .and_then(|witness| load_assignments(&circuit, &witness, &mpi_config));
+                        let mut assignments = match assignments {
+                            Ok(assignments) => assignments,
+                            Err(e) => {
+                                info!("Unable to load witness: {}", e);
+                                return reply::with_status(vec![], StatusCode::BAD_REQUEST);
+                            }
+                        };
+                        let mut prover = prover.lock().unwrap_or_else(PoisonError::into_inner);
+                        let Some(envelopes) = catch_panic(|| {
+                            prove_rounds(&header, &mut prover, &circuit, &mut assignments)
+                        }) else {
+                            info!("Proving failed.");
+                            return reply::with_status(vec![], StatusCode::INTERNAL_SERVER_ERROR);
+                        };
+                        match dump_proofs(&envelopes) {
+                            Ok(bytes) => reply::with_status(bytes, StatusCode::OK),
+                            Err(_) => reply::with_status(vec![], StatusCode::INTERNAL_SERVER_ERROR),
+                        }
+                    });
+            let verify =
+                warp::path("verify")
+                    .and(warp::body::bytes())
+                    .map(move |bytes: bytes::Bytes| {
+                        info!("Received verify request.");
+                        let witness_and_proof_bytes: Vec<u8> = bytes.to_vec();
+                        let Some((witness_bytes, proof_bytes)) =
+                            split_witness_and_proof(&witness_and_proof_bytes)
+                        else {
+                            return "failure".to_string();
+                        };
+
+                        let witness = match Witness::<C>::from_bytes(witness_bytes) {
+                            Ok(witness) => witness,
+                            Err(e) => {
+                                info!("Unable to load witness: {}", e);
+                                return "failure".to_string();
+                            }
+                        };
+                        let result = catch_panic(|| {
+                            verify_proofs(
+                                &header_clone_for_verifier,
+                                &verifier,
+                                &circuit_clone_for_verifier,
+                                &witness,
+                                proof_bytes,
+                            )
+                        })
+                        .unwrap_or_else(|| Err("Verification failed.".to_string()));
+                        match result {
+                            Ok(true) => "success".to_string(),
+                            Ok(false) => "failure".to_string(),
+                            Err(e) => {
+                                info!("{}", e);
+                                "failure".to_string()
+                            }
+                        }
+                    });
+            warp::serve(
+                warp::post()
+                    .and(prove.or(verify))
+                    .or(warp::get().and(ready)),
+            )
+            .run((host, port))
+            .await;
+        }
+        _ => {
+            return Err(format!("Invalid command {}.\n{}", command, USAGE));
+        }
+    }
+
+    Ok(())
+}
+
+// The verify request body is laid out as
+// `witness_len: u64 | proof_len: u64 | witness bytes | proof bytes`.
+fn split_witness_and_proof(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let witness_len = u64::from_le_bytes(bytes.get(0..8)?.try_into().unwrap()) as usize;
+    let proof_len = u64::from_le_bytes(bytes.get(8..16)?.try_into().unwrap()) as usize;
+    let rest = &bytes[16..];
+    let witness_bytes = rest.get(..witness_len)?;
+    let proof_bytes = rest.get(witness_len..witness_len.checked_add(proof_len)?)?;
+    Some((witness_bytes, proof_bytes))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    // examples:
+    // expander-exec prove <input:circuit_file> <input:witness_file> <output:proof>
+    // expander-exec verify <input:circuit_file> <input:witness_file> <input:proof>
+    // expander-exec serve <input:circuit_file> <input:ip> <input:port>
+    env_logger::init();
+    let args = std::env::args().collect::<Vec<String>>();
+    if args.len() < 5 {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+    let command = &args[1];
+    let circuit_file = &args[2];
+    let field_type = match detect_field_type_from_circuit_file(circuit_file) {
+        Ok(field_type) => field_type,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    debug!("field type: {:?}", field_type);
+
+    let mpi_config = MPIConfig::new();
+    let result = match field_type {
+        FieldType::M31 => {
+            run_command::<M31ExtConfigSha2>(
+                command,
+                circuit_file,
+                Config::<M31ExtConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone()),
+                &args,
+            )
+            .await
+        }
+        FieldType::BN254 => {
+            run_command::<BN254ConfigSha2>(
+                command,
+                circuit_file,
+                Config::<BN254ConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone()),
+                &args,
+            )
+            .await
+        }
+        FieldType::GF2 => {
+            run_command::<GF2ExtConfigSha2>(
+                command,
+                circuit_file,
+                Config::<GF2ExtConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone()),
+                &args,
+            )
+            .await
+        }
+        FieldType::Goldilocks => {
+            run_command::<GoldilocksExtConfigSha2>(
+                command,
+                circuit_file,
+                Config::<GoldilocksExtConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone()),
+                &args,
+            )
+            .await
+        }
+    };
+
+    MPIConfig::finalize();
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
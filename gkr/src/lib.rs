@@ -9,6 +9,9 @@ pub use prover::*;
 pub mod verifier;
 pub use verifier::*;
 
+pub mod envelope;
+pub use envelope::*;
+
 pub mod utils;
 
 #[cfg(test)]
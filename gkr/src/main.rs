@@ -3,7 +3,7 @@ use std::{
     thread,
 };
 
-use circuit::Circuit;
+use circuit::{CircuitAssignment, CircuitStructure};
 use clap::Parser;
 use config::{
     BN254ConfigSha2, Config, FieldType, GF2ExtConfigSha2, GKRConfig, GKRScheme, M31ExtConfigSha2,
@@ -91,16 +91,20 @@ fn run_benchmark<C: GKRConfig>(args: &Args, config: Config<C>) {
     let pack_size = C::get_field_pack_size();
 
     // load circuit
-    let mut circuit_template = match args.scheme.as_str() {
+    let circuit = match args.scheme.as_str() {
         "keccak" => match C::FIELD_TYPE {
-            FieldType::GF2 => Circuit::<C>::load_circuit(KECCAK_GF2_CIRCUIT),
-            FieldType::M31 => Circuit::<C>::load_circuit(KECCAK_M31_CIRCUIT),
-            FieldType::BN254 => Circuit::<C>::load_circuit(KECCAK_BN254_CIRCUIT),
+            FieldType::GF2 => CircuitStructure::<C>::load_circuit(KECCAK_GF2_CIRCUIT).unwrap(),
+            FieldType::M31 => CircuitStructure::<C>::load_circuit(KECCAK_M31_CIRCUIT).unwrap(),
+            FieldType::BN254 => CircuitStructure::<C>::load_circuit(KECCAK_BN254_CIRCUIT).unwrap(),
+            FieldType::Goldilocks => unreachable!(),
         },
         "poseidon" => match C::FIELD_TYPE {
             FieldType::GF2 => unreachable!(),
-            FieldType::M31 => Circuit::<C>::load_circuit(POSEIDON_M31_CIRCUIT),
-            FieldType::BN254 => Circuit::<C>::load_circuit(POSEIDON_BN254_CIRCUIT),
+            FieldType::M31 => CircuitStructure::<C>::load_circuit(POSEIDON_M31_CIRCUIT).unwrap(),
+            FieldType::BN254 => {
+                CircuitStructure::<C>::load_circuit(POSEIDON_BN254_CIRCUIT).unwrap()
+            }
+            FieldType::Goldilocks => unreachable!(),
         },
 
         _ => unreachable!(),
@@ -110,8 +114,10 @@ fn run_benchmark<C: GKRConfig>(args: &Args, config: Config<C>) {
         FieldType::GF2 => KECCAK_GF2_WITNESS,
         FieldType::M31 => KECCAK_M31_WITNESS,
         FieldType::BN254 => KECCAK_BN254_WITNESS,
+        FieldType::Goldilocks => unreachable!(),
     };
-    circuit_template.load_witness_file(witness_path);
+    let mut assignment_template = CircuitAssignment::new(&circuit);
+    assignment_template.load_witness_file(witness_path).unwrap();
 
     let circuit_copy_size: usize = match (C::FIELD_TYPE, args.scheme.as_str()) {
         (FieldType::GF2, "keccak") => 1,
@@ -122,29 +128,28 @@ fn run_benchmark<C: GKRConfig>(args: &Args, config: Config<C>) {
         _ => unreachable!(),
     };
 
-    let circuits = (0..args.threads)
-        .map(|_| {
-            let mut c = circuit_template.clone();
-            c.evaluate();
-            c
-        })
+    // the circuit is shared by the threads, each one proves its own copy of the witness
+    let circuit = Arc::new(circuit);
+    let assignments = (0..args.threads)
+        .map(|_| assignment_template.clone())
         .collect::<Vec<_>>();
 
     println!("Circuit loaded!");
 
     let start_time = std::time::Instant::now();
-    let _ = circuits
+    let _ = assignments
         .into_iter()
         .enumerate()
-        .map(|(i, mut c)| {
+        .map(|(i, mut assignment)| {
             let partial_proof_cnt = partial_proof_cnts[i].clone();
             let local_config = config.clone();
+            let circuit = circuit.clone();
             thread::spawn(move || {
                 loop {
                     // bench func
                     let mut prover = Prover::new(&local_config);
-                    prover.prepare_mem(&c);
-                    prover.prove(&mut c);
+                    prover.prepare_mem(&circuit).unwrap();
+                    prover.prove(&circuit, &mut assignment);
                     // update cnt
                     let mut cnt = partial_proof_cnt.lock().unwrap();
                     let proof_cnt_this_round = circuit_copy_size * pack_size;
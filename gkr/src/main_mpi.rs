@@ -1,4 +1,4 @@
-use circuit::Circuit;
+use circuit::{CircuitAssignment, CircuitStructure, Witness};
 use clap::Parser;
 use config::{
     BN254ConfigSha2, Config, FieldType, GF2ExtConfigSha2, GKRConfig, GKRScheme, M31ExtConfigSha2,
@@ -79,16 +79,20 @@ fn run_benchmark<C: GKRConfig>(args: &Args, config: Config<C>) {
     let pack_size = C::get_field_pack_size();
 
     // load circuit
-    let mut circuit = match args.scheme.as_str() {
+    let circuit = match args.scheme.as_str() {
         "keccak" => match C::FIELD_TYPE {
-            FieldType::GF2 => Circuit::<C>::load_circuit(KECCAK_GF2_CIRCUIT),
-            FieldType::M31 => Circuit::<C>::load_circuit(KECCAK_M31_CIRCUIT),
-            FieldType::BN254 => Circuit::<C>::load_circuit(KECCAK_BN254_CIRCUIT),
+            FieldType::GF2 => CircuitStructure::<C>::load_circuit(KECCAK_GF2_CIRCUIT).unwrap(),
+            FieldType::M31 => CircuitStructure::<C>::load_circuit(KECCAK_M31_CIRCUIT).unwrap(),
+            FieldType::BN254 => CircuitStructure::<C>::load_circuit(KECCAK_BN254_CIRCUIT).unwrap(),
+            FieldType::Goldilocks => unreachable!(),
         },
         "poseidon" => match C::FIELD_TYPE {
             FieldType::GF2 => unreachable!(),
-            FieldType::M31 => Circuit::<C>::load_circuit(POSEIDON_M31_CIRCUIT),
-            FieldType::BN254 => Circuit::<C>::load_circuit(POSEIDON_BN254_CIRCUIT),
+            FieldType::M31 => CircuitStructure::<C>::load_circuit(POSEIDON_M31_CIRCUIT).unwrap(),
+            FieldType::BN254 => {
+                CircuitStructure::<C>::load_circuit(POSEIDON_BN254_CIRCUIT).unwrap()
+            }
+            FieldType::Goldilocks => unreachable!(),
         },
         _ => unreachable!(),
     };
@@ -97,8 +101,22 @@ fn run_benchmark<C: GKRConfig>(args: &Args, config: Config<C>) {
         FieldType::GF2 => KECCAK_GF2_WITNESS,
         FieldType::M31 => KECCAK_M31_WITNESS,
         FieldType::BN254 => KECCAK_BN254_WITNESS,
+        FieldType::Goldilocks => unreachable!(),
     };
-    circuit.load_witness_file(witness_path);
+    // the batches are spread over the ranks, one per rank and round,
+    // and the ranks past the last batch reuse the first ones
+    let witness = Witness::<C>::load(witness_path).unwrap();
+    let num_rounds = witness
+        .num_batches()
+        .div_ceil(config.mpi_config.world_size());
+    let mut assignments = (0..num_rounds)
+        .map(|round| {
+            let batch = Witness::<C>::mpi_batch(round, &config.mpi_config) % witness.num_batches();
+            let mut assignment = CircuitAssignment::new(&circuit);
+            assignment.load_witness_batch(&witness, batch).unwrap();
+            assignment
+        })
+        .collect::<Vec<_>>();
 
     let circuit_copy_size: usize = match (C::FIELD_TYPE, args.scheme.as_str()) {
         (FieldType::GF2, "keccak") => 1,
@@ -116,14 +134,17 @@ fn run_benchmark<C: GKRConfig>(args: &Args, config: Config<C>) {
         let start_time = std::time::Instant::now();
         for _j in 0..N_PROOF {
             let mut prover = Prover::new(&config);
-            prover.prepare_mem(&circuit);
-            prover.prove(&mut circuit);
+            prover.prepare_mem(&circuit).unwrap();
+            for assignment in assignments.iter_mut() {
+                prover.prove(&circuit, assignment);
+            }
         }
         let stop_time = std::time::Instant::now();
         let duration = stop_time.duration_since(start_time);
-        let throughput = (N_PROOF * circuit_copy_size * pack_size * config.mpi_config.world_size())
-            as f64
-            / duration.as_secs_f64();
+        let throughput =
+            (N_PROOF * num_rounds * circuit_copy_size * pack_size * config.mpi_config.world_size())
+                as f64
+                / duration.as_secs_f64();
         println!("{}-bench: throughput: {} hashes/s", i, throughput.round());
     }
 }
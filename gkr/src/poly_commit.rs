@@ -1,7 +1,160 @@
+use std::io::{Read, Write};
+
+use arith::{FieldSerdeError, FieldSerdeResult};
+use bi_kzg::BiKZGSRS;
+use config::{Config, GKRConfig, MPIConfig, PolynomialCommitmentType};
+use halo2curves::bn256::Bn256;
+use thiserror::Error;
+use transcript::{Proof, ProofReadResult, ProofReader, Transcript, TranscriptInstance};
+
 pub mod raw;
 pub use self::raw::*;
 
+pub mod kzg;
+pub use self::kzg::*;
+
+pub mod fri;
+pub use self::fri::*;
+
+pub mod merkle;
+pub use self::merkle::*;
+
+pub mod orion;
+pub use self::orion::*;
+
 pub mod poly;
 pub use self::poly::*;
 
-pub trait PolynomialCommitment {}
+#[derive(Debug, Error)]
+pub enum PCSSetupError {
+    #[error("the KZG commitment needs the SRS of a trusted setup, see `Config::kzg_srs_file`")]
+    MissingSrsFile,
+
+    #[error("can not read the SRS: {0:?}")]
+    BadSrs(FieldSerdeError),
+
+    #[error("the SRS is for {found} variables, the polynomial has {expected}")]
+    SrsSizeMismatch { expected: usize, found: usize },
+}
+
+pub type PCSSetupResult<T> = Result<T, PCSSetupError>;
+
+/// Polynomial commitment to the input layer of the circuit.
+///
+/// The committed multilinear polynomial is over the variables (x, x_simd, x_mpi), i.e.,
+/// the local input values of all the ranks concatenated, with the SIMD lanes unpacked.
+/// `num_vars` always counts all three groups of variables.
+pub trait PolynomialCommitment<C: GKRConfig>: Sized {
+    /// Public parameters shared by the prover and the verifier, for polynomials with a given
+    /// number of variables.
+    type Params;
+
+    /// Prover-side data produced by the commit and consumed by the opening.
+    type ProverState;
+
+    /// Build the public parameters for polynomials with `num_vars` variables.
+    fn setup(config: &Config<C>, num_vars: usize) -> PCSSetupResult<Self::Params>;
+
+    /// Create a commitment collectively.
+    /// Only the commitment on the root rank is meaningful.
+    /// Should also work if mpi is not initialized.
+    fn mpi_commit(
+        params: &Self::Params,
+        local_poly_vals: &[C::SimdCircuitField],
+        mpi_config: &MPIConfig,
+    ) -> (Self, Self::ProverState);
+
+    /// Size of the serialized commitment in bytes.
+    fn size(&self) -> usize;
+
+    fn serialize_into<W: Write>(&self, writer: W) -> FieldSerdeResult<()>;
+
+    fn deserialize_from<R: Read>(reader: R, num_vars: usize) -> FieldSerdeResult<Self>;
+
+    /// Open the committed polynomial at (x, x_simd, x_mpi), sending the opening to the
+    /// verifier.
+    /// Note: this runs on all the ranks, only the transcript and proof of the root rank are
+    /// meaningful
+    #[allow(clippy::too_many_arguments)]
+    fn open(
+        params: &Self::Params,
+        state: &Self::ProverState,
+        x: &[C::ChallengeField],
+        x_simd: &[C::ChallengeField],
+        x_mpi: &[C::ChallengeField],
+        transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+        proof: &mut Proof,
+        mpi_config: &MPIConfig,
+    );
+
+    /// Verify that the committed polynomial evaluates to y at (x, x_simd, x_mpi),
+    /// reading the opening from the proof.
+    /// A failed check returns false, an opening that can not be read returns an error.
+    /// Note: the mpi size is implicitly specified by the length of x_mpi
+    #[allow(clippy::too_many_arguments)]
+    fn verify(
+        &self,
+        params: &Self::Params,
+        x: &[C::ChallengeField],
+        x_simd: &[C::ChallengeField],
+        x_mpi: &[C::ChallengeField],
+        y: C::ChallengeField,
+        transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+        proof: &mut ProofReader,
+    ) -> ProofReadResult<bool>;
+}
+
+/// The public parameters of the configured commitment, see `PolynomialCommitment::setup`.
+/// The prover and the verifier dispatch on them.
+pub enum PCSParams<C: GKRConfig> {
+    Raw,
+    KZG(BiKZGSRS<Bn256>),
+    Orion(OrionParams),
+    FRI(FRIParams<C::CircuitField>),
+}
+
+impl<C: GKRConfig> PCSParams<C> {
+    pub fn setup(config: &Config<C>, num_vars: usize) -> PCSSetupResult<Self> {
+        Ok(match config.polynomial_commitment_type {
+            PolynomialCommitmentType::Raw => {
+                RawCommitment::<C>::setup(config, num_vars)?;
+                PCSParams::Raw
+            }
+            PolynomialCommitmentType::KZG => PCSParams::KZG(
+                <KZGCommitment as PolynomialCommitment<C>>::setup(config, num_vars)?,
+            ),
+            PolynomialCommitmentType::Orion => {
+                PCSParams::Orion(OrionCommitment::<C>::setup(config, num_vars)?)
+            }
+            PolynomialCommitmentType::FRI => {
+                PCSParams::FRI(FRICommitment::<C>::setup(config, num_vars)?)
+            }
+        })
+    }
+}
+
+/// Bits of security of the queries of the opening with the configured commitment,
+/// infinite for the commitments that are not checked by queries.
+#[cfg(feature = "grinding")]
+pub(crate) fn pcs_query_bits<C: GKRConfig>(config: &Config<C>) -> f64 {
+    match config.polynomial_commitment_type {
+        PolynomialCommitmentType::Raw | PolynomialCommitmentType::KZG => f64::INFINITY,
+        PolynomialCommitmentType::Orion => OrionParams::new(config.security_bits).query_bits(),
+        PolynomialCommitmentType::FRI => fri_query_bits(config.security_bits),
+    }
+}
+
+/// Sample `num_queries` positions in 0..range, a power of 2, from the transcript.
+#[inline]
+pub(crate) fn generate_query_indices<C: GKRConfig>(
+    transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    num_queries: usize,
+    range: usize,
+) -> Vec<usize> {
+    let mut bytes = vec![0u8; num_queries * 8];
+    transcript.challenge_bytes("query indices", &mut bytes);
+    bytes
+        .chunks_exact(8)
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()) as usize % range)
+        .collect()
+}
@@ -0,0 +1,450 @@
+//! FRI-based multilinear commitment in the style of Basefold, for the M31 configs.
+//!
+//! M31 has no large multiplicative subgroup of order 2^k, so instead of Reed-Solomon over an FFT
+//! domain, the evaluations are encoded with a random foldable code. Enc_0 repeats an element
+//! 2^FRI_RATE_BITS times, and the encoding of m = m_l || m_r at level i + 1 is
+//! (Enc_i(m_l) + t_i * Enc_i(m_r)) || (Enc_i(m_l) - t_i * Enc_i(m_r)),
+//! for a public random vector t_i over the circuit field. Folding a level i + 1 codeword with r
+//! gives the encoding of (1 - r) m_l + r m_r, i.e., binds the last variable of the polynomial to r.
+//!
+//! To open at z, the prover runs the sumcheck for y = sum_b f(b) eq(b, z), binding the variables
+//! from the last one, and folds and commits the codeword with the same challenges. The verifier
+//! checks the sumcheck against the final constant, and the foldings at random positions.
+
+use std::{
+    io::{Read, Write},
+    marker::PhantomData,
+};
+
+use arith::{Field, FieldSerde, FieldSerdeResult, SimdField};
+use config::{Config, GKRConfig, MPIConfig};
+use rand::{rngs::StdRng, SeedableRng};
+use transcript::{
+    FiatShamirHash, Proof, ProofReadResult, ProofReader, Transcript, TranscriptInstance,
+};
+
+use crate::{
+    generate_query_indices, MerkleTree, MultiLinearPoly, PCSSetupResult, PolynomialCommitment,
+};
+
+/// Seed of the public randomness defining the code.
+const FRI_CODE_SEED: u64 = 0x4261_7365_466f_6c64;
+
+/// log2 of the inverse of the code rate.
+const FRI_RATE_BITS: usize = 3;
+
+/// Within the unique decoding radius (1 - rate) / 2, a query catches a codeword far from the
+/// code with probability at least (1 - rate) / 2.
+#[inline]
+fn fri_bits_per_query() -> f64 {
+    let rate = (-(FRI_RATE_BITS as f64)).exp2();
+    -((1.0 + rate) / 2.0).log2()
+}
+
+/// Number of queries for a soundness error of the queries of at most 2^-security_bits.
+#[inline]
+fn fri_num_queries(security_bits: usize) -> usize {
+    (security_bits as f64 / fri_bits_per_query()).ceil() as usize
+}
+
+/// Bits of security of the queries of `FRIParams::new(_, security_bits)`.
+#[cfg(feature = "grinding")]
+pub(crate) fn fri_query_bits(security_bits: usize) -> f64 {
+    fri_num_queries(security_bits) as f64 * fri_bits_per_query()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FRIParams<F: Field> {
+    /// Number of positions at which the foldings are checked.
+    pub num_queries: usize,
+    /// t_i for the variables i = 0..num_vars, of length 2^{FRI_RATE_BITS + i}.
+    diagonals: Vec<Vec<F>>,
+    /// The inverses of the t_i, used by the folding.
+    diagonal_invs: Vec<Vec<F>>,
+}
+
+impl<F: Field> FRIParams<F> {
+    /// The parameters for polynomials with `num_vars` variables, with a soundness error of the
+    /// queries of at most 2^-security_bits.
+    pub fn new(num_vars: usize, security_bits: usize) -> Self {
+        let mut rng = StdRng::seed_from_u64(FRI_CODE_SEED);
+        let diagonals = (0..num_vars)
+            .map(|i| {
+                (0..(1 << (FRI_RATE_BITS + i)))
+                    .map(|_| {
+                        std::iter::repeat_with(|| F::random_unsafe(&mut rng))
+                            .find(|t| !t.is_zero())
+                            .unwrap()
+                    })
+                    .collect::<Vec<F>>()
+            })
+            .collect::<Vec<_>>();
+        let diagonal_invs = diagonals
+            .iter()
+            .map(|t| t.iter().map(|t| t.inv().unwrap()).collect())
+            .collect();
+        Self {
+            num_queries: fri_num_queries(security_bits),
+            diagonals,
+            diagonal_invs,
+        }
+    }
+
+    /// Encode the evaluations of a multilinear polynomial.
+    pub fn encode(&self, evals: &[F]) -> Vec<F> {
+        let num_vars = evals.len().trailing_zeros() as usize;
+        let mut codeword = evals
+            .iter()
+            .flat_map(|e| vec![*e; 1 << FRI_RATE_BITS])
+            .collect::<Vec<F>>();
+        for t in self.diagonals.iter().take(num_vars) {
+            for block in codeword.chunks_mut(2 * t.len()) {
+                let (lo, hi) = block.split_at_mut(t.len());
+                for ((lo, hi), t) in lo.iter_mut().zip(hi.iter_mut()).zip(t) {
+                    let t_hi = *t * *hi;
+                    *hi = *lo - t_hi;
+                    *lo += t_hi;
+                }
+            }
+        }
+        codeword
+    }
+}
+
+/// Fold the pair (c0, c1) = (a + t b, a - t b) into a + r (b - a).
+#[inline]
+fn fold<C: GKRConfig>(
+    c0: &C::ChallengeField,
+    c1: &C::ChallengeField,
+    t_inv: &C::CircuitField,
+    r: &C::ChallengeField,
+) -> C::ChallengeField {
+    let a = (*c0 + c1) * C::ChallengeField::INV_2;
+    let b = C::challenge_mul_circuit_field(&((*c0 - c1) * C::ChallengeField::INV_2), t_inv);
+    a + (b - a) * r
+}
+
+/// Bind the last variable of the multilinear polynomial to r.
+#[inline]
+fn bind_last_var<F: Field>(evals: &mut Vec<F>, r: &F) {
+    let half = evals.len() / 2;
+    let (lo, hi) = evals.split_at_mut(half);
+    lo.iter_mut()
+        .zip(hi.iter())
+        .for_each(|(lo, hi)| *lo += (*hi - *lo) * r);
+    evals.truncate(half);
+}
+
+/// The leaf j of a codeword of length 2m holds the pair (j, j + m), which folds into position j.
+#[inline]
+fn leaf_bytes<F: FieldSerde>(codeword: &[F], j: usize) -> Vec<u8> {
+    let mut buffer = vec![];
+    codeword[j].serialize_into(&mut buffer).unwrap();
+    codeword[j + codeword.len() / 2]
+        .serialize_into(&mut buffer)
+        .unwrap();
+    buffer
+}
+
+#[inline]
+fn merkle_commit<F: FieldSerde, H: FiatShamirHash>(codeword: &[F]) -> MerkleTree<H> {
+    let leaves = (0..codeword.len() / 2)
+        .map(|j| MerkleTree::<H>::hash_leaf(&leaf_bytes(codeword, j)))
+        .collect::<Vec<_>>();
+    MerkleTree::new(&leaves)
+}
+
+/// Read a leaf from the proof and check it against the root,
+/// returning the pair of values it holds, or None if the path does not match.
+#[inline]
+fn read_leaf<C: GKRConfig, F: FieldSerde>(
+    root: &[u8],
+    index: usize,
+    num_leaves: usize,
+    transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    proof: &mut ProofReader,
+) -> ProofReadResult<Option<(F, F)>> {
+    let bytes = proof.peek_bytes(2 * F::SERIALIZED_SIZE)?;
+    let c0 = transcript.receive_field_element::<F>(proof)?;
+    let c1 = transcript.receive_field_element::<F>(proof)?;
+    let path = transcript.receive_from_prover(
+        MerkleTree::<C::FiatShamirHashType>::path_size(num_leaves),
+        proof,
+    )?;
+    let leaf_digest = MerkleTree::<C::FiatShamirHashType>::hash_leaf(bytes);
+    if !MerkleTree::<C::FiatShamirHashType>::verify_path(root, &leaf_digest, index, path) {
+        return Ok(None);
+    }
+    Ok(Some((c0, c1)))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FRICommitment<C: GKRConfig> {
+    /// The Merkle root of the codeword.
+    pub root: Vec<u8>,
+    phantom: PhantomData<C>,
+}
+
+#[derive(Default)]
+pub struct FRIProverState<C: GKRConfig> {
+    evals: Vec<C::CircuitField>,
+    codeword: Vec<C::CircuitField>,
+    tree: MerkleTree<C::FiatShamirHashType>,
+}
+
+impl<C: GKRConfig> PolynomialCommitment<C> for FRICommitment<C> {
+    type Params = FRIParams<C::CircuitField>;
+    type ProverState = FRIProverState<C>;
+
+    #[inline]
+    fn setup(config: &Config<C>, num_vars: usize) -> PCSSetupResult<Self::Params> {
+        Ok(FRIParams::new(num_vars, config.security_bits))
+    }
+
+    /// The evaluations are gathered on the root rank, which is the only rank that commits.
+    fn mpi_commit(
+        params: &Self::Params,
+        local_poly_vals: &[C::SimdCircuitField],
+        mpi_config: &MPIConfig,
+    ) -> (Self, Self::ProverState) {
+        let mut poly_vals = if mpi_config.is_root() {
+            vec![C::SimdCircuitField::zero(); local_poly_vals.len() * mpi_config.world_size()]
+        } else {
+            vec![]
+        };
+        mpi_config.gather_vec(&local_poly_vals.to_vec(), &mut poly_vals);
+        if !mpi_config.is_root() {
+            return (Self::default(), FRIProverState::default());
+        }
+
+        let local_size = local_poly_vals.len();
+        let pack_size = C::get_field_pack_size();
+        let mut evals = vec![C::CircuitField::zero(); poly_vals.len() * pack_size];
+        for (rank, rank_vals) in poly_vals.chunks(local_size).enumerate() {
+            for (i, v) in rank_vals.iter().enumerate() {
+                for (lane, v) in v.unpack().into_iter().enumerate() {
+                    evals[(rank * pack_size + lane) * local_size + i] = v;
+                }
+            }
+        }
+
+        let codeword = params.encode(&evals);
+        let tree = merkle_commit::<_, C::FiatShamirHashType>(&codeword);
+        let commitment = Self {
+            root: tree.root().to_vec(),
+            phantom: PhantomData,
+        };
+        (
+            commitment,
+            FRIProverState {
+                evals,
+                codeword,
+                tree,
+            },
+        )
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        C::FiatShamirHashType::DIGEST_SIZE
+    }
+
+    #[inline]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        writer.write_all(&self.root)?;
+        Ok(())
+    }
+
+    #[inline]
+    fn deserialize_from<R: Read>(mut reader: R, _num_vars: usize) -> FieldSerdeResult<Self> {
+        let mut root = vec![0u8; C::FiatShamirHashType::DIGEST_SIZE];
+        reader.read_exact(&mut root)?;
+        Ok(Self {
+            root,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Only the root rank, which holds the evaluations, opens.
+    fn open(
+        params: &Self::Params,
+        state: &Self::ProverState,
+        x: &[C::ChallengeField],
+        x_simd: &[C::ChallengeField],
+        x_mpi: &[C::ChallengeField],
+        transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+        proof: &mut Proof,
+        mpi_config: &MPIConfig,
+    ) {
+        if !mpi_config.is_root() {
+            return;
+        }
+        let point = x
+            .iter()
+            .chain(x_simd)
+            .chain(x_mpi)
+            .cloned()
+            .collect::<Vec<C::ChallengeField>>();
+        let num_vars = point.len();
+        assert_eq!(state.evals.len(), 1 << num_vars);
+
+        let mut f = state
+            .evals
+            .iter()
+            .map(|e| C::ChallengeField::from(*e))
+            .collect::<Vec<_>>();
+        let mut g = MultiLinearPoly::eq_evals(&point);
+        let mut codeword = state
+            .codeword
+            .iter()
+            .map(|c| C::ChallengeField::from(*c))
+            .collect::<Vec<_>>();
+
+        // the folded codewords of the levels num_vars - 1, ..., 1
+        let mut folded = vec![];
+        for k in 0..num_vars {
+            // the round polynomial at 0, 1 and 2
+            let half = f.len() / 2;
+            let mut h = [C::ChallengeField::zero(); 3];
+            let (f_lo, f_hi) = f.split_at(half);
+            let (g_lo, g_hi) = g.split_at(half);
+            for (((f0, f1), g0), g1) in f_lo.iter().zip(f_hi).zip(g_lo).zip(g_hi) {
+                h[0] += *f0 * g0;
+                h[1] += *f1 * g1;
+                h[2] += (f1.double() - f0) * (g1.double() - g0);
+            }
+            h.iter()
+                .for_each(|v| transcript.send_field_element(v, proof));
+            let r = transcript.generate_challenge::<C::ChallengeField>();
+
+            bind_last_var(&mut f, &r);
+            bind_last_var(&mut g, &r);
+
+            let t_inv = &params.diagonal_invs[num_vars - 1 - k];
+            let m = codeword.len() / 2;
+            codeword = (0..m)
+                .map(|j| fold::<C>(&codeword[j], &codeword[j + m], &t_inv[j], &r))
+                .collect();
+
+            if k + 1 < num_vars {
+                let tree = merkle_commit::<_, C::FiatShamirHashType>(&codeword);
+                transcript.send_to_verifier(tree.root(), proof);
+                folded.push((codeword.clone(), tree));
+            }
+        }
+        transcript.send_field_element(&f[0], proof);
+
+        let num_leaves = state.codeword.len() / 2;
+        for q in generate_query_indices::<C>(transcript, params.num_queries, num_leaves) {
+            transcript.send_to_verifier(&leaf_bytes(&state.codeword, q), proof);
+            transcript.send_to_verifier(&state.tree.path(q), proof);
+            for (codeword, tree) in &folded {
+                let j = q % (codeword.len() / 2);
+                transcript.send_to_verifier(&leaf_bytes(codeword, j), proof);
+                transcript.send_to_verifier(&tree.path(j), proof);
+            }
+        }
+    }
+
+    fn verify(
+        &self,
+        params: &Self::Params,
+        x: &[C::ChallengeField],
+        x_simd: &[C::ChallengeField],
+        x_mpi: &[C::ChallengeField],
+        y: C::ChallengeField,
+        transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+        proof: &mut ProofReader,
+    ) -> ProofReadResult<bool> {
+        let point = x
+            .iter()
+            .chain(x_simd)
+            .chain(x_mpi)
+            .cloned()
+            .collect::<Vec<C::ChallengeField>>();
+        let num_vars = point.len();
+        if num_vars == 0 || params.diagonal_invs.len() < num_vars {
+            return Ok(false);
+        }
+
+        // sumcheck, with the roots of the folded codewords
+        let mut claim = y;
+        let mut challenges = vec![];
+        let mut roots = vec![];
+        for k in 0..num_vars {
+            let mut h = [C::ChallengeField::zero(); 3];
+            for v in h.iter_mut() {
+                *v = transcript.receive_field_element::<C::ChallengeField>(proof)?;
+            }
+            if h[0] + h[1] != claim {
+                return Ok(false);
+            }
+            let r = transcript.generate_challenge::<C::ChallengeField>();
+
+            // Lagrange interpolation over 0, 1, 2
+            let one = C::ChallengeField::one();
+            let two = one.double();
+            claim = (h[0] * (r - one) * (r - two) + h[2] * r * (r - one))
+                * C::ChallengeField::INV_2
+                - h[1] * r * (r - two);
+            challenges.push(r);
+
+            if k + 1 < num_vars {
+                roots.push(
+                    transcript.receive_from_prover(C::FiatShamirHashType::DIGEST_SIZE, proof)?,
+                );
+            }
+        }
+        let final_v = transcript.receive_field_element::<C::ChallengeField>(proof)?;
+
+        // the k-th challenge binds the variable num_vars - 1 - k
+        let eq = point
+            .iter()
+            .rev()
+            .zip(&challenges)
+            .map(|(z, r)| *z * r + (C::ChallengeField::one() - z) * (C::ChallengeField::one() - r))
+            .product::<C::ChallengeField>();
+        if claim != final_v * eq {
+            return Ok(false);
+        }
+
+        let num_leaves = 1 << (FRI_RATE_BITS + num_vars - 1);
+        let mut verified = true;
+        for q in generate_query_indices::<C>(transcript, params.num_queries, num_leaves) {
+            let (c0, c1) = match read_leaf::<C, C::CircuitField>(
+                &self.root, q, num_leaves, transcript, proof,
+            )? {
+                Some(leaf) => leaf,
+                None => return Ok(false),
+            };
+            let mut folded_v = fold::<C>(
+                &C::ChallengeField::from(c0),
+                &C::ChallengeField::from(c1),
+                &params.diagonal_invs[num_vars - 1][q],
+                &challenges[0],
+            );
+
+            for (k, root) in roots.iter().enumerate() {
+                let m = num_leaves >> (k + 1);
+                let j = q % m;
+                let (c0, c1) =
+                    match read_leaf::<C, C::ChallengeField>(root, j, m, transcript, proof)? {
+                        Some(leaf) => leaf,
+                        None => return Ok(false),
+                    };
+                verified &= folded_v == if q % (2 * m) < m { c0 } else { c1 };
+                folded_v = fold::<C>(
+                    &c0,
+                    &c1,
+                    &params.diagonal_invs[num_vars - 2 - k][j],
+                    &challenges[k + 1],
+                );
+            }
+
+            // the last codeword repeats the final constant
+            verified &= folded_v == final_v;
+        }
+
+        Ok(verified)
+    }
+}
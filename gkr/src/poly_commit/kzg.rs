@@ -0,0 +1,358 @@
+//! KZG commitment for the BN254 configs, built on top of the bivariate KZG in `bi-kzg`.
+//!
+//! The multilinear polynomial with evaluations `e` over `n` variables is viewed as the univariate
+//! `P(X) = sum_i e_i X^i`, whose coefficients are laid out row by row into a bivariate
+//! `Q(X, Y)` of degrees `d0 x d1 = 2^n`, so that `P(X) = Q(X, X^d0)`.
+//!
+//! Opening at a point `r` follows Gemini: writing `P_j(X) = E_j(X^2) + X O_j(X^2)`, the prover
+//! commits to the folded polynomials `P_{j+1}(X) = (1 - r_j) E_j(X) + r_j O_j(X)`, and opens every
+//! `P_j` at `beta^{2^j}` and `-beta^{2^j}` for a random `beta`. The verifier checks each fold
+//! against the next opening, and that the last fold equals the claimed evaluation.
+//!
+//! The SRS comes from a trusted setup ceremony, and `setup` loads it from `Config::kzg_srs_file`
+//! with [`KZGCommitment::load_srs`].
+
+use std::{
+    fs,
+    io::{Read, Write},
+};
+
+use arith::{Field, FieldSerde, FieldSerdeError, FieldSerdeResult, SimdField};
+use bi_kzg::{
+    BiKZGCommitment, BiKZGProof, BiKZGSRS, BiKZGVerifierParam, BivariatePolynomial, CoeffFormBiKZG,
+    PolynomialCommitmentScheme,
+};
+use config::{Config, GKRConfig, MPIConfig};
+use halo2curves::{
+    bn256::{Bn256, Fr, G1Affine, G2Affine},
+    group::GroupEncoding,
+};
+use transcript::{Proof, ProofReadResult, ProofReader, Transcript, TranscriptInstance};
+
+use crate::{PCSSetupError, PCSSetupResult, PolynomialCommitment};
+
+/// Seed of the testing SRS, see [`KZGCommitment::setup_for_testing`].
+#[cfg(test)]
+const KZG_TESTING_SRS_SEED: u64 = 0x4578_7061_6e64_6572;
+
+/// Size of a compressed G1 point in the proof.
+const G1_SIZE: usize = 32;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KZGCommitment {
+    pub com: BiKZGCommitment<Bn256>,
+}
+
+/// Shape of the bivariate polynomial for a multilinear polynomial with `num_vars` variables.
+#[inline]
+fn bivariate_shape(num_vars: usize) -> (usize, usize) {
+    (1 << ((num_vars + 1) / 2), 1 << (num_vars / 2))
+}
+
+/// KZG is only defined over BN254, where the circuit and challenge fields are `Fr` itself.
+/// The generic prover and verifier hand their values over by the canonical serialization.
+#[inline]
+fn to_fr<F: FieldSerde>(f: &F) -> Fr {
+    let mut buffer = vec![];
+    f.serialize_into(&mut buffer).unwrap();
+    Fr::deserialize_from(buffer.as_slice()).unwrap()
+}
+
+#[inline]
+fn to_bivariate(coefs: &[Fr], num_vars: usize) -> BivariatePolynomial<Fr> {
+    let (degree_0, degree_1) = bivariate_shape(num_vars);
+    let mut coefficients = coefs.to_vec();
+    coefficients.resize(degree_0 * degree_1, Fr::zero());
+    BivariatePolynomial::new(coefficients, degree_0, degree_1)
+}
+
+/// The bivariate point `(z, z^d0)` at which `Q` agrees with `P(z)`; `d0` is a power of two.
+#[inline]
+fn bivariate_point(z: Fr, degree_0: usize) -> (Fr, Fr) {
+    let mut z_d0 = z;
+    for _ in 0..degree_0.trailing_zeros() {
+        z_d0 = z_d0.square();
+    }
+    (z, z_d0)
+}
+
+#[inline]
+fn read_g1<R: Read>(mut reader: R) -> FieldSerdeResult<G1Affine> {
+    let mut repr = <G1Affine as GroupEncoding>::Repr::default();
+    reader.read_exact(repr.as_mut())?;
+    let g1: Option<G1Affine> = G1Affine::from_bytes(&repr).into();
+    g1.ok_or(FieldSerdeError::DeserializeError)
+}
+
+#[inline]
+fn read_g2<R: Read>(mut reader: R) -> FieldSerdeResult<G2Affine> {
+    let mut repr = <G2Affine as GroupEncoding>::Repr::default();
+    reader.read_exact(repr.as_mut())?;
+    let g2: Option<G2Affine> = G2Affine::from_bytes(&repr).into();
+    g2.ok_or(FieldSerdeError::DeserializeError)
+}
+
+impl KZGCommitment {
+    pub const SIZE: usize = G1_SIZE;
+
+    /// Generate the SRS for multilinear polynomials with `num_vars` variables.
+    ///
+    /// WARNING: the trapdoor is derived from a public seed, so the SRS is for testing only.
+    #[cfg(test)]
+    pub fn setup_for_testing(num_vars: usize) -> BiKZGSRS<Bn256> {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let (degree_0, degree_1) = bivariate_shape(num_vars);
+        let rng = StdRng::seed_from_u64(KZG_TESTING_SRS_SEED);
+        CoeffFormBiKZG::<Bn256>::gen_srs_for_testing(rng, degree_0, degree_1)
+    }
+
+    /// Number of variables of the multilinear polynomials the SRS commits to.
+    pub fn srs_num_vars(srs: &BiKZGSRS<Bn256>) -> usize {
+        srs.powers_of_g.len().trailing_zeros() as usize
+    }
+
+    /// Load an SRS written by [`KZGCommitment::write_srs`], e.g., the output of a ceremony.
+    pub fn load_srs(filename: &str) -> FieldSerdeResult<BiKZGSRS<Bn256>> {
+        let bytes = fs::read(filename)?;
+        Self::read_srs(bytes.as_slice())
+    }
+
+    /// Read an SRS: the number of variables as a u64, the coefficient and the Lagrange bases
+    /// as compressed G1 points, then `h`, `tau_0 h` and `tau_1 h` as compressed G2 points.
+    pub fn read_srs<R: Read>(mut reader: R) -> FieldSerdeResult<BiKZGSRS<Bn256>> {
+        let num_vars = u64::deserialize_from(&mut reader)? as usize;
+        if num_vars >= usize::BITS as usize {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        let size = 1usize << num_vars;
+        let powers_of_g = (0..size)
+            .map(|_| read_g1(&mut reader))
+            .collect::<FieldSerdeResult<Vec<_>>>()?;
+        let powers_of_g_lagrange_over_both_roots = (0..size)
+            .map(|_| read_g1(&mut reader))
+            .collect::<FieldSerdeResult<Vec<_>>>()?;
+        Ok(BiKZGSRS {
+            powers_of_g,
+            powers_of_g_lagrange_over_both_roots,
+            h: read_g2(&mut reader)?,
+            tau_0_h: read_g2(&mut reader)?,
+            tau_1_h: read_g2(&mut reader)?,
+        })
+    }
+
+    pub fn write_srs<W: Write>(srs: &BiKZGSRS<Bn256>, mut writer: W) -> FieldSerdeResult<()> {
+        (Self::srs_num_vars(srs) as u64).serialize_into(&mut writer)?;
+        for g in srs
+            .powers_of_g
+            .iter()
+            .chain(&srs.powers_of_g_lagrange_over_both_roots)
+        {
+            writer.write_all(g.to_bytes().as_ref())?;
+        }
+        for h in [&srs.h, &srs.tau_0_h, &srs.tau_1_h] {
+            writer.write_all(h.to_bytes().as_ref())?;
+        }
+        Ok(())
+    }
+}
+
+impl<C: GKRConfig> PolynomialCommitment<C> for KZGCommitment {
+    /// The SRS for exactly the number of variables of the committed polynomial.
+    type Params = BiKZGSRS<Bn256>;
+
+    /// The flattened evaluations of the committed polynomial, only kept on the root rank.
+    type ProverState = Vec<Fr>;
+
+    fn setup(config: &Config<C>, num_vars: usize) -> PCSSetupResult<Self::Params> {
+        let filename = config
+            .kzg_srs_file
+            .as_ref()
+            .ok_or(PCSSetupError::MissingSrsFile)?;
+        let srs = Self::load_srs(filename).map_err(PCSSetupError::BadSrs)?;
+        if Self::srs_num_vars(&srs) != num_vars {
+            return Err(PCSSetupError::SrsSizeMismatch {
+                expected: num_vars,
+                found: Self::srs_num_vars(&srs),
+            });
+        }
+        Ok(srs)
+    }
+
+    /// Commit to the multilinear polynomial over the variables (x, x_simd, x_mpi),
+    /// i.e., the local values of all the ranks concatenated, with the SIMD lanes unpacked.
+    ///
+    /// The evaluations are gathered on the root rank, which is the only rank that commits.
+    /// Returns the commitment together with the flattened evaluations needed for the opening.
+    fn mpi_commit(
+        srs: &Self::Params,
+        local_poly_vals: &[C::SimdCircuitField],
+        mpi_config: &MPIConfig,
+    ) -> (Self, Self::ProverState) {
+        let mut poly_vals = if mpi_config.is_root() {
+            vec![C::SimdCircuitField::zero(); local_poly_vals.len() * mpi_config.world_size()]
+        } else {
+            vec![]
+        };
+        mpi_config.gather_vec(&local_poly_vals.to_vec(), &mut poly_vals);
+        if !mpi_config.is_root() {
+            return (Self::default(), vec![]);
+        }
+
+        let local_size = local_poly_vals.len();
+        let pack_size = C::get_field_pack_size();
+        let mut evals = vec![Fr::zero(); poly_vals.len() * pack_size];
+        for (rank, rank_vals) in poly_vals.chunks(local_size).enumerate() {
+            for (i, v) in rank_vals.iter().enumerate() {
+                for (lane, v) in v.unpack().iter().enumerate() {
+                    evals[(rank * pack_size + lane) * local_size + i] = to_fr(v);
+                }
+            }
+        }
+
+        let num_vars = evals.len().trailing_zeros() as usize;
+        let com = CoeffFormBiKZG::<Bn256>::commit(srs, &to_bivariate(&evals, num_vars));
+        (Self { com }, evals)
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        Self::SIZE
+    }
+
+    #[inline]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        writer.write_all(self.com.com.to_bytes().as_ref())?;
+        Ok(())
+    }
+
+    #[inline]
+    fn deserialize_from<R: Read>(reader: R, _num_vars: usize) -> FieldSerdeResult<Self> {
+        read_g1(reader).map(|com| Self {
+            com: BiKZGCommitment { com },
+        })
+    }
+
+    /// Open the committed polynomial at (x, x_simd, x_mpi), sending the opening to the
+    /// verifier. Only the root rank, which holds the evaluations, opens.
+    fn open(
+        srs: &Self::Params,
+        evals: &Self::ProverState,
+        x: &[C::ChallengeField],
+        x_simd: &[C::ChallengeField],
+        x_mpi: &[C::ChallengeField],
+        transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+        proof: &mut Proof,
+        mpi_config: &MPIConfig,
+    ) {
+        if !mpi_config.is_root() {
+            return;
+        }
+        let point = x
+            .iter()
+            .chain(x_simd)
+            .chain(x_mpi)
+            .map(to_fr)
+            .collect::<Vec<Fr>>();
+        let num_vars = point.len();
+        assert_eq!(evals.len(), 1 << num_vars);
+
+        // P_0 is the committed polynomial, P_{j+1} folds P_j with r_j
+        let mut folded_polys = vec![evals.to_vec()];
+        for r in point.iter().take(num_vars - 1) {
+            let folded = folded_polys
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|c| c[0] + (c[1] - c[0]) * r)
+                .collect::<Vec<Fr>>();
+            folded_polys.push(folded);
+        }
+        let folded_polys = folded_polys
+            .iter()
+            .map(|p| to_bivariate(p, num_vars))
+            .collect::<Vec<_>>();
+
+        for poly in folded_polys.iter().skip(1) {
+            let com = CoeffFormBiKZG::<Bn256>::commit(srs, poly);
+            transcript.send_to_verifier(com.com.to_bytes().as_ref(), proof);
+        }
+
+        let (degree_0, _) = bivariate_shape(num_vars);
+        let mut gamma = transcript.generate_challenge::<Fr>();
+        for poly in &folded_polys {
+            for z in [gamma, -gamma] {
+                let (opening, v) =
+                    CoeffFormBiKZG::<Bn256>::open(srs, poly, &bivariate_point(z, degree_0));
+                transcript.send_field_element(&v, proof);
+                transcript.send_to_verifier(opening.pi0.to_bytes().as_ref(), proof);
+                transcript.send_to_verifier(opening.pi1.to_bytes().as_ref(), proof);
+            }
+            gamma = gamma.square();
+        }
+    }
+
+    /// Verify that the committed polynomial evaluates to y at (x, x_simd, x_mpi),
+    /// reading the opening from the proof.
+    fn verify(
+        &self,
+        srs: &Self::Params,
+        x: &[C::ChallengeField],
+        x_simd: &[C::ChallengeField],
+        x_mpi: &[C::ChallengeField],
+        y: C::ChallengeField,
+        transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+        proof: &mut ProofReader,
+    ) -> ProofReadResult<bool> {
+        let point = x
+            .iter()
+            .chain(x_simd)
+            .chain(x_mpi)
+            .map(to_fr)
+            .collect::<Vec<Fr>>();
+        let num_vars = point.len();
+        let vk = BiKZGVerifierParam::from(srs);
+
+        let mut coms = vec![self.com.clone()];
+        for _ in 0..num_vars - 1 {
+            let com = transcript.receive_with(read_g1, proof)?;
+            coms.push(BiKZGCommitment { com });
+        }
+
+        let (degree_0, _) = bivariate_shape(num_vars);
+        let mut gamma = transcript.generate_challenge::<Fr>();
+        // the value of P_j at gamma_j implied by the fold of P_{j-1}
+        let mut folded_v = None;
+        let mut verified = true;
+        for (com, r) in coms.iter().zip(point.iter()) {
+            let mut vs = [Fr::zero(); 2];
+            for (v, z) in vs.iter_mut().zip([gamma, -gamma]) {
+                *v = transcript.receive_field_element::<Fr>(proof)?;
+                let pi0 = transcript.receive_with(read_g1, proof)?;
+                let pi1 = transcript.receive_with(read_g1, proof)?;
+
+                verified &= CoeffFormBiKZG::<Bn256>::verify(
+                    &vk,
+                    com,
+                    &bivariate_point(z, degree_0),
+                    v,
+                    &BiKZGProof { pi0, pi1 },
+                );
+            }
+
+            if let Some(folded_v) = folded_v {
+                verified &= vs[0] == folded_v;
+            }
+            let gamma_double_inv = match gamma.double().inv() {
+                Some(inv) => inv,
+                None => return Ok(false),
+            };
+            let even = (vs[0] + vs[1]) * Fr::INV_2;
+            let odd = (vs[0] - vs[1]) * gamma_double_inv;
+            folded_v = Some(even + (odd - even) * r);
+            gamma = gamma.square();
+        }
+
+        Ok(verified && folded_v == Some(to_fr(&y)))
+    }
+}
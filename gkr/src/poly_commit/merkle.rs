@@ -0,0 +1,105 @@
+//! Merkle tree over the configured Fiat-Shamir hash, used by the hash-based commitments.
+
+use std::marker::PhantomData;
+
+use transcript::FiatShamirHash;
+
+#[derive(Debug, Clone)]
+pub struct MerkleTree<H: FiatShamirHash> {
+    /// Digests in the heap layout, i.e., the root is node 1,
+    /// and the leaves are nodes num_leaves..2 * num_leaves.
+    nodes: Vec<u8>,
+    num_leaves: usize,
+    phantom: PhantomData<H>,
+}
+
+impl<H: FiatShamirHash> Default for MerkleTree<H> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            num_leaves: 0,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<H: FiatShamirHash> MerkleTree<H> {
+    #[inline]
+    pub fn hash_leaf(leaf: &[u8]) -> Vec<u8> {
+        let mut digest = vec![0u8; H::DIGEST_SIZE];
+        H::hash(&mut digest, leaf);
+        digest
+    }
+
+    #[inline]
+    fn hash_children(left: &[u8], right: &[u8]) -> Vec<u8> {
+        let mut digest = vec![0u8; H::DIGEST_SIZE];
+        H::hash(&mut digest, &[left, right].concat());
+        digest
+    }
+
+    /// Build the tree from the leaf digests. The number of leaves must be a power of 2.
+    pub fn new(leaf_digests: &[Vec<u8>]) -> Self {
+        let num_leaves = leaf_digests.len();
+        assert!(num_leaves.is_power_of_two());
+
+        let mut nodes = vec![0u8; 2 * num_leaves * H::DIGEST_SIZE];
+        for (i, digest) in leaf_digests.iter().enumerate() {
+            let offset = (num_leaves + i) * H::DIGEST_SIZE;
+            nodes[offset..offset + H::DIGEST_SIZE].copy_from_slice(digest);
+        }
+        for i in (1..num_leaves).rev() {
+            let children = &nodes[2 * i * H::DIGEST_SIZE..(2 * i + 2) * H::DIGEST_SIZE];
+            let digest =
+                Self::hash_children(&children[..H::DIGEST_SIZE], &children[H::DIGEST_SIZE..]);
+            nodes[i * H::DIGEST_SIZE..(i + 1) * H::DIGEST_SIZE].copy_from_slice(&digest);
+        }
+
+        Self {
+            nodes,
+            num_leaves,
+            phantom: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn node(&self, i: usize) -> &[u8] {
+        &self.nodes[i * H::DIGEST_SIZE..(i + 1) * H::DIGEST_SIZE]
+    }
+
+    #[inline]
+    pub fn root(&self) -> &[u8] {
+        self.node(1)
+    }
+
+    /// Size of an authentication path in bytes.
+    #[inline]
+    pub fn path_size(num_leaves: usize) -> usize {
+        num_leaves.trailing_zeros() as usize * H::DIGEST_SIZE
+    }
+
+    /// The siblings from the leaf up to the root, concatenated.
+    pub fn path(&self, index: usize) -> Vec<u8> {
+        let mut path = vec![];
+        let mut node = self.num_leaves + index;
+        while node > 1 {
+            path.extend_from_slice(self.node(node ^ 1));
+            node >>= 1;
+        }
+        path
+    }
+
+    pub fn verify_path(root: &[u8], leaf_digest: &[u8], index: usize, path: &[u8]) -> bool {
+        let mut digest = leaf_digest.to_vec();
+        let mut index = index;
+        for sibling in path.chunks(H::DIGEST_SIZE) {
+            digest = if index & 1 == 0 {
+                Self::hash_children(&digest, sibling)
+            } else {
+                Self::hash_children(sibling, &digest)
+            };
+            index >>= 1;
+        }
+        index == 0 && digest == root
+    }
+}
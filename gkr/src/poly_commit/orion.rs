@@ -0,0 +1,458 @@
+//! Orion-style commitment: a transparent, field-agnostic scheme built on a linear code
+//! and Merkle trees over the configured Fiat-Shamir hash.
+//!
+//! The local values of each rank, 2^n elements of `SimdCircuitField`, are arranged into a
+//! 2^{n_row} x 2^{n_col} matrix, with the column index on the first n_col variables. Every row is
+//! encoded with a Spielman-style recursive expander code, and the columns of the encoded matrix
+//! are hashed into a Merkle tree. Each rank commits to its own values, and the commitment is the
+//! list of roots gathered on the root rank.
+//!
+//! To open at (x, x_simd, x_mpi), every rank computes two linear combinations of its rows: one
+//! with random coefficients for the proximity test, and one weighted by eq(x_row, .) for the
+//! evaluation. The verifier encodes both and checks them against random columns opened from the
+//! Merkle trees, evaluates the second one at (x_col, x_simd), and finally combines the local
+//! evaluations of all the ranks over x_mpi. Only the combinations and the opened columns are
+//! gathered on the root rank, which writes them to the proof.
+
+use std::{
+    io::{Read, Write},
+    marker::PhantomData,
+};
+
+use arith::{Field, FieldSerde, FieldSerdeError, FieldSerdeResult, SimdField};
+use config::{Config, GKRConfig, MPIConfig};
+use rand::{rngs::StdRng, seq::index, SeedableRng};
+use transcript::{
+    FiatShamirHash, Proof, ProofReadResult, ProofReader, Transcript, TranscriptInstance,
+};
+
+use crate::{
+    generate_query_indices, MerkleTree, MultiLinearPoly, PCSSetupResult, PolynomialCommitment,
+};
+
+/// Seed of the public randomness defining the code.
+const ORION_CODE_SEED: u64 = 0x4f72_696f_6e43_6f64;
+
+/// Messages up to this length are encoded with a dense random code.
+const ORION_CODE_BASE_LEN: usize = 16;
+
+/// Number of edges out of each left vertex of the expander graphs.
+const ORION_GRAPH_DEGREE: usize = 8;
+
+/// Relative distance assumed for the code, a conservative bound rather than a proven one.
+///
+/// The recursive expander codes of Brakedown (Golovnev et al., 2021) and Orion (Xie et al., 2022)
+/// have a distance proven with high probability only for the graph degrees derived from their
+/// lossless-expander analysis. This code has the same layout with its own degree-8 graphs, so
+/// that analysis does not apply. The encodings of sparse messages, the lightest codewords of an
+/// expander code, weigh more than a third of the code length for messages up to 2^12 elements,
+/// and `test_orion_code_distance` checks that they stay above this bound. The price of the
+/// margin is the number of queries, about 2900 for 100 bits.
+pub(crate) const ORION_RELATIVE_DISTANCE: f64 = 0.07;
+
+#[derive(Debug, Clone, Default)]
+pub struct OrionParams {
+    /// Number of columns opened for each opening.
+    pub num_queries: usize,
+}
+
+/// A query catches a combination that is far from the code with probability at least
+/// distance / 3, so that `num_queries` queries leave a soundness error of
+/// (1 - distance / 3)^num_queries.
+#[inline]
+fn orion_bits_per_query() -> f64 {
+    -(1.0 - ORION_RELATIVE_DISTANCE / 3.0).log2()
+}
+
+impl OrionParams {
+    /// The parameters with a soundness error of the queries of at most 2^-security_bits.
+    pub fn new(security_bits: usize) -> Self {
+        Self {
+            num_queries: (security_bits as f64 / orion_bits_per_query()).ceil() as usize,
+        }
+    }
+
+    /// Bits of security of the queries.
+    pub fn query_bits(&self) -> f64 {
+        self.num_queries as f64 * orion_bits_per_query()
+    }
+}
+
+/// A bipartite graph with weighted edges, mapping a vector on the left vertices
+/// to a vector on the right vertices.
+#[derive(Debug, Clone)]
+pub struct OrionExpanderGraph<F: Field> {
+    /// For each right vertex, its neighbors on the left and the edge weights.
+    edges: Vec<Vec<(usize, F)>>,
+}
+
+#[inline]
+fn random_nonzero<F: Field>(rng: &mut StdRng) -> F {
+    let r = F::random_unsafe(&mut *rng);
+    if r.is_zero() {
+        F::one()
+    } else {
+        r
+    }
+}
+
+impl<F: Field> OrionExpanderGraph<F> {
+    /// Each left vertex has `ORION_GRAPH_DEGREE` distinct neighbors, sampled without replacement.
+    fn random(l_size: usize, r_size: usize, rng: &mut StdRng) -> Self {
+        let mut edges = vec![vec![]; r_size];
+        for l in 0..l_size {
+            for r in index::sample(rng, r_size, ORION_GRAPH_DEGREE.min(r_size)) {
+                edges[r].push((l, random_nonzero(rng)));
+            }
+        }
+        Self { edges }
+    }
+
+    fn dense(l_size: usize, r_size: usize, rng: &mut StdRng) -> Self {
+        let edges = (0..r_size)
+            .map(|_| (0..l_size).map(|l| (l, random_nonzero(rng))).collect())
+            .collect();
+        Self { edges }
+    }
+
+    #[inline]
+    fn apply<T: Field>(&self, input: &[T], mul: impl Fn(&T, &F) -> T + Copy) -> Vec<T> {
+        self.edges
+            .iter()
+            .map(|neighbors| neighbors.iter().map(|(l, w)| mul(&input[*l], w)).sum())
+            .collect()
+    }
+}
+
+/// Systematic linear code of rate 1/2 over `F`.
+///
+/// A message x of length n is encoded as x || z || v, where z is the encoding of G0 x
+/// for a graph G0 with n/4 right vertices, and v = G1 z for a graph G1 of size n/2 x n/2.
+#[derive(Debug, Clone)]
+pub enum OrionCode<F: Field> {
+    Base(OrionExpanderGraph<F>),
+    Recursive {
+        g0: OrionExpanderGraph<F>,
+        inner: Box<OrionCode<F>>,
+        g1: OrionExpanderGraph<F>,
+    },
+}
+
+impl<F: Field> OrionCode<F> {
+    /// The code for messages of length `msg_len`, a power of 2.
+    /// The code is derived from a public seed, so that no setup is needed.
+    pub fn new(msg_len: usize) -> Self {
+        assert!(msg_len.is_power_of_two());
+        let mut rng = StdRng::seed_from_u64(ORION_CODE_SEED);
+        Self::generate(msg_len, &mut rng)
+    }
+
+    fn generate(msg_len: usize, rng: &mut StdRng) -> Self {
+        if msg_len <= ORION_CODE_BASE_LEN {
+            Self::Base(OrionExpanderGraph::dense(msg_len, msg_len, rng))
+        } else {
+            let g0 = OrionExpanderGraph::random(msg_len, msg_len / 4, rng);
+            let inner = Box::new(Self::generate(msg_len / 4, rng));
+            let g1 = OrionExpanderGraph::random(msg_len / 2, msg_len / 2, rng);
+            Self::Recursive { g0, inner, g1 }
+        }
+    }
+
+    /// Encode a message over any field `T` on which `F` acts linearly through `mul`.
+    pub fn encode<T: Field>(&self, msg: &[T], mul: impl Fn(&T, &F) -> T + Copy) -> Vec<T> {
+        let mut codeword = msg.to_vec();
+        match self {
+            Self::Base(parity) => codeword.extend(parity.apply(msg, mul)),
+            Self::Recursive { g0, inner, g1 } => {
+                let z = inner.encode(&g0.apply(msg, mul), mul);
+                let v = g1.apply(&z, mul);
+                codeword.extend(z);
+                codeword.extend(v);
+            }
+        }
+        codeword
+    }
+}
+
+/// (n_row, n_col) of the matrix for a local polynomial with `num_local_vars` variables.
+#[inline]
+fn matrix_shape(num_local_vars: usize) -> (usize, usize) {
+    (num_local_vars / 2, num_local_vars - num_local_vars / 2)
+}
+
+/// sum_i coefs_i * vals_i, where vals_i is the i-th row or the i-th element of a column.
+#[inline]
+fn combine<'a, C: GKRConfig>(
+    vals: impl Iterator<Item = &'a C::SimdCircuitField>,
+    coefs: &[C::ChallengeField],
+) -> C::Field {
+    vals.zip(coefs)
+        .map(|(v, c)| C::simd_circuit_field_mul_challenge_field(v, c))
+        .sum()
+}
+
+#[inline]
+fn serialize_column<C: GKRConfig>(
+    encoded_rows: &[C::SimdCircuitField],
+    code_len: usize,
+    index: usize,
+) -> Vec<u8> {
+    let mut buffer = vec![];
+    encoded_rows
+        .iter()
+        .skip(index)
+        .step_by(code_len)
+        .for_each(|v| v.serialize_into(&mut buffer).unwrap());
+    buffer
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OrionCommitment<C: GKRConfig> {
+    /// The Merkle roots of the local commitments, one per rank.
+    pub roots: Vec<Vec<u8>>,
+    phantom: PhantomData<C>,
+}
+
+#[derive(Default)]
+pub struct OrionProverState<C: GKRConfig> {
+    /// The local encoded rows concatenated.
+    encoded_rows: Vec<C::SimdCircuitField>,
+    /// The Merkle tree over the columns of the local encoded rows.
+    tree: MerkleTree<C::FiatShamirHashType>,
+}
+
+impl<C: GKRConfig> PolynomialCommitment<C> for OrionCommitment<C> {
+    type Params = OrionParams;
+    type ProverState = OrionProverState<C>;
+
+    #[inline]
+    fn setup(config: &Config<C>, _num_vars: usize) -> PCSSetupResult<Self::Params> {
+        Ok(OrionParams::new(config.security_bits))
+    }
+
+    /// Every rank commits to its local values, only the roots are gathered on the root rank.
+    fn mpi_commit(
+        _params: &Self::Params,
+        local_poly_vals: &[C::SimdCircuitField],
+        mpi_config: &MPIConfig,
+    ) -> (Self, Self::ProverState) {
+        let (_, n_col) = matrix_shape(local_poly_vals.len().trailing_zeros() as usize);
+        let code = OrionCode::<C::CircuitField>::new(1 << n_col);
+        let code_len = 2 << n_col;
+
+        let encoded_rows = local_poly_vals
+            .chunks(1 << n_col)
+            .flat_map(|row| code.encode(row, |v, c| C::circuit_field_mul_simd_circuit_field(c, v)))
+            .collect::<Vec<C::SimdCircuitField>>();
+        let leaves = (0..code_len)
+            .map(|j| {
+                MerkleTree::<C::FiatShamirHashType>::hash_leaf(&serialize_column::<C>(
+                    &encoded_rows,
+                    code_len,
+                    j,
+                ))
+            })
+            .collect::<Vec<_>>();
+        let tree = MerkleTree::new(&leaves);
+
+        let mut roots = vec![];
+        mpi_config.gather_bytes(tree.root(), &mut roots);
+        let commitment = Self {
+            roots: roots
+                .chunks(C::FiatShamirHashType::DIGEST_SIZE)
+                .map(|root| root.to_vec())
+                .collect(),
+            phantom: PhantomData,
+        };
+        (commitment, OrionProverState { encoded_rows, tree })
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        u64::SERIALIZED_SIZE + self.roots.len() * C::FiatShamirHashType::DIGEST_SIZE
+    }
+
+    #[inline]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        (self.roots.len() as u64).serialize_into(&mut writer)?;
+        self.roots
+            .iter()
+            .try_for_each(|root| writer.write_all(root))?;
+        Ok(())
+    }
+
+    #[inline]
+    fn deserialize_from<R: Read>(mut reader: R, num_vars: usize) -> FieldSerdeResult<Self> {
+        let num_roots = u64::deserialize_from(&mut reader)? as usize;
+        if num_roots == 0 || num_roots > 1 << num_vars {
+            return Err(FieldSerdeError::DeserializeError);
+        }
+        let roots = (0..num_roots)
+            .map(|_| {
+                let mut root = vec![0u8; C::FiatShamirHashType::DIGEST_SIZE];
+                reader.read_exact(&mut root)?;
+                Ok(root)
+            })
+            .collect::<FieldSerdeResult<_>>()?;
+        Ok(Self {
+            roots,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Every rank combines and opens its own rows with the challenges of the root rank,
+    /// which sends the messages of all the ranks.
+    fn open(
+        params: &Self::Params,
+        state: &Self::ProverState,
+        x: &[C::ChallengeField],
+        _x_simd: &[C::ChallengeField],
+        _x_mpi: &[C::ChallengeField],
+        transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+        proof: &mut Proof,
+        mpi_config: &MPIConfig,
+    ) {
+        let (n_row, n_col) = matrix_shape(x.len());
+        let code_len = 2 << n_col;
+        let root_buffer = |len: usize| {
+            if mpi_config.is_root() {
+                len * mpi_config.world_size()
+            } else {
+                0
+            }
+        };
+
+        mpi_config.transcript_sync_up(transcript);
+        let proximity_coefs = transcript.generate_challenge_vector::<C::ChallengeField>(1 << n_row);
+        let eq_row = MultiLinearPoly::eq_evals(&x[n_col..]);
+
+        // the message part of the systematic code is the row itself
+        let combinations = [&proximity_coefs, &eq_row]
+            .iter()
+            .flat_map(|coefs| {
+                (0..(1 << n_col)).map(move |j| {
+                    combine::<C>(state.encoded_rows.iter().skip(j).step_by(code_len), coefs)
+                })
+            })
+            .collect::<Vec<C::Field>>();
+        let mut all_combinations = vec![C::Field::zero(); root_buffer(combinations.len())];
+        mpi_config.gather_vec(&combinations, &mut all_combinations);
+        for v in &all_combinations {
+            transcript.send_field_element(v, proof);
+        }
+
+        mpi_config.transcript_sync_up(transcript);
+        for index in generate_query_indices::<C>(transcript, params.num_queries, code_len) {
+            let column = state
+                .encoded_rows
+                .iter()
+                .skip(index)
+                .step_by(code_len)
+                .cloned()
+                .collect::<Vec<_>>();
+            let mut columns = vec![C::SimdCircuitField::zero(); root_buffer(column.len())];
+            mpi_config.gather_vec(&column, &mut columns);
+            let path = state.tree.path(index);
+            let mut paths = vec![];
+            mpi_config.gather_bytes(&path, &mut paths);
+
+            if mpi_config.is_root() {
+                for (column, path) in columns.chunks(1 << n_row).zip(paths.chunks(path.len())) {
+                    let mut buffer = vec![];
+                    column
+                        .iter()
+                        .for_each(|v| v.serialize_into(&mut buffer).unwrap());
+                    transcript.send_to_verifier(&buffer, proof);
+                    transcript.send_to_verifier(path, proof);
+                }
+            }
+        }
+    }
+
+    fn verify(
+        &self,
+        params: &Self::Params,
+        x: &[C::ChallengeField],
+        x_simd: &[C::ChallengeField],
+        x_mpi: &[C::ChallengeField],
+        y: C::ChallengeField,
+        transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+        proof: &mut ProofReader,
+    ) -> ProofReadResult<bool> {
+        if self.roots.len() != 1 << x_mpi.len() {
+            return Ok(false);
+        }
+
+        let (n_row, n_col) = matrix_shape(x.len());
+        let code = OrionCode::<C::CircuitField>::new(1 << n_col);
+        let code_len = 2 << n_col;
+
+        // the prover syncs the transcripts of the ranks up before each round of challenges
+        if !x_mpi.is_empty() {
+            transcript.hash_to_digest();
+        }
+        let proximity_coefs = transcript.generate_challenge_vector::<C::ChallengeField>(1 << n_row);
+        let eq_row = MultiLinearPoly::eq_evals(&x[n_col..]);
+
+        // for each rank, the encoded proximity row and evaluation row
+        let mut encoded_combinations = vec![];
+        let mut local_evals = vec![];
+        for _ in 0..self.roots.len() {
+            let mut rows = vec![];
+            for _ in 0..2 {
+                let row = (0..(1 << n_col))
+                    .map(|_| transcript.receive_field_element::<C::Field>(proof))
+                    .collect::<ProofReadResult<Vec<C::Field>>>()?;
+                rows.push(row);
+            }
+
+            let x_col = x[..n_col]
+                .iter()
+                .map(|xi| C::Field::from(*xi))
+                .collect::<Vec<C::Field>>();
+            let mut scratch = vec![C::Field::default(); rows[1].len()];
+            let y_simd = MultiLinearPoly::eval_generic(&rows[1], &x_col, &mut scratch);
+            let y_simd_unpacked = y_simd.unpack();
+            let mut scratch = vec![C::ChallengeField::default(); y_simd_unpacked.len()];
+            local_evals.push(MultiLinearPoly::eval_generic(
+                &y_simd_unpacked,
+                x_simd,
+                &mut scratch,
+            ));
+
+            encoded_combinations.push(
+                rows.iter()
+                    .map(|row| code.encode(row, |v, c| C::field_mul_circuit_field(v, c)))
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        if !x_mpi.is_empty() {
+            transcript.hash_to_digest();
+        }
+        let mut verified = true;
+        let path_size = MerkleTree::<C::FiatShamirHashType>::path_size(code_len);
+        for index in generate_query_indices::<C>(transcript, params.num_queries, code_len) {
+            for (root, encoded) in self.roots.iter().zip(&encoded_combinations) {
+                let column_bytes =
+                    proof.peek_bytes((1 << n_row) * C::SimdCircuitField::SERIALIZED_SIZE)?;
+                let column = (0..(1 << n_row))
+                    .map(|_| transcript.receive_field_element::<C::SimdCircuitField>(proof))
+                    .collect::<ProofReadResult<Vec<C::SimdCircuitField>>>()?;
+                let path = transcript.receive_from_prover(path_size, proof)?;
+                verified &= MerkleTree::<C::FiatShamirHashType>::verify_path(
+                    root,
+                    &MerkleTree::<C::FiatShamirHashType>::hash_leaf(column_bytes),
+                    index,
+                    path,
+                );
+
+                verified &= combine::<C>(column.iter(), &proximity_coefs) == encoded[0][index];
+                verified &= combine::<C>(column.iter(), &eq_row) == encoded[1][index];
+            }
+        }
+
+        let mut scratch = vec![C::ChallengeField::default(); local_evals.len()];
+        Ok(verified && y == MultiLinearPoly::eval_generic(&local_evals, x_mpi, &mut scratch))
+    }
+}
@@ -28,6 +28,20 @@ impl MultiLinearPoly {
         }
     }
 
+    /// eq(r, i) for all i, with the first variable as the lowest bit of i.
+    pub fn eq_evals<F: Field>(r: &[F]) -> Vec<F> {
+        let mut evals = vec![F::one()];
+        for ri in r {
+            let hi = evals.iter().map(|e| *e * ri).collect::<Vec<F>>();
+            evals
+                .iter_mut()
+                .zip(hi.iter())
+                .for_each(|(lo, hi)| *lo -= hi);
+            evals.extend(hi);
+        }
+        evals
+    }
+
     pub fn eval_circuit_vals_at_challenge<C: GKRConfig>(
         evals: &[C::SimdCircuitField],
         x: &[C::ChallengeField],
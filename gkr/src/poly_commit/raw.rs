@@ -4,9 +4,10 @@
 use std::io::{Read, Write};
 
 use arith::{Field, FieldSerde, FieldSerdeResult, SimdField};
-use config::{GKRConfig, MPIConfig};
+use config::{Config, GKRConfig, MPIConfig};
+use transcript::{Proof, ProofReadResult, ProofReader, TranscriptInstance};
 
-use crate::MultiLinearPoly;
+use crate::{MultiLinearPoly, PCSSetupResult, PolynomialCommitment};
 
 #[derive(Default)]
 pub struct RawOpening {}
@@ -17,41 +18,43 @@ pub struct RawCommitment<C: GKRConfig> {
 
 impl<C: GKRConfig> RawCommitment<C> {
     #[inline]
-    pub fn size(&self) -> usize {
-        self.poly_vals.len() * C::SimdCircuitField::SIZE
-    }
-
-    #[inline]
-    pub fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
-        self.poly_vals
-            .iter()
-            .try_for_each(|v| v.serialize_into(&mut writer))
+    pub fn new(poly_vals: &[C::SimdCircuitField]) -> Self {
+        RawCommitment {
+            poly_vals: poly_vals.to_owned(),
+        }
     }
 
-    #[inline]
-    pub fn deserialize_from<R: Read>(mut reader: R, poly_size: usize) -> Self {
-        let poly_vals = (0..poly_size)
-            .map(|_| C::SimdCircuitField::deserialize_from(&mut reader).unwrap()) // TODO: error propagation
-            .collect();
-
-        RawCommitment { poly_vals }
+    #[inline(always)]
+    fn eval_local(
+        v: &[C::SimdCircuitField],
+        x: &[C::ChallengeField],
+        x_simd: &[C::ChallengeField],
+    ) -> C::ChallengeField {
+        let mut scratch = vec![C::Field::default(); v.len()];
+        let y_simd = MultiLinearPoly::eval_circuit_vals_at_challenge::<C>(v, x, &mut scratch);
+        let y_simd_unpacked = y_simd.unpack();
+        let mut scratch = vec![C::ChallengeField::default(); y_simd_unpacked.len()];
+        MultiLinearPoly::eval_generic(&y_simd_unpacked, x_simd, &mut scratch)
     }
 }
 
-impl<C: GKRConfig> RawCommitment<C> {
+impl<C: GKRConfig> PolynomialCommitment<C> for RawCommitment<C> {
+    type Params = ();
+    type ProverState = ();
+
     #[inline]
-    pub fn new(poly_vals: &[C::SimdCircuitField]) -> Self {
-        RawCommitment {
-            poly_vals: poly_vals.to_owned(),
-        }
+    fn setup(_config: &Config<C>, _num_vars: usize) -> PCSSetupResult<Self::Params> {
+        Ok(())
     }
 
-    /// create a commitment collectively
-    /// Should also work if mpi is not initialized
     #[inline]
-    pub fn mpi_new(local_poly_vals: &Vec<C::SimdCircuitField>, mpi_config: &MPIConfig) -> Self {
+    fn mpi_commit(
+        _params: &Self::Params,
+        local_poly_vals: &[C::SimdCircuitField],
+        mpi_config: &MPIConfig,
+    ) -> (Self, Self::ProverState) {
         if mpi_config.world_size() == 1 {
-            Self::new(local_poly_vals)
+            (Self::new(local_poly_vals), ())
         } else {
             let mut buffer = if mpi_config.is_root() {
                 vec![C::SimdCircuitField::zero(); local_poly_vals.len() * mpi_config.world_size()]
@@ -59,46 +62,60 @@ impl<C: GKRConfig> RawCommitment<C> {
                 vec![]
             };
 
-            mpi_config.gather_vec(local_poly_vals, &mut buffer);
-            Self { poly_vals: buffer }
+            mpi_config.gather_vec(&local_poly_vals.to_vec(), &mut buffer);
+            (Self { poly_vals: buffer }, ())
         }
     }
 
-    #[inline(always)]
-    fn eval_local(
-        v: &[C::SimdCircuitField],
-        x: &[C::ChallengeField],
-        x_simd: &[C::ChallengeField],
-    ) -> C::ChallengeField {
-        let mut scratch = vec![C::Field::default(); v.len()];
-        let y_simd = MultiLinearPoly::eval_circuit_vals_at_challenge::<C>(v, x, &mut scratch);
-        let y_simd_unpacked = y_simd.unpack();
-        let mut scratch = vec![C::ChallengeField::default(); y_simd_unpacked.len()];
-        MultiLinearPoly::eval_generic(&y_simd_unpacked, x_simd, &mut scratch)
+    #[inline]
+    fn size(&self) -> usize {
+        self.poly_vals.len() * C::SimdCircuitField::SIZE
     }
 
     #[inline]
-    pub fn verify(
-        &self,
-        x: &[C::ChallengeField],
-        x_simd: &[C::ChallengeField],
-        y: C::ChallengeField,
-    ) -> bool {
-        y == Self::eval_local(&self.poly_vals, x, x_simd)
+    fn serialize_into<W: Write>(&self, mut writer: W) -> FieldSerdeResult<()> {
+        self.poly_vals
+            .iter()
+            .try_for_each(|v| v.serialize_into(&mut writer))
+    }
+
+    #[inline]
+    fn deserialize_from<R: Read>(mut reader: R, num_vars: usize) -> FieldSerdeResult<Self> {
+        let poly_size = (1 << num_vars) / C::get_field_pack_size();
+        let poly_vals = (0..poly_size)
+            .map(|_| C::SimdCircuitField::deserialize_from(&mut reader))
+            .collect::<FieldSerdeResult<_>>()?;
+
+        Ok(RawCommitment { poly_vals })
+    }
+
+    /// For Raw, the verifier evaluates the polynomial itself, so there is nothing to open.
+    #[inline]
+    fn open(
+        _params: &Self::Params,
+        _state: &Self::ProverState,
+        _x: &[C::ChallengeField],
+        _x_simd: &[C::ChallengeField],
+        _x_mpi: &[C::ChallengeField],
+        _transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+        _proof: &mut Proof,
+        _mpi_config: &MPIConfig,
+    ) {
+        // no need to update transcript
     }
 
-    /// Note: this only runs on the root rank
-    /// Note: verifier should not have access to the MPIConfig
-    /// The mpi size is implicitly specified by the length of x_mpi,
-    /// So make sure it is correct
     #[inline]
-    pub fn mpi_verify(
+    fn verify(
         &self,
+        _params: &Self::Params,
         x: &[C::ChallengeField],
         x_simd: &[C::ChallengeField],
         x_mpi: &[C::ChallengeField],
         y: C::ChallengeField,
-    ) -> bool {
+        _transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+        _proof: &mut ProofReader,
+    ) -> ProofReadResult<bool> {
+        // for Raw, no need to load from proof
         let local_poly_size = self.poly_vals.len() >> x_mpi.len();
         let local_evals = self
             .poly_vals
@@ -107,6 +124,6 @@ impl<C: GKRConfig> RawCommitment<C> {
             .collect::<Vec<C::ChallengeField>>();
 
         let mut scratch = vec![C::ChallengeField::default(); local_evals.len()];
-        y == MultiLinearPoly::eval_generic(&local_evals, x_mpi, &mut scratch)
+        Ok(y == MultiLinearPoly::eval_generic(&local_evals, x_mpi, &mut scratch))
     }
 }
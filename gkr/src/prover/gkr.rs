@@ -2,19 +2,22 @@
 
 use arith::{Field, SimdField};
 use ark_std::{end_timer, start_timer};
-use circuit::Circuit;
+use circuit::{CircuitAssignment, CircuitStructure, RndCoefs};
 use config::{GKRConfig, MPIConfig};
 use sumcheck::{sumcheck_prove_gkr_layer, GkrScratchpad};
-use transcript::{Transcript, TranscriptInstance};
+use transcript::{Proof, Transcript, TranscriptInstance};
 
 use crate::MultiLinearPoly;
 
 // FIXME
 #[allow(clippy::type_complexity)]
 pub fn gkr_prove<C: GKRConfig>(
-    circuit: &Circuit<C>,
+    circuit: &CircuitStructure<C>,
+    rnd_coefs: &RndCoefs<C>,
+    assignment: &CircuitAssignment<C>,
     sp: &mut GkrScratchpad<C>,
     transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    proof: &mut Proof,
     mpi_config: &MPIConfig,
 ) -> (
     C::ChallengeField,
@@ -45,7 +48,7 @@ pub fn gkr_prove<C: GKRConfig>(
     let mut alpha = C::ChallengeField::one();
     let mut beta = None;
 
-    let output_vals = &circuit.layers.last().unwrap().output_vals;
+    let output_vals = &assignment.output_vals;
 
     let claimed_v_simd =
         MultiLinearPoly::eval_circuit_vals_at_challenge::<C>(output_vals, &rz0, &mut sp.hg_evals);
@@ -72,6 +75,8 @@ pub fn gkr_prove<C: GKRConfig>(
     for i in (0..layer_num).rev() {
         (rz0, rz1, r_simd, r_mpi) = sumcheck_prove_gkr_layer(
             &circuit.layers[i],
+            rnd_coefs.layer(i),
+            &assignment.input_vals[i],
             &rz0,
             &rz1,
             &r_simd,
@@ -79,6 +84,7 @@ pub fn gkr_prove<C: GKRConfig>(
             &alpha,
             &beta,
             transcript,
+            proof,
             sp,
             mpi_config,
         );
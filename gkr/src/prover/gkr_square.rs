@@ -1,19 +1,33 @@
 // an implementation of the GKR^2 protocol
 //! This module implements the core GKR^2 IOP.
 
+use arith::SimdField;
 use ark_std::{end_timer, start_timer};
-use circuit::Circuit;
+use circuit::{CircuitAssignment, CircuitStructure, RndCoefs};
 use config::GKRConfig;
 use sumcheck::{sumcheck_prove_gkr_square_layer, GkrScratchpad};
-use transcript::{Transcript, TranscriptInstance};
+use transcript::{Proof, Transcript, TranscriptInstance};
 
 use crate::MultiLinearPoly;
 
+/// Prove a GKR^2 circuit.
+///
+/// The sumcheck runs lane-wise over the SIMD packed field, so the claimed output
+/// of every SIMD lane is sent in the proof and then folded with `r_simd` into the
+/// returned `claimed_v`. After the last layer a fresh `r_simd` is sampled to fold
+/// the per-lane input claims for the polynomial commitment opening.
 pub fn gkr_square_prove<C: GKRConfig>(
-    circuit: &Circuit<C>,
+    circuit: &CircuitStructure<C>,
+    rnd_coefs: &RndCoefs<C>,
+    assignment: &CircuitAssignment<C>,
     sp: &mut GkrScratchpad<C>,
     transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
-) -> (C::Field, Vec<C::ChallengeField>) {
+    proof: &mut Proof,
+) -> (
+    C::ChallengeField,
+    Vec<C::ChallengeField>,
+    Vec<C::ChallengeField>,
+) {
     let timer = start_timer!(|| "gkr^2 prove");
     let layer_num = circuit.layers.len();
 
@@ -22,15 +36,34 @@ pub fn gkr_square_prove<C: GKRConfig>(
         rz0.push(transcript.generate_challenge::<C::ChallengeField>());
     }
 
-    let circuit_output = &circuit.layers.last().unwrap().output_vals;
-    let claimed_v = MultiLinearPoly::eval_circuit_vals_at_challenge::<C>(
+    let circuit_output = &assignment.output_vals;
+    let claimed_v_simd = MultiLinearPoly::eval_circuit_vals_at_challenge::<C>(
         circuit_output,
         &rz0,
         &mut sp.hg_evals,
     );
+    transcript.send_field_element::<C::Field>(&claimed_v_simd, proof);
+
+    let mut r_simd = vec![];
+    for _ in 0..C::get_field_pack_size().trailing_zeros() {
+        r_simd.push(transcript.generate_challenge::<C::ChallengeField>());
+    }
+    let claimed_v = MultiLinearPoly::eval_generic::<C::ChallengeField>(
+        &claimed_v_simd.unpack(),
+        &r_simd,
+        &mut sp.eq_evals_at_r_simd0,
+    );
 
     for i in (0..layer_num).rev() {
-        rz0 = sumcheck_prove_gkr_square_layer(&circuit.layers[i], &rz0, transcript, sp);
+        rz0 = sumcheck_prove_gkr_square_layer(
+            &circuit.layers[i],
+            rnd_coefs.layer(i),
+            &assignment.input_vals[i],
+            &rz0,
+            transcript,
+            proof,
+            sp,
+        );
 
         log::trace!("Layer {} proved", i);
         log::trace!("rz0.0: {:?}", rz0[0]);
@@ -38,6 +71,11 @@ pub fn gkr_square_prove<C: GKRConfig>(
         log::trace!("rz0.2: {:?}", rz0[2]);
     }
 
+    let mut r_simd = vec![];
+    for _ in 0..C::get_field_pack_size().trailing_zeros() {
+        r_simd.push(transcript.generate_challenge::<C::ChallengeField>());
+    }
+
     end_timer!(timer);
-    (claimed_v, rz0)
+    (claimed_v, rz0, r_simd)
 }
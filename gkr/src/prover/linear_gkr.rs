@@ -1,63 +1,222 @@
 //! This module implements the whole GKR prover, including the IOP and PCS.
 
+use arith::Field;
 use ark_std::{end_timer, start_timer};
-use circuit::Circuit;
-use config::{Config, GKRConfig, GKRScheme, PolynomialCommitmentType};
+use circuit::{CircuitAssignment, CircuitStructure};
+use config::{Config, FieldType, GKRConfig, GKRScheme, PolynomialCommitmentType};
 use sumcheck::GkrScratchpad;
 use transcript::{Proof, Transcript, TranscriptInstance};
 
-use crate::{gkr_prove, gkr_square_prove, RawCommitment};
+use crate::{
+    gkr_prove, gkr_square_prove, FRICommitment, KZGCommitment, OrionCommitment, PCSParams,
+    PCSSetupResult, PolynomialCommitment, RawCommitment,
+};
 
+/// Size in bytes of the proof-of-work nonce in the proof.
+#[cfg(feature = "grinding")]
+pub(crate) const GRINDING_NONCE_SIZE: usize = 8;
+
+/// Number of leading zero bits of H(digest || nonce).
+#[cfg(feature = "grinding")]
+pub(crate) fn grinding_leading_zeros<C: GKRConfig>(digest: &[u8], nonce: u64) -> usize {
+    use transcript::FiatShamirHash;
+
+    let mut input = digest.to_vec();
+    input.extend_from_slice(&nonce.to_le_bytes());
+    let mut output = vec![0u8; C::FiatShamirHashType::DIGEST_SIZE];
+    C::FiatShamirHashType::hash(&mut output, &input);
+
+    let mut zeros = 0;
+    for byte in output.iter() {
+        zeros += byte.leading_zeros() as usize;
+        if *byte != 0 {
+            break;
+        }
+    }
+    zeros
+}
+
+/// Proof-of-work bits for proving the circuit, see `Config::grinding_bits_for`.
+///
+/// A vanilla GKR layer runs the sumchecks over x and y, each with a round per variable of the
+/// layer input, SIMD lane and MPI rank, of degree at most 3. A GKR^2 layer runs one sumcheck,
+/// of degree 6 for the pow5 gates.
+#[cfg(feature = "grinding")]
+pub fn grinding_bits<C: GKRConfig>(config: &Config<C>, circuit: &CircuitStructure<C>) -> usize {
+    let (sumcheck_degree, num_phases) = match config.gkr_scheme {
+        GKRScheme::Vanilla => (3, 2),
+        GKRScheme::GkrSquare => (6, 1),
+    };
+    let num_extra_vars = C::get_field_pack_size().trailing_zeros() as usize
+        + config.mpi_config.world_size().trailing_zeros() as usize;
+    let num_sumcheck_rounds = circuit
+        .layers
+        .iter()
+        .map(|layer| num_phases * (layer.input_var_num + num_extra_vars))
+        .sum();
+    config.grinding_bits_for(
+        sumcheck_degree,
+        num_sumcheck_rounds,
+        crate::pcs_query_bits(config),
+    )
+}
+
+/// Proof of work: find a nonce such that H(digest || nonce) has `grinding_bits` leading
+/// zeros, and send it to the verifier.
+///
+/// The nonce comes after the commitment and before any challenge, so every attempt at new
+/// random coefficients and first GKR challenges costs 2^grinding_bits hashes. It does not
+/// protect the later challenges: a cheating prover can redraw one of them by changing the
+/// prover message just before it, without a new nonce.
 #[cfg(feature = "grinding")]
 pub(crate) fn grind<C: GKRConfig>(
-    transcript: &mut transcript::TranscriptInstance<C::FiatShamirHashType>,
-    config: &Config<C>,
+    transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    proof: &mut Proof,
+    grinding_bits: usize,
 ) {
-    use arith::{Field, FieldSerde};
-    use transcript::FiatShamirHash;
+    let timer = start_timer!(|| format!("grind {} bits", grinding_bits));
 
-    let timer = start_timer!(|| format!("grind {} bits", config.grinding_bits));
+    transcript.absorb_label("grinding");
+    transcript.hash_to_digest();
+    let nonce = (0u64..)
+        .find(|nonce| grinding_leading_zeros::<C>(&transcript.digest, *nonce) >= grinding_bits)
+        .unwrap();
+    transcript.send_to_verifier(&nonce.to_le_bytes(), proof);
 
-    let mut hash_bytes = vec![];
+    end_timer!(timer);
+}
 
-    // ceil(32/field_size)
-    let num_field_elements = (31 + C::ChallengeField::SIZE) / C::ChallengeField::SIZE;
+/// Commit to the input layer with `PCS`, run the GKR IOP, and open the commitment at
+/// the resulting challenges.
+fn prove_with_pcs<C: GKRConfig, PCS: PolynomialCommitment<C>>(
+    config: &Config<C>,
+    sp: &mut GkrScratchpad<C>,
+    pcs_params: &PCS::Params,
+    structure: &CircuitStructure<C>,
+    assignment: &mut CircuitAssignment<C>,
+) -> (C::ChallengeField, Proof) {
+    // PC commit
+    let (commitment, pcs_state) =
+        PCS::mpi_commit(pcs_params, &assignment.input_vals[0], &config.mpi_config);
 
-    let initial_hash =
-        transcript.generate_challenge_vector::<C::ChallengeField>(num_field_elements);
-    initial_hash
-        .iter()
-        .for_each(|h| h.serialize_into(&mut hash_bytes).unwrap()); // TODO: error propagation
+    // the verifier gets the public inputs of all the processes
+    let mut public_input = if config.mpi_config.is_root() {
+        vec![
+            C::SimdCircuitField::zero();
+            assignment.public_input.len() * config.mpi_config.world_size()
+        ]
+    } else {
+        vec![]
+    };
+    config
+        .mpi_config
+        .gather_vec(&assignment.public_input, &mut public_input);
+
+    let mut transcript = TranscriptInstance::new();
+    structure.absorb_statement(&public_input, &mut transcript);
 
-    assert!(hash_bytes.len() >= 32, "hash len: {}", hash_bytes.len());
-    hash_bytes.truncate(32);
+    let mut buffer = vec![];
+    commitment.serialize_into(&mut buffer).unwrap(); // TODO: error propagation
+    let mut proof = Proof::default();
+    transcript.absorb_label("commitment");
+    transcript.send_to_verifier(&buffer, &mut proof);
 
-    for _ in 0..(1 << config.grinding_bits) {
-        C::FiatShamirHashType::hash_inplace(&mut hash_bytes);
+    config.mpi_config.transcript_sync_up(&mut transcript);
+
+    #[cfg(feature = "grinding")]
+    grind::<C>(
+        &mut transcript,
+        &mut proof,
+        grinding_bits(config, structure),
+    );
+
+    let rnd_coefs = structure.draw_rnd_coefs(&mut transcript);
+    assignment.evaluate(structure, &rnd_coefs);
+
+    transcript.absorb_label("gkr");
+    let (claimed_v, rx, ry, rsimd, rmpi) = match config.gkr_scheme {
+        GKRScheme::GkrSquare => {
+            let (claimed_v, rx, rsimd) = gkr_square_prove(
+                structure,
+                &rnd_coefs,
+                assignment,
+                sp,
+                &mut transcript,
+                &mut proof,
+            );
+            (claimed_v, rx, None, rsimd, vec![])
+        }
+        GKRScheme::Vanilla => gkr_prove(
+            structure,
+            &rnd_coefs,
+            assignment,
+            sp,
+            &mut transcript,
+            &mut proof,
+            &config.mpi_config,
+        ),
+    };
+
+    // open, collectively
+    transcript.absorb_label("opening");
+    PCS::open(
+        pcs_params,
+        &pcs_state,
+        &rx,
+        &rsimd,
+        &rmpi,
+        &mut transcript,
+        &mut proof,
+        &config.mpi_config,
+    );
+    if let Some(ry) = ry {
+        transcript.absorb_label("opening");
+        PCS::open(
+            pcs_params,
+            &pcs_state,
+            &ry,
+            &rsimd,
+            &rmpi,
+            &mut transcript,
+            &mut proof,
+            &config.mpi_config,
+        );
     }
-    transcript.append_u8_slice(&hash_bytes[..32]);
-    end_timer!(timer);
+
+    (claimed_v, proof)
 }
 
 #[derive(Default)]
 pub struct Prover<C: GKRConfig> {
     config: Config<C>,
     sp: GkrScratchpad<C>,
+    pcs_params: Option<PCSParams<C>>,
 }
 
 impl<C: GKRConfig> Prover<C> {
     pub fn new(config: &Config<C>) -> Self {
         // assert_eq!(config.fs_hash, crate::config::FiatShamirHashType::SHA256);
-        assert_eq!(
-            config.polynomial_commitment_type,
-            PolynomialCommitmentType::Raw
+        match config.polynomial_commitment_type {
+            PolynomialCommitmentType::Raw => (),
+            PolynomialCommitmentType::KZG => assert_eq!(C::FIELD_TYPE, FieldType::BN254),
+            PolynomialCommitmentType::Orion => (),
+            PolynomialCommitmentType::FRI => assert_eq!(C::FIELD_TYPE, FieldType::M31),
+        }
+        // each process would only prove its own slice of the committed inputs
+        assert!(
+            config.gkr_scheme != GKRScheme::GkrSquare || config.mpi_config.world_size() == 1,
+            "GKR^2 does not support MPI"
         );
         Prover {
             config: config.clone(),
             sp: GkrScratchpad::default(),
+            pcs_params: None,
         }
     }
-    pub fn prepare_mem(&mut self, c: &Circuit<C>) {
+
+    /// Allocate the scratchpad and set up the commitment for the circuit.
+    /// Fails if the parameters of the commitment can not be set up, e.g., a missing KZG SRS.
+    pub fn prepare_mem(&mut self, c: &CircuitStructure<C>) -> PCSSetupResult<()> {
         let max_num_input_var = c
             .layers
             .iter()
@@ -75,50 +234,59 @@ impl<C: GKRConfig> Prover<C> {
             max_num_output_var,
             self.config.mpi_config.world_size(),
         );
+
+        let num_vars = c.log_input_size()
+            + C::get_field_pack_size().trailing_zeros() as usize
+            + self.config.mpi_config.world_size().trailing_zeros() as usize;
+        self.pcs_params = Some(PCSParams::setup(&self.config, num_vars)?);
+        Ok(())
     }
 
-    pub fn prove(&mut self, c: &mut Circuit<C>) -> (C::ChallengeField, Proof) {
+    /// Prove the circuit on the inputs of the assignment, evaluating the other layers.
+    /// The structure is only read, so it can be shared by the provers of several threads.
+    pub fn prove(
+        &mut self,
+        structure: &CircuitStructure<C>,
+        assignment: &mut CircuitAssignment<C>,
+    ) -> (C::ChallengeField, Proof) {
         let timer = start_timer!(|| "prove");
         // std::thread::sleep(std::time::Duration::from_secs(1)); // TODO
 
-        // PC commit
-        let commitment =
-            RawCommitment::<C>::mpi_new(&c.layers[0].input_vals, &self.config.mpi_config);
-
-        let mut buffer = vec![];
-        commitment.serialize_into(&mut buffer).unwrap(); // TODO: error propagation
-        let mut transcript = TranscriptInstance::new();
-        transcript.append_u8_slice(&buffer);
-
-        self.config.mpi_config.transcript_sync_up(&mut transcript);
-
-        #[cfg(feature = "grinding")]
-        grind::<C>(&mut transcript, &self.config);
-
-        c.fill_rnd_coefs(&mut transcript);
-        c.evaluate();
-
-        let mut claimed_v = C::ChallengeField::default();
-        let mut _rx = vec![];
-        let mut _ry = None;
-        let mut _rsimd = vec![];
-        let mut _rmpi = vec![];
-
-        if self.config.gkr_scheme == GKRScheme::GkrSquare {
-            (_, _rx) = gkr_square_prove(c, &mut self.sp, &mut transcript);
-        } else {
-            (claimed_v, _rx, _ry, _rsimd, _rmpi) =
-                gkr_prove(c, &mut self.sp, &mut transcript, &self.config.mpi_config);
-        }
-
-        // open
-        match self.config.polynomial_commitment_type {
-            PolynomialCommitmentType::Raw => {
-                // no need to update transcript
-            }
-            _ => todo!(),
-        }
+        let pcs_params = self
+            .pcs_params
+            .as_ref()
+            .expect("prepare_mem must be called before proving");
+        let ret = match pcs_params {
+            PCSParams::Raw => prove_with_pcs::<C, RawCommitment<C>>(
+                &self.config,
+                &mut self.sp,
+                &(),
+                structure,
+                assignment,
+            ),
+            PCSParams::KZG(srs) => prove_with_pcs::<C, KZGCommitment>(
+                &self.config,
+                &mut self.sp,
+                srs,
+                structure,
+                assignment,
+            ),
+            PCSParams::Orion(params) => prove_with_pcs::<C, OrionCommitment<C>>(
+                &self.config,
+                &mut self.sp,
+                params,
+                structure,
+                assignment,
+            ),
+            PCSParams::FRI(params) => prove_with_pcs::<C, FRICommitment<C>>(
+                &self.config,
+                &mut self.sp,
+                params,
+                structure,
+                assignment,
+            ),
+        };
         end_timer!(timer);
-        (claimed_v, transcript.proof)
+        ret
     }
 }
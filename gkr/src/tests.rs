@@ -1,2 +1,64 @@
+mod circuit_builder;
+mod circuit_threads;
 mod gkr_correctness;
+mod poly_commit;
+mod proof_envelope;
+mod statement_binding;
 mod system;
+mod verification_errors;
+
+use circuit::{CircuitAssignment, CircuitBuilder, CircuitStructure, Coef, Term, Witness};
+use config::{Config, GKRConfig, M31ExtConfigSha2};
+use transcript::Proof;
+
+use crate::Prover;
+
+type C = M31ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+// w = x0 * x1 * x2 + 2 * x0 + p, asserted to be zero
+fn test_circuit() -> CircuitStructure<C> {
+    let mut builder = CircuitBuilder::<C>::new();
+    let x = builder.inputs(3);
+    let p = builder.public_input();
+
+    let y = builder.mul(x[0], x[1]);
+    let z = builder.mul(y, x[2]);
+    let w = builder.sum(vec![
+        Term::Add(z, Coef::one()),
+        Term::Add(x[0], Coef::Constant(F::from(2))),
+        Term::Const(Coef::PublicInput(p)),
+    ]);
+    builder.assert_zero(w);
+    builder.build().unwrap()
+}
+
+// The same private and public inputs in every SIMD lane
+fn test_assignment(
+    circuit: &CircuitStructure<C>,
+    private_inputs: &[F],
+    public_inputs: &[F],
+) -> CircuitAssignment<C> {
+    let mut witness = Witness::<C>::new(private_inputs, public_inputs);
+    for _ in 1..C::get_field_pack_size() {
+        witness.push(private_inputs, public_inputs).unwrap();
+    }
+    let mut assignment = CircuitAssignment::new(circuit);
+    assignment.load_witness_batch(&witness, 0).unwrap();
+    assignment
+}
+
+// A satisfying assignment of the test circuit: 2 * 3 * 5 + 2 * 2 - 34 = 0
+fn test_circuit_assignment(circuit: &CircuitStructure<C>) -> CircuitAssignment<C> {
+    test_assignment(circuit, &[2u32, 3, 5, 0].map(F::from), &[-F::from(34)])
+}
+
+fn prove(
+    config: &Config<C>,
+    circuit: &CircuitStructure<C>,
+    assignment: &mut CircuitAssignment<C>,
+) -> (<C as GKRConfig>::ChallengeField, Proof) {
+    let mut prover = Prover::new(config);
+    prover.prepare_mem(circuit).unwrap();
+    prover.prove(circuit, assignment)
+}
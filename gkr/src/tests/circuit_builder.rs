@@ -0,0 +1,75 @@
+use arith::Field;
+use circuit::{CircuitBuilder, Coef, Term};
+use config::{Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig};
+
+use super::{prove, test_assignment, test_circuit, test_circuit_assignment};
+use crate::Verifier;
+
+type C = M31ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+#[test]
+fn test_circuit_builder_prove() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::default());
+    let circuit = test_circuit();
+    let mut assignment = test_circuit_assignment(&circuit);
+    let (claimed_v, proof) = prove(&config, &circuit, &mut assignment);
+
+    let verifier = Verifier::new(&config);
+    assert!(verifier
+        .verify(&circuit, &assignment.public_input, &claimed_v, &proof)
+        .unwrap());
+
+    let wrong_v = claimed_v + <C as GKRConfig>::ChallengeField::one();
+    assert!(!verifier
+        .verify(&circuit, &assignment.public_input, &wrong_v, &proof)
+        .unwrap());
+}
+
+#[test]
+fn test_pow5_circuit_prove() {
+    // v = (x0^5)^5 + 3 * x1 + p, asserted to be zero, and u = x0^5
+    let mut builder = CircuitBuilder::<C>::new();
+    let x = builder.inputs(2);
+    let p = builder.public_input();
+    let u = builder.pow5(x[0]);
+    let v = builder.sum(vec![
+        Term::Pow5(u, Coef::one()),
+        Term::Add(x[1], Coef::Constant(F::from(3))),
+        Term::Const(Coef::PublicInput(p)),
+    ]);
+    builder.assert_zero(v);
+    builder.output(u);
+    let circuit = builder.build().unwrap();
+    let mut assignment = test_assignment(
+        &circuit,
+        &[F::from(2), F::from(5)],
+        &[-F::from((1 << 25) + 15)],
+    );
+
+    let config = Config::<C>::new(GKRScheme::GkrSquare, MPIConfig::default());
+    let (claimed_v, proof) = prove(&config, &circuit, &mut assignment);
+    assert!(Verifier::new(&config)
+        .verify(&circuit, &assignment.public_input, &claimed_v, &proof)
+        .unwrap());
+}
+
+#[test]
+fn test_rnd_coefs_prove() {
+    // the prover and the verifier draw the same random coefficients
+    let mut builder = CircuitBuilder::<C>::new();
+    let x = builder.inputs(2);
+    let y = builder.sum(vec![
+        Term::Mul(x[0], x[1], Coef::Random),
+        Term::Const(Coef::Random),
+    ]);
+    builder.output(y);
+    let circuit = builder.build().unwrap();
+    let mut assignment = test_assignment(&circuit, &[F::from(3), F::from(5)], &[]);
+
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::default());
+    let (claimed_v, proof) = prove(&config, &circuit, &mut assignment);
+    assert!(Verifier::new(&config)
+        .verify(&circuit, &assignment.public_input, &claimed_v, &proof)
+        .unwrap());
+}
@@ -0,0 +1,48 @@
+use std::{sync::Arc, thread};
+
+use arith::Field;
+use circuit::{CircuitAssignment, CircuitStructure};
+use config::{Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig};
+
+use super::{prove, test_circuit, test_circuit_assignment};
+use crate::Verifier;
+
+type C = M31ExtConfigSha2;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn assert_circuit_send_sync<Cfg: GKRConfig>() {
+    assert_send_sync::<CircuitStructure<Cfg>>();
+    assert_send_sync::<CircuitAssignment<Cfg>>();
+}
+
+#[test]
+fn test_circuit_shared_across_threads() {
+    assert_circuit_send_sync::<C>();
+
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::default());
+    let circuit = Arc::new(test_circuit());
+    let assignment = test_circuit_assignment(&circuit);
+
+    // each thread proves its own assignment of the same structure
+    let handles = (0..2)
+        .map(|_| {
+            let circuit = circuit.clone();
+            let config = config.clone();
+            let mut assignment = assignment.clone();
+            thread::spawn(move || {
+                let (claimed_v, proof) = prove(&config, &circuit, &mut assignment);
+                (assignment, claimed_v, proof)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let verifier = Verifier::new(&config);
+    for handle in handles {
+        let (assignment, claimed_v, proof) = handle.join().unwrap();
+        assert!(assignment.output_vals[0].is_zero());
+        assert!(verifier
+            .verify(&circuit, &assignment.public_input, &claimed_v, &proof)
+            .unwrap());
+    }
+}
@@ -24,7 +24,7 @@
 //     assert_eq!(last_layer_first_output, M31x16::zero());
 
 //     let mut prover = Prover::new(&config);
-//     prover.prepare_mem(&circuit);
+//     prover.prepare_mem(&circuit).unwrap();
 //     let (claimed_v, proof) = prover.prove(&mut circuit);
 //     println!("Proof generated. Size: {} bytes", proof.bytes.len());
 //     // write proof to file
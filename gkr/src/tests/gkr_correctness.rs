@@ -1,17 +1,21 @@
-use std::panic;
-use std::panic::AssertUnwindSafe;
 use std::time::Instant;
 
 use arith::Field;
-use circuit::Circuit;
+use circuit::{
+    CircuitAssignment, CircuitLayer, CircuitStructure, CoefType, Gate, POW1_GATE_TYPE,
+    POW5_GATE_TYPE,
+};
 use config::{
-    root_println, BN254ConfigKeccak, BN254ConfigSha2, Config, FieldType, GF2ExtConfigKeccak,
-    GF2ExtConfigSha2, GKRConfig, GKRScheme, M31ExtConfigKeccak, M31ExtConfigSha2, MPIConfig,
+    root_println, BN254ConfigKeccak, BN254ConfigMiMC7, BN254ConfigPoseidon, BN254ConfigSha2,
+    Config, FieldType, GF2ExtConfigKeccak, GF2ExtConfigSha2, GKRConfig, GKRScheme,
+    GoldilocksExtConfigKeccak, GoldilocksExtConfigSha2, M31ExtConfigKeccak, M31ExtConfigPoseidon,
+    M31ExtConfigSha2, MPIConfig, PolynomialCommitmentType,
 };
 use rand::Rng;
 use sha2::Digest;
+use transcript::TranscriptInstance;
 
-use crate::{utils::*, Prover, Verifier};
+use crate::{utils::*, KZGCommitment, Prover, Verifier};
 
 #[test]
 fn test_gkr_correctness() {
@@ -33,6 +37,10 @@ fn test_gkr_correctness() {
         GKRScheme::Vanilla,
         mpi_config.clone(),
     ));
+    test_gkr_correctness_helper::<M31ExtConfigPoseidon>(&Config::<M31ExtConfigPoseidon>::new(
+        GKRScheme::Vanilla,
+        mpi_config.clone(),
+    ));
     test_gkr_correctness_helper::<BN254ConfigSha2>(&Config::<BN254ConfigSha2>::new(
         GKRScheme::Vanilla,
         mpi_config.clone(),
@@ -41,6 +49,14 @@ fn test_gkr_correctness() {
         GKRScheme::Vanilla,
         mpi_config.clone(),
     ));
+    test_gkr_correctness_helper::<BN254ConfigPoseidon>(&Config::<BN254ConfigPoseidon>::new(
+        GKRScheme::Vanilla,
+        mpi_config.clone(),
+    ));
+    test_gkr_correctness_helper::<BN254ConfigMiMC7>(&Config::<BN254ConfigMiMC7>::new(
+        GKRScheme::Vanilla,
+        mpi_config.clone(),
+    ));
 
     MPIConfig::finalize();
 }
@@ -68,7 +84,7 @@ fn test_gkr_correctness_helper<C: GKRConfig>(config: &Config<C>) {
         FieldType::BN254 => "../data/circuit_bn254.txt",
         _ => unreachable!(),
     };
-    let mut circuit = Circuit::<C>::load_circuit(circuit_path);
+    let circuit = CircuitStructure::<C>::load_circuit(circuit_path).unwrap();
     root_println!(config.mpi_config, "Circuit loaded.");
 
     let witness_path = match C::FIELD_TYPE {
@@ -77,20 +93,24 @@ fn test_gkr_correctness_helper<C: GKRConfig>(config: &Config<C>) {
         FieldType::BN254 => KECCAK_BN254_WITNESS,
         _ => unreachable!(),
     };
-    circuit.load_witness_file(witness_path);
+    let mut assignment = CircuitAssignment::new(&circuit);
+    assignment.load_witness_file(witness_path).unwrap();
     root_println!(config.mpi_config, "Witness loaded.");
 
-    circuit.evaluate();
-    let output = &circuit.layers.last().unwrap().output_vals;
+    assignment.evaluate(
+        &circuit,
+        &circuit.draw_rnd_coefs(&mut TranscriptInstance::new()),
+    );
+    let output = &assignment.output_vals;
     assert!(output[..circuit.expected_num_output_zeros]
         .iter()
         .all(|f| f.is_zero()));
 
     let mut prover = Prover::new(config);
-    prover.prepare_mem(&circuit);
+    prover.prepare_mem(&circuit).unwrap();
 
     let proving_start = Instant::now();
-    let (claimed_v, proof) = prover.prove(&mut circuit);
+    let (claimed_v, proof) = prover.prove(&circuit, &mut assignment);
     root_println!(
         config.mpi_config,
         "Proving time: {} μs",
@@ -122,20 +142,25 @@ fn test_gkr_correctness_helper<C: GKRConfig>(config: &Config<C>) {
     root_println!(config.mpi_config,);
 
     let mut public_input_gathered = if config.mpi_config.is_root() {
-        vec![C::SimdCircuitField::ZERO; circuit.public_input.len() * config.mpi_config.world_size()]
+        vec![
+            C::SimdCircuitField::ZERO;
+            assignment.public_input.len() * config.mpi_config.world_size()
+        ]
     } else {
         vec![]
     };
     config
         .mpi_config
-        .gather_vec(&circuit.public_input, &mut public_input_gathered);
+        .gather_vec(&assignment.public_input, &mut public_input_gathered);
 
     // Verify
     if config.mpi_config.is_root() {
         let verifier = Verifier::new(config);
         println!("Verifier created.");
         let verification_start = Instant::now();
-        assert!(verifier.verify(&mut circuit, &public_input_gathered, &claimed_v, &proof));
+        assert!(verifier
+            .verify(&circuit, &public_input_gathered, &claimed_v, &proof)
+            .unwrap());
         println!(
             "Verification time: {} μs",
             verification_start.elapsed().as_micros()
@@ -147,15 +172,251 @@ fn test_gkr_correctness_helper<C: GKRConfig>(config: &Config<C>) {
         let random_change = rng.gen_range(1..256) as u8;
         bad_proof.bytes[random_idx] ^= random_change;
 
-        // Catch the panic and treat it as returning `false`
-        let result = panic::catch_unwind(AssertUnwindSafe(|| {
-            verifier.verify(&mut circuit, &public_input_gathered, &claimed_v, &bad_proof)
-        }));
-
-        let final_result = result.unwrap_or_default();
-
-        assert!(!final_result,);
+        // a malformed proof is an error rather than a panic
+        let result = verifier.verify(&circuit, &public_input_gathered, &claimed_v, &bad_proof);
+        assert!(!result.unwrap_or_default());
         println!("Bad proof rejected.");
         println!("============== end ===============");
     }
 }
+
+#[test]
+fn test_gkr_square_correctness() {
+    let mpi_config = MPIConfig::default();
+
+    test_pcs_correctness_helper(
+        &Config::<M31ExtConfigSha2>::new(GKRScheme::GkrSquare, mpi_config.clone()),
+        gkr_square_test_circuit(),
+    );
+    test_pcs_correctness_helper(
+        &Config::<M31ExtConfigKeccak>::new(GKRScheme::GkrSquare, mpi_config.clone()),
+        gkr_square_test_circuit(),
+    );
+    test_pcs_correctness_helper(
+        &Config::<BN254ConfigSha2>::new(GKRScheme::GkrSquare, mpi_config.clone()),
+        gkr_square_test_circuit(),
+    );
+    test_pcs_correctness_helper(
+        &Config::<BN254ConfigKeccak>::new(GKRScheme::GkrSquare, mpi_config.clone()),
+        gkr_square_test_circuit(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "GKR^2 does not support MPI")]
+fn test_gkr_square_mpi_unsupported() {
+    Verifier::new(&Config::<M31ExtConfigSha2>::new(
+        GKRScheme::GkrSquare,
+        MPIConfig::new_for_verifier(2),
+    ));
+}
+
+#[cfg(feature = "grinding")]
+#[test]
+fn test_grinding() {
+    use transcript::{Proof, Transcript};
+
+    use crate::{grind, grinding_leading_zeros, GRINDING_NONCE_SIZE};
+
+    let commitment = b"commitment";
+    let mut transcript =
+        TranscriptInstance::<<M31ExtConfigSha2 as GKRConfig>::FiatShamirHashType>::new();
+    let mut proof = Proof::default();
+    transcript.send_to_verifier(commitment, &mut proof);
+    grind::<M31ExtConfigSha2>(&mut transcript, &mut proof, 8);
+    assert_eq!(proof.bytes.len(), commitment.len() + GRINDING_NONCE_SIZE);
+
+    // the verifier checks the nonce with a single hash
+    let mut replay =
+        TranscriptInstance::<<M31ExtConfigSha2 as GKRConfig>::FiatShamirHashType>::new();
+    replay.absorb_public(commitment);
+    replay.absorb_label("grinding");
+    replay.hash_to_digest();
+    let nonce = u64::from_le_bytes(proof.bytes[commitment.len()..].try_into().unwrap());
+    assert!(grinding_leading_zeros::<M31ExtConfigSha2>(&replay.digest, nonce) >= 8);
+}
+
+fn test_gate<C: GKRConfig, const INPUT_NUM: usize>(
+    i_ids: [usize; INPUT_NUM],
+    o_id: usize,
+    coef: u32,
+    gate_type: usize,
+) -> Gate<C, INPUT_NUM> {
+    Gate {
+        i_ids,
+        o_id,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::from(coef),
+        gate_type,
+    }
+}
+
+// Layers of 2^3 wires, where `gates` adds the gates of output i of a layer, plus a constant
+// added to output `const_o_id`.
+fn layered_test_circuit<C: GKRConfig>(
+    num_layers: usize,
+    const_o_id: usize,
+    gates: impl Fn(&mut CircuitLayer<C>, usize, usize),
+) -> CircuitStructure<C> {
+    const VAR_NUM: usize = 3;
+
+    let mut circuit = CircuitStructure::<C>::default();
+    for _ in 0..num_layers {
+        let mut layer = CircuitLayer::<C> {
+            input_var_num: VAR_NUM,
+            output_var_num: VAR_NUM,
+            ..Default::default()
+        };
+        for i in 0..(1 << VAR_NUM) {
+            gates(&mut layer, i, 1 << VAR_NUM);
+        }
+        layer.const_.push(test_gate([], const_o_id, 7, 0));
+        circuit.layers.push(layer);
+    }
+    circuit.identify_rnd_coefs();
+    circuit.identify_structure_info();
+    circuit
+}
+
+// A small poseidon-like circuit: every layer applies pow5 and pow1 gates, plus a constant.
+fn gkr_square_test_circuit<C: GKRConfig>() -> CircuitStructure<C> {
+    layered_test_circuit(3, 3, |layer, i, n| {
+        layer
+            .uni
+            .push(test_gate([i], i, i as u32 + 1, POW5_GATE_TYPE));
+        layer
+            .uni
+            .push(test_gate([(i + 1) % n], i, 2, POW1_GATE_TYPE));
+    })
+}
+
+#[test]
+fn test_gkr_kzg_correctness() {
+    let mpi_config = MPIConfig::default();
+
+    let mut config = Config::<BN254ConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::KZG;
+    test_pcs_correctness_helper(&config, gkr_test_circuit::<BN254ConfigSha2>());
+
+    let mut config = Config::<BN254ConfigKeccak>::new(GKRScheme::Vanilla, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::KZG;
+    test_pcs_correctness_helper(&config, gkr_test_circuit::<BN254ConfigKeccak>());
+
+    let mut config = Config::<BN254ConfigSha2>::new(GKRScheme::GkrSquare, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::KZG;
+    test_pcs_correctness_helper(&config, gkr_square_test_circuit::<BN254ConfigSha2>());
+}
+
+#[test]
+fn test_gkr_orion_correctness() {
+    let mpi_config = MPIConfig::default();
+
+    let mut config = Config::<M31ExtConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::Orion;
+    test_pcs_correctness_helper(&config, gkr_test_circuit::<M31ExtConfigSha2>());
+
+    let mut config = Config::<M31ExtConfigKeccak>::new(GKRScheme::Vanilla, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::Orion;
+    test_pcs_correctness_helper(&config, gkr_test_circuit::<M31ExtConfigKeccak>());
+
+    let mut config = Config::<GF2ExtConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::Orion;
+    test_pcs_correctness_helper(&config, gkr_test_circuit::<GF2ExtConfigSha2>());
+
+    let mut config = Config::<GF2ExtConfigKeccak>::new(GKRScheme::Vanilla, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::Orion;
+    test_pcs_correctness_helper(&config, gkr_test_circuit::<GF2ExtConfigKeccak>());
+
+    let mut config = Config::<M31ExtConfigSha2>::new(GKRScheme::GkrSquare, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::Orion;
+    test_pcs_correctness_helper(&config, gkr_square_test_circuit::<M31ExtConfigSha2>());
+}
+
+#[test]
+fn test_gkr_fri_correctness() {
+    let mpi_config = MPIConfig::default();
+
+    let mut config = Config::<M31ExtConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::FRI;
+    test_pcs_correctness_helper(&config, gkr_test_circuit::<M31ExtConfigSha2>());
+
+    let mut config = Config::<M31ExtConfigKeccak>::new(GKRScheme::Vanilla, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::FRI;
+    test_pcs_correctness_helper(&config, gkr_test_circuit::<M31ExtConfigKeccak>());
+
+    let mut config = Config::<M31ExtConfigSha2>::new(GKRScheme::GkrSquare, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::FRI;
+    test_pcs_correctness_helper(&config, gkr_square_test_circuit::<M31ExtConfigSha2>());
+}
+
+#[test]
+fn test_gkr_goldilocks_correctness() {
+    let mpi_config = MPIConfig::default();
+
+    let config = Config::<GoldilocksExtConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone());
+    test_pcs_correctness_helper(&config, gkr_test_circuit::<GoldilocksExtConfigSha2>());
+
+    let config = Config::<GoldilocksExtConfigKeccak>::new(GKRScheme::Vanilla, mpi_config.clone());
+    test_pcs_correctness_helper(&config, gkr_test_circuit::<GoldilocksExtConfigKeccak>());
+
+    let config = Config::<GoldilocksExtConfigSha2>::new(GKRScheme::GkrSquare, mpi_config.clone());
+    test_pcs_correctness_helper(
+        &config,
+        gkr_square_test_circuit::<GoldilocksExtConfigSha2>(),
+    );
+
+    let mut config = Config::<GoldilocksExtConfigSha2>::new(GKRScheme::Vanilla, mpi_config.clone());
+    config.polynomial_commitment_type = PolynomialCommitmentType::Orion;
+    test_pcs_correctness_helper(&config, gkr_test_circuit::<GoldilocksExtConfigSha2>());
+}
+
+// A small circuit with mul, add and const gates, so that both rz0 and rz1 are opened.
+fn gkr_test_circuit<C: GKRConfig>() -> CircuitStructure<C> {
+    layered_test_circuit(2, 5, |layer, i, n| {
+        layer
+            .mul
+            .push(test_gate([i, (i + 1) % n], i, i as u32 + 1, 0));
+        layer.add.push(test_gate([(i + 3) % n], i, 2, 1));
+    })
+}
+
+fn test_pcs_correctness_helper<C: GKRConfig>(config: &Config<C>, circuit: CircuitStructure<C>) {
+    let mut assignment = CircuitAssignment::new(&circuit);
+    assignment.set_random_input_for_test();
+
+    let mut config = config.clone();
+    if config.polynomial_commitment_type == PolynomialCommitmentType::KZG {
+        let num_vars =
+            circuit.log_input_size() + C::get_field_pack_size().trailing_zeros() as usize;
+        let srs_file = std::env::temp_dir().join(format!(
+            "expander-kzg-srs-{}-{}",
+            std::process::id(),
+            num_vars
+        ));
+        let mut bytes = vec![];
+        KZGCommitment::write_srs(&KZGCommitment::setup_for_testing(num_vars), &mut bytes).unwrap();
+        std::fs::write(&srs_file, bytes).unwrap();
+        config.kzg_srs_file = Some(srs_file.to_str().unwrap().to_string());
+    }
+
+    let mut prover = Prover::new(&config);
+    let mut verifier = Verifier::new(&config);
+    prover.prepare_mem(&circuit).unwrap();
+    verifier.setup(&circuit).unwrap();
+    let (claimed_v, proof) = prover.prove(&circuit, &mut assignment);
+
+    let public_input = assignment.public_input.clone();
+    assert!(verifier
+        .verify(&circuit, &public_input, &claimed_v, &proof)
+        .unwrap());
+
+    let mut bad_proof = proof.clone();
+    let rng = &mut rand::thread_rng();
+    let random_idx = rng.gen_range(0..bad_proof.bytes.len());
+    let random_change = rng.gen_range(1..256) as u8;
+    bad_proof.bytes[random_idx] ^= random_change;
+
+    // a malformed proof is an error rather than a panic
+    let result = verifier.verify(&circuit, &public_input, &claimed_v, &bad_proof);
+    assert!(!result.unwrap_or_default());
+}
@@ -0,0 +1,174 @@
+use arith::{Field, SimdField};
+use config::{BN254ConfigSha2, GKRConfig, M31ExtConfigKeccak, M31ExtConfigSha2, MPIConfig};
+use rand::{rngs::StdRng, seq::index, SeedableRng};
+use transcript::{Proof, ProofReader, Transcript, TranscriptInstance};
+
+use crate::{
+    FRICommitment, FRIParams, KZGCommitment, MultiLinearPoly, OrionCode, OrionCommitment,
+    OrionParams, PolynomialCommitment, RawCommitment, ORION_RELATIVE_DISTANCE,
+};
+
+#[test]
+fn test_fri_against_raw() {
+    test_pcs_against_raw_helper::<M31ExtConfigSha2, FRICommitment<M31ExtConfigSha2>>(
+        1,
+        |num_vars| FRIParams::new(num_vars, 100),
+    );
+    test_pcs_against_raw_helper::<M31ExtConfigSha2, FRICommitment<M31ExtConfigSha2>>(
+        6,
+        |num_vars| FRIParams::new(num_vars, 100),
+    );
+    test_pcs_against_raw_helper::<M31ExtConfigKeccak, FRICommitment<M31ExtConfigKeccak>>(
+        6,
+        |num_vars| FRIParams::new(num_vars, 100),
+    );
+}
+
+#[test]
+fn test_orion_against_raw() {
+    test_pcs_against_raw_helper::<M31ExtConfigSha2, OrionCommitment<M31ExtConfigSha2>>(1, |_| {
+        OrionParams::new(100)
+    });
+    test_pcs_against_raw_helper::<M31ExtConfigSha2, OrionCommitment<M31ExtConfigSha2>>(6, |_| {
+        OrionParams::new(100)
+    });
+    test_pcs_against_raw_helper::<BN254ConfigSha2, OrionCommitment<BN254ConfigSha2>>(5, |_| {
+        OrionParams::new(100)
+    });
+}
+
+// The distance of the Orion code is assumed: check it on the encodings of sparse messages,
+// the lightest codewords of an expander code, and of random messages.
+#[test]
+fn test_orion_code_distance() {
+    type F = <M31ExtConfigSha2 as GKRConfig>::CircuitField;
+    let mut rng = StdRng::seed_from_u64(0);
+    for msg_len in [1 << 4, 1 << 6, 1 << 8, 1 << 10] {
+        let code = OrionCode::<F>::new(msg_len);
+        let min_weight = (ORION_RELATIVE_DISTANCE * (2 * msg_len) as f64).ceil() as usize;
+        for weight in [1, 2, 3, 8, msg_len] {
+            for _ in 0..16 {
+                let mut msg = vec![F::zero(); msg_len];
+                for i in index::sample(&mut rng, msg_len, weight) {
+                    msg[i] = F::random_unsafe(&mut rng);
+                }
+                let codeword = code.encode(&msg, |a, b| *a * *b);
+                assert!(codeword.iter().filter(|v| !v.is_zero()).count() >= min_weight);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_kzg_against_raw() {
+    test_pcs_against_raw_helper::<BN254ConfigSha2, KZGCommitment>(
+        4,
+        KZGCommitment::setup_for_testing,
+    );
+}
+
+#[test]
+fn test_kzg_srs_serde() {
+    let srs = KZGCommitment::setup_for_testing(3);
+    let mut bytes = vec![];
+    KZGCommitment::write_srs(&srs, &mut bytes).unwrap();
+    let loaded = KZGCommitment::read_srs(bytes.as_slice()).unwrap();
+    assert_eq!(loaded, srs);
+    assert_eq!(KZGCommitment::srs_num_vars(&loaded), 3);
+    assert!(KZGCommitment::read_srs(&bytes[..bytes.len() - 1]).is_err());
+}
+
+// Open at a random point, and check that the commitment accepts exactly the evaluations
+// that the raw commitment accepts. `setup` gives the parameters for a number of variables.
+fn test_pcs_against_raw_helper<C: GKRConfig, PCS: PolynomialCommitment<C>>(
+    num_local_vars: usize,
+    setup: impl FnOnce(usize) -> PCS::Params,
+) {
+    let mut rng = rand::thread_rng();
+    let mpi_config = MPIConfig::default();
+    let num_simd_vars = C::get_field_pack_size().trailing_zeros() as usize;
+
+    let poly_vals = (0..1 << num_local_vars)
+        .map(|_| C::SimdCircuitField::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let x = (0..num_local_vars)
+        .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let x_simd = (0..num_simd_vars)
+        .map(|_| C::ChallengeField::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+
+    // the evaluations over (x, x_simd), with the SIMD lanes unpacked
+    let mut evals = vec![C::ChallengeField::zero(); poly_vals.len() << num_simd_vars];
+    for (i, v) in poly_vals.iter().enumerate() {
+        for (lane, v) in v.unpack().into_iter().enumerate() {
+            evals[(lane << num_local_vars) + i] = C::ChallengeField::from(v);
+        }
+    }
+    let point = x.iter().chain(&x_simd).cloned().collect::<Vec<_>>();
+    let mut scratch = vec![C::ChallengeField::zero(); evals.len()];
+    let y = MultiLinearPoly::eval_generic(&evals, &point, &mut scratch);
+
+    let (raw_commitment, _) = RawCommitment::<C>::mpi_commit(&(), &poly_vals, &mpi_config);
+    let params = setup(num_local_vars + num_simd_vars);
+    let (commitment, state) = PCS::mpi_commit(&params, &poly_vals, &mpi_config);
+
+    let mut proof = Proof::default();
+    PCS::open(
+        &params,
+        &state,
+        &x,
+        &x_simd,
+        &[],
+        &mut TranscriptInstance::new(),
+        &mut proof,
+        &mpi_config,
+    );
+
+    for claimed_y in [y, y + C::ChallengeField::one()] {
+        let raw_verified = raw_commitment
+            .verify(
+                &(),
+                &x,
+                &x_simd,
+                &[],
+                claimed_y,
+                &mut TranscriptInstance::new(),
+                &mut ProofReader::new(&Proof::default()),
+            )
+            .unwrap();
+        let mut reader = ProofReader::new(&proof);
+        let verified = commitment
+            .verify(
+                &params,
+                &x,
+                &x_simd,
+                &[],
+                claimed_y,
+                &mut TranscriptInstance::new(),
+                &mut reader,
+            )
+            .unwrap();
+        assert_eq!(raw_verified, claimed_y == y);
+        assert_eq!(verified, raw_verified);
+        if verified {
+            reader.finish().unwrap();
+        }
+    }
+
+    // a truncated opening can not be read
+    let truncated = Proof {
+        bytes: proof.bytes[..proof.bytes.len() - 1].to_vec(),
+    };
+    assert!(commitment
+        .verify(
+            &params,
+            &x,
+            &x_simd,
+            &[],
+            y,
+            &mut TranscriptInstance::new(),
+            &mut ProofReader::new(&truncated),
+        )
+        .is_err());
+}
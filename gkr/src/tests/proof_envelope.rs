@@ -0,0 +1,130 @@
+use circuit::{CircuitBuilder, CircuitStructure, Coef, Term};
+use config::{
+    BN254ConfigSha2, Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig,
+    PolynomialCommitmentType,
+};
+
+use super::{prove, test_assignment};
+use crate::{ProofEnvelope, ProofError, ProofHeader, Verifier, PROOF_MAGIC};
+
+type C = M31ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+// y = x0 * x1 + c
+fn test_circuit(c: u32) -> CircuitStructure<C> {
+    let mut builder = CircuitBuilder::<C>::new();
+    let x = builder.inputs(2);
+    let y = builder.sum(vec![
+        Term::Mul(x[0], x[1], Coef::one()),
+        Term::Const(Coef::Constant(F::from(c))),
+    ]);
+    builder.output(y);
+    builder.build().unwrap()
+}
+
+fn prove_envelope(config: &Config<C>, circuit: &CircuitStructure<C>) -> ProofEnvelope<C> {
+    let mut assignment = test_assignment(circuit, &[F::from(3), F::from(5)], &[]);
+    let (claimed_v, proof) = prove(config, circuit, &mut assignment);
+    ProofEnvelope::new(config, circuit, claimed_v, proof)
+}
+
+fn envelope_bytes(envelope: &ProofEnvelope<C>) -> Vec<u8> {
+    let mut bytes = vec![];
+    envelope.serialize_into(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn test_proof_envelope() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::default());
+    let circuit = test_circuit(7);
+    let envelope = prove_envelope(&config, &circuit);
+    let bytes = envelope_bytes(&envelope);
+    assert_eq!(bytes[..8], PROOF_MAGIC);
+
+    let read = ProofEnvelope::<C>::deserialize_from(&bytes[..]).unwrap();
+    assert_eq!(read.header, envelope.header);
+    assert_eq!(read.claimed_v, envelope.claimed_v);
+    assert_eq!(read.proof.bytes, envelope.proof.bytes);
+    read.check(&config, &circuit).unwrap();
+
+    let verifier = Verifier::new(&config);
+    assert!(verifier
+        .verify(&circuit, &[], &read.claimed_v, &read.proof)
+        .unwrap());
+}
+
+#[test]
+fn test_proof_envelope_mismatch() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::default());
+    let circuit = test_circuit(7);
+    let envelope = prove_envelope(&config, &circuit);
+
+    let mut other_config = config.clone();
+    other_config.gkr_scheme = GKRScheme::GkrSquare;
+    assert!(matches!(
+        envelope.check(&other_config, &circuit),
+        Err(ProofError::ConfigMismatch {
+            kind: "GKR scheme",
+            ..
+        })
+    ));
+    let mut other_config = config.clone();
+    other_config.polynomial_commitment_type = PolynomialCommitmentType::Orion;
+    assert!(matches!(
+        envelope.check(&other_config, &circuit),
+        Err(ProofError::ConfigMismatch {
+            kind: "polynomial commitment",
+            ..
+        })
+    ));
+    assert!(matches!(
+        envelope.check(&config, &test_circuit(8)),
+        Err(ProofError::CircuitMismatch)
+    ));
+
+    // the field is checked before reading the claimed value
+    let bytes = envelope_bytes(&envelope);
+    assert!(matches!(
+        ProofEnvelope::<BN254ConfigSha2>::deserialize_from(&bytes[..]),
+        Err(ProofError::ConfigMismatch { kind: "field", .. })
+    ));
+}
+
+#[test]
+fn test_proof_envelope_errors() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::default());
+    let envelope = prove_envelope(&config, &test_circuit(7));
+    let bytes = envelope_bytes(&envelope);
+
+    let mut bad = bytes.clone();
+    bad[0] ^= 1;
+    assert!(matches!(
+        ProofHeader::deserialize_from(&bad[..]),
+        Err(ProofError::BadMagic)
+    ));
+
+    let mut bad = bytes.clone();
+    bad[8] = 2;
+    assert!(matches!(
+        ProofHeader::deserialize_from(&bad[..]),
+        Err(ProofError::UnsupportedVersion(2))
+    ));
+
+    // the GKR scheme follows the field and hash identifiers
+    let mut bad = bytes.clone();
+    bad[18] = 100;
+    assert!(matches!(
+        ProofHeader::deserialize_from(&bad[..]),
+        Err(ProofError::UnknownId { id: 100, .. })
+    ));
+
+    assert!(matches!(
+        ProofEnvelope::<C>::deserialize_from(&bytes[..bytes.len() - 1]),
+        Err(ProofError::TruncatedBody { .. })
+    ));
+    assert!(matches!(
+        ProofEnvelope::<C>::deserialize_from(&bytes[..20]),
+        Err(ProofError::Serde(_))
+    ));
+}
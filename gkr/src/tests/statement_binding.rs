@@ -0,0 +1,49 @@
+use arith::Field;
+use circuit::CircuitStructure;
+use config::{Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig};
+use transcript::{Transcript, TranscriptInstance};
+
+use super::{prove, test_circuit, test_circuit_assignment};
+use crate::Verifier;
+
+type C = M31ExtConfigSha2;
+type F = <C as GKRConfig>::CircuitField;
+
+#[test]
+fn test_statement_binding() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::default());
+    let circuit = test_circuit();
+    let mut assignment = test_circuit_assignment(&circuit);
+    let (claimed_v, proof) = prove(&config, &circuit, &mut assignment);
+
+    // the same shape with another constant, and other public inputs
+    let mut other_circuit = circuit.clone();
+    let layer = other_circuit
+        .layers
+        .iter_mut()
+        .find(|layer| !layer.add.is_empty())
+        .unwrap();
+    layer.add[0].coef += F::one();
+    let mut other_input = assignment.public_input.clone();
+    other_input[0] += <C as GKRConfig>::SimdCircuitField::one();
+
+    let challenge = |circuit: &CircuitStructure<C>, public_input: &[_]| {
+        let mut transcript = TranscriptInstance::new();
+        circuit.absorb_statement(public_input, &mut transcript);
+        transcript.generate_challenge::<F>()
+    };
+    let expected = challenge(&circuit, &assignment.public_input);
+    assert_ne!(
+        challenge(&other_circuit, &assignment.public_input),
+        expected
+    );
+    assert_ne!(challenge(&circuit, &other_input), expected);
+
+    let verifier = Verifier::new(&config);
+    assert!(!verifier
+        .verify(&other_circuit, &assignment.public_input, &claimed_v, &proof)
+        .unwrap());
+    assert!(!verifier
+        .verify(&circuit, &other_input, &claimed_v, &proof)
+        .unwrap());
+}
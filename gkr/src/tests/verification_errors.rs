@@ -0,0 +1,109 @@
+use circuit::{CircuitAssignment, CircuitStructure};
+use config::{Config, GKRConfig, GKRScheme, M31ExtConfigSha2, MPIConfig, PolynomialCommitmentType};
+use transcript::{Proof, ProofReadError};
+
+use super::{prove, test_circuit, test_circuit_assignment};
+use crate::{PCSSetupError, VerificationError, Verifier};
+
+type C = M31ExtConfigSha2;
+
+fn prove_test_circuit(
+    config: &Config<C>,
+) -> (
+    CircuitStructure<C>,
+    CircuitAssignment<C>,
+    <C as GKRConfig>::ChallengeField,
+    Proof,
+) {
+    let circuit = test_circuit();
+    let mut assignment = test_circuit_assignment(&circuit);
+    let (claimed_v, proof) = prove(config, &circuit, &mut assignment);
+    (circuit, assignment, claimed_v, proof)
+}
+
+#[test]
+fn test_malformed_proof() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::default());
+    let (circuit, assignment, claimed_v, proof) = prove_test_circuit(&config);
+    let verifier = Verifier::new(&config);
+
+    // a proof that can not be read is an error, not a failed check
+    let mut long_proof = proof.clone();
+    long_proof.bytes.push(0);
+    assert!(matches!(
+        verifier.verify(&circuit, &assignment.public_input, &claimed_v, &long_proof),
+        Err(VerificationError::MalformedProof(
+            ProofReadError::TrailingBytes(1)
+        ))
+    ));
+    let mut short_proof = proof.clone();
+    short_proof.bytes.pop();
+    assert!(matches!(
+        verifier.verify(&circuit, &assignment.public_input, &claimed_v, &short_proof),
+        Err(VerificationError::MalformedProof(
+            ProofReadError::Truncated(_)
+        ))
+    ));
+}
+
+#[test]
+fn test_public_input_size_mismatch() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::default());
+    let (circuit, assignment, claimed_v, proof) = prove_test_circuit(&config);
+    let verifier = Verifier::new(&config);
+
+    assert!(matches!(
+        verifier.verify(&circuit, &[], &claimed_v, &proof),
+        Err(VerificationError::PublicInputSizeMismatch {
+            expected: 1,
+            found: 0
+        })
+    ));
+    let mut long_input = assignment.public_input.clone();
+    long_input.push(long_input[0]);
+    assert!(matches!(
+        verifier.verify(&circuit, &long_input, &claimed_v, &proof),
+        Err(VerificationError::PublicInputSizeMismatch {
+            expected: 1,
+            found: 2
+        })
+    ));
+
+    // with 2 processes, the verifier expects the public inputs of both
+    let verifier = Verifier::new(&Config::<C>::new(
+        GKRScheme::Vanilla,
+        MPIConfig::new_for_verifier(2),
+    ));
+    assert!(matches!(
+        verifier.verify(&circuit, &assignment.public_input, &claimed_v, &proof),
+        Err(VerificationError::PublicInputSizeMismatch {
+            expected: 2,
+            found: 1
+        })
+    ));
+}
+
+#[test]
+fn test_missing_pcs_params() {
+    let config = Config::<C>::new(GKRScheme::Vanilla, MPIConfig::default());
+    let (circuit, assignment, claimed_v, proof) = prove_test_circuit(&config);
+
+    // a verifier without the KZG SRS fails to set up, it does not panic
+    let mut kzg_config = config.clone();
+    kzg_config.polynomial_commitment_type = PolynomialCommitmentType::KZG;
+    let mut verifier = Verifier::new(&kzg_config);
+    assert!(matches!(
+        verifier.setup(&circuit),
+        Err(PCSSetupError::MissingSrsFile)
+    ));
+    assert!(matches!(
+        verifier.verify(&circuit, &assignment.public_input, &claimed_v, &proof),
+        Err(VerificationError::PCSSetup(PCSSetupError::MissingSrsFile))
+    ));
+
+    kzg_config.kzg_srs_file = Some("/nonexistent/expander-kzg-srs".to_string());
+    assert!(matches!(
+        Verifier::new(&kzg_config).verify(&circuit, &assignment.public_input, &claimed_v, &proof),
+        Err(VerificationError::PCSSetup(PCSSetupError::BadSrs(_)))
+    ));
+}
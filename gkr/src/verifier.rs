@@ -1,29 +1,48 @@
-use std::{io::Cursor, vec};
+use std::vec;
 
-use arith::Field;
+use arith::{Field, SimdField};
 use ark_std::{end_timer, start_timer};
-use circuit::{Circuit, CircuitLayer};
-use config::{Config, GKRConfig, PolynomialCommitmentType};
+use circuit::{CircuitLayer, CircuitStructure, LayerRndCoefs, RndCoefs};
+use config::{Config, GKRConfig, GKRScheme};
 use sumcheck::{GKRVerifierHelper, VerifierScratchPad};
-use transcript::{Proof, Transcript, TranscriptInstance};
+use thiserror::Error;
+use transcript::{
+    Proof, ProofReadError, ProofReadResult, ProofReader, Transcript, TranscriptInstance,
+};
 
 #[cfg(feature = "grinding")]
-use crate::grind;
-use crate::RawCommitment;
+use crate::{grinding_bits, grinding_leading_zeros, GRINDING_NONCE_SIZE};
+use crate::{
+    FRICommitment, KZGCommitment, MultiLinearPoly, OrionCommitment, PCSParams, PCSSetupError,
+    PCSSetupResult, PolynomialCommitment, RawCommitment,
+};
+
+#[derive(Debug, Error)]
+pub enum VerificationError {
+    #[error("malformed proof: {0}")]
+    MalformedProof(#[from] ProofReadError),
+
+    #[error("{found} public inputs, expected {expected}")]
+    PublicInputSizeMismatch { expected: usize, found: usize },
+
+    #[error("can not set up the commitment: {0}")]
+    PCSSetup(#[from] PCSSetupError),
+}
+
+pub type VerificationResult<T> = Result<T, VerificationError>;
 
 #[inline(always)]
 fn verify_sumcheck_step<C: GKRConfig>(
-    proof: &mut Proof,
+    proof: &mut ProofReader,
     degree: usize,
     transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
     claimed_sum: &mut C::ChallengeField,
     randomness_vec: &mut Vec<C::ChallengeField>,
     sp: &VerifierScratchPad<C>,
-) -> bool {
+) -> ProofReadResult<bool> {
     let mut ps = vec![];
-    for i in 0..(degree + 1) {
-        ps.push(proof.get_next_and_step());
-        transcript.append_field_element::<C::ChallengeField>(&ps[i]);
+    for _ in 0..(degree + 1) {
+        ps.push(transcript.receive_field_element::<C::ChallengeField>(proof)?);
     }
 
     let r = transcript.generate_challenge::<C::ChallengeField>();
@@ -37,7 +56,7 @@ fn verify_sumcheck_step<C: GKRConfig>(
         *claimed_sum = GKRVerifierHelper::degree_3_eval(&ps, r, sp);
     }
 
-    verified
+    Ok(verified)
 }
 
 // todo: FIXME
@@ -47,6 +66,7 @@ fn verify_sumcheck_step<C: GKRConfig>(
 fn sumcheck_verify_gkr_layer<C: GKRConfig>(
     config: &Config<C>,
     layer: &CircuitLayer<C>,
+    rnd_coefs: &LayerRndCoefs<C>,
     public_input: &[C::SimdCircuitField],
     rz0: &[C::ChallengeField],
     rz1: &Option<Vec<C::ChallengeField>>,
@@ -56,10 +76,10 @@ fn sumcheck_verify_gkr_layer<C: GKRConfig>(
     claimed_v1: Option<C::ChallengeField>,
     alpha: C::ChallengeField,
     beta: Option<C::ChallengeField>,
-    proof: &mut Proof,
+    proof: &mut ProofReader,
     transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
     sp: &mut VerifierScratchPad<C>,
-) -> (
+) -> ProofReadResult<(
     bool,
     Vec<C::ChallengeField>,
     Option<Vec<C::ChallengeField>>,
@@ -67,7 +87,7 @@ fn sumcheck_verify_gkr_layer<C: GKRConfig>(
     Vec<C::ChallengeField>,
     C::ChallengeField,
     Option<C::ChallengeField>,
-) {
+)> {
     debug_assert_eq!(rz1.is_none(), claimed_v1.is_none());
     debug_assert_eq!(rz1.is_none(), beta.is_none());
 
@@ -79,7 +99,7 @@ fn sumcheck_verify_gkr_layer<C: GKRConfig>(
     if claimed_v1.is_some() && beta.is_some() {
         sum += claimed_v1.unwrap() * beta.unwrap();
     }
-    sum -= GKRVerifierHelper::eval_cst(&layer.const_, public_input, sp);
+    sum -= GKRVerifierHelper::eval_cst(&layer.const_, rnd_coefs, public_input, sp);
 
     let mut rx = vec![];
     let mut ry = None;
@@ -88,57 +108,189 @@ fn sumcheck_verify_gkr_layer<C: GKRConfig>(
     let mut verified = true;
 
     for _i_var in 0..var_num {
-        verified &= verify_sumcheck_step::<C>(proof, 2, transcript, &mut sum, &mut rx, sp);
+        verified &= verify_sumcheck_step::<C>(proof, 2, transcript, &mut sum, &mut rx, sp)?;
         // println!("x {} var, verified? {}", _i_var, verified);
     }
     GKRVerifierHelper::set_rx(&rx, sp);
 
     for _i_var in 0..simd_var_num {
-        verified &= verify_sumcheck_step::<C>(proof, 3, transcript, &mut sum, &mut r_simd_xy, sp);
+        verified &= verify_sumcheck_step::<C>(proof, 3, transcript, &mut sum, &mut r_simd_xy, sp)?;
         // println!("{} simd var, verified? {}", _i_var, verified);
     }
     GKRVerifierHelper::set_r_simd_xy(&r_simd_xy, sp);
 
     for _i_var in 0..config.mpi_config.world_size().trailing_zeros() {
-        verified &= verify_sumcheck_step::<C>(proof, 3, transcript, &mut sum, &mut r_mpi_xy, sp);
+        verified &= verify_sumcheck_step::<C>(proof, 3, transcript, &mut sum, &mut r_mpi_xy, sp)?;
         // println!("{} mpi var, verified? {}", _i_var, verified);
     }
     GKRVerifierHelper::set_r_mpi_xy(&r_mpi_xy, sp);
 
-    let vx_claim = proof.get_next_and_step::<C::ChallengeField>();
-    sum -= vx_claim * GKRVerifierHelper::eval_add(&layer.add, sp);
-    transcript.append_field_element::<C::ChallengeField>(&vx_claim);
+    let vx_claim = transcript.receive_field_element::<C::ChallengeField>(proof)?;
+    sum -= vx_claim * GKRVerifierHelper::eval_add(&layer.add, rnd_coefs, sp);
 
     let vy_claim = if !layer.structure_info.max_degree_one {
         ry = Some(vec![]);
         for _i_var in 0..var_num {
-            verified &=
-                verify_sumcheck_step::<C>(proof, 2, transcript, &mut sum, ry.as_mut().unwrap(), sp);
+            verified &= verify_sumcheck_step::<C>(
+                proof,
+                2,
+                transcript,
+                &mut sum,
+                ry.as_mut().unwrap(),
+                sp,
+            )?;
             // println!("y {} var, verified? {}", _i_var, verified);
         }
         GKRVerifierHelper::set_ry(ry.as_ref().unwrap(), sp);
-        let vy_claim = proof.get_next_and_step::<C::ChallengeField>();
-        transcript.append_field_element::<C::ChallengeField>(&vy_claim);
-        verified &= sum == vx_claim * vy_claim * GKRVerifierHelper::eval_mul(&layer.mul, sp);
+        let vy_claim = transcript.receive_field_element::<C::ChallengeField>(proof)?;
+        verified &=
+            sum == vx_claim * vy_claim * GKRVerifierHelper::eval_mul(&layer.mul, rnd_coefs, sp);
         Some(vy_claim)
     } else {
         verified &= sum == C::ChallengeField::ZERO;
         None
     };
 
-    (verified, rx, ry, r_simd_xy, r_mpi_xy, vx_claim, vy_claim)
+    Ok((verified, rx, ry, r_simd_xy, r_mpi_xy, vx_claim, vy_claim))
+}
+
+// GKR^2 round polynomials are sent lane-wise, i.e., as simd fields
+#[inline(always)]
+fn verify_gkr_square_sumcheck_step<C: GKRConfig>(
+    proof: &mut ProofReader,
+    degree: usize,
+    transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    claimed_sum: &mut C::Field,
+    randomness_vec: &mut Vec<C::ChallengeField>,
+    sp: &VerifierScratchPad<C>,
+) -> ProofReadResult<bool> {
+    let mut ps = vec![];
+    for _ in 0..(degree + 1) {
+        ps.push(transcript.receive_field_element::<C::Field>(proof)?);
+    }
+
+    let r = transcript.generate_challenge::<C::ChallengeField>();
+    randomness_vec.push(r);
+
+    let verified = (ps[0] + ps[1]) == *claimed_sum;
+    *claimed_sum = GKRVerifierHelper::degree_6_eval(&ps, r, sp);
+
+    Ok(verified)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn sumcheck_verify_gkr_square_layer<C: GKRConfig>(
+    layer: &CircuitLayer<C>,
+    rnd_coefs: &LayerRndCoefs<C>,
+    public_input: &[C::SimdCircuitField],
+    rz0: &[C::ChallengeField],
+    claimed_v: C::Field,
+    proof: &mut ProofReader,
+    transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    sp: &mut VerifierScratchPad<C>,
+) -> ProofReadResult<(bool, Vec<C::ChallengeField>, C::Field)> {
+    const D: usize = 7;
+    GKRVerifierHelper::prepare_square_layer(rz0, sp);
+
+    let mut sum =
+        claimed_v - GKRVerifierHelper::eval_cst_simd(&layer.const_, rnd_coefs, public_input, sp);
+
+    let mut rx = vec![];
+    let mut verified = true;
+
+    for _i_var in 0..layer.input_var_num {
+        verified &=
+            verify_gkr_square_sumcheck_step::<C>(proof, D - 1, transcript, &mut sum, &mut rx, sp)?;
+    }
+    GKRVerifierHelper::set_rx(&rx, sp);
+
+    let vx_claim = transcript.receive_field_element::<C::Field>(proof)?;
+
+    let vx_claim_pow5 = vx_claim.square().square() * vx_claim;
+    let pow5_coef = GKRVerifierHelper::eval_pow5(&layer.uni, rnd_coefs, sp);
+    let pow1_coef = GKRVerifierHelper::eval_pow1(&layer.uni, rnd_coefs, sp);
+    verified &= sum
+        == C::challenge_mul_field(&pow5_coef, &vx_claim_pow5)
+            + C::challenge_mul_field(&pow1_coef, &vx_claim);
+
+    Ok((verified, rx, vx_claim))
+}
+
+/// Verify a GKR^2 proof.
+///
+/// Returns the verification result, together with the opening point `(rx, r_simd)`
+/// and the claimed evaluation of the input layer at that point,
+/// or an error if the proof is malformed.
+#[allow(clippy::type_complexity)]
+pub fn gkr_square_verify<C: GKRConfig>(
+    config: &Config<C>,
+    circuit: &CircuitStructure<C>,
+    rnd_coefs: &RndCoefs<C>,
+    public_input: &[C::SimdCircuitField],
+    claimed_v: &C::ChallengeField,
+    transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    proof: &mut ProofReader,
+) -> ProofReadResult<(
+    bool,
+    Vec<C::ChallengeField>,
+    Vec<C::ChallengeField>,
+    C::ChallengeField,
+)> {
+    let timer = start_timer!(|| "gkr^2 verify");
+    let mut sp = VerifierScratchPad::<C>::new(config, circuit);
+
+    let layer_num = circuit.layers.len();
+    let mut rz0 = vec![];
+    for _ in 0..circuit.layers.last().unwrap().output_var_num {
+        rz0.push(transcript.generate_challenge::<C::ChallengeField>());
+    }
+
+    let mut claimed_v_simd = transcript.receive_field_element::<C::Field>(proof)?;
+
+    let r_simd = (0..C::get_field_pack_size().trailing_zeros())
+        .map(|_| transcript.generate_challenge::<C::ChallengeField>())
+        .collect::<Vec<_>>();
+    let mut scratch = vec![C::ChallengeField::default(); C::get_field_pack_size()];
+    let mut verified =
+        MultiLinearPoly::eval_generic(&claimed_v_simd.unpack(), &r_simd, &mut scratch)
+            == *claimed_v;
+
+    for i in (0..layer_num).rev() {
+        let cur_verified;
+        (cur_verified, rz0, claimed_v_simd) = sumcheck_verify_gkr_square_layer(
+            &circuit.layers[i],
+            &rnd_coefs.layer(i),
+            public_input,
+            &rz0,
+            claimed_v_simd,
+            proof,
+            transcript,
+            &mut sp,
+        )?;
+        verified &= cur_verified;
+        log::trace!("Layer {} verified: {}", i, cur_verified);
+    }
+
+    let r_simd = (0..C::get_field_pack_size().trailing_zeros())
+        .map(|_| transcript.generate_challenge::<C::ChallengeField>())
+        .collect::<Vec<_>>();
+    let claimed_v0 = MultiLinearPoly::eval_generic(&claimed_v_simd.unpack(), &r_simd, &mut scratch);
+
+    end_timer!(timer);
+    Ok((verified, rz0, r_simd, claimed_v0))
 }
 
 // todo: FIXME
 #[allow(clippy::type_complexity)]
 pub fn gkr_verify<C: GKRConfig>(
     config: &Config<C>,
-    circuit: &Circuit<C>,
+    circuit: &CircuitStructure<C>,
+    rnd_coefs: &RndCoefs<C>,
     public_input: &[C::SimdCircuitField],
     claimed_v: &C::ChallengeField,
     transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
-    proof: &mut Proof,
-) -> (
+    proof: &mut ProofReader,
+) -> ProofReadResult<(
     bool,
     Vec<C::ChallengeField>,
     Option<Vec<C::ChallengeField>>,
@@ -146,7 +298,7 @@ pub fn gkr_verify<C: GKRConfig>(
     Vec<C::ChallengeField>,
     C::ChallengeField,
     Option<C::ChallengeField>,
-) {
+)> {
     let timer = start_timer!(|| "gkr verify");
     let mut sp = VerifierScratchPad::<C>::new(config, circuit);
 
@@ -187,6 +339,7 @@ pub fn gkr_verify<C: GKRConfig>(
         ) = sumcheck_verify_gkr_layer(
             config,
             &circuit.layers[i],
+            &rnd_coefs.layer(i),
             public_input,
             &rz0,
             &rz1,
@@ -199,7 +352,7 @@ pub fn gkr_verify<C: GKRConfig>(
             proof,
             transcript,
             &mut sp,
-        );
+        )?;
         verified &= cur_verified;
         alpha = transcript.generate_challenge::<C::ChallengeField>();
         beta = if rz1.is_some() {
@@ -217,99 +370,212 @@ pub fn gkr_verify<C: GKRConfig>(
         );
     }
     end_timer!(timer);
-    (verified, rz0, rz1, r_simd, r_mpi, claimed_v0, claimed_v1)
+    Ok((verified, rz0, rz1, r_simd, r_mpi, claimed_v0, claimed_v1))
 }
 
 pub struct Verifier<C: GKRConfig> {
     config: Config<C>,
+    /// The parameters of the commitment and their number of variables, see `Verifier::setup`.
+    pcs_params: Option<(usize, PCSParams<C>)>,
 }
 
 impl<C: GKRConfig> Default for Verifier<C> {
     fn default() -> Self {
         Self {
             config: Config::<C>::default(),
+            pcs_params: None,
         }
     }
 }
 
 impl<C: GKRConfig> Verifier<C> {
     pub fn new(config: &Config<C>) -> Self {
+        assert!(
+            config.gkr_scheme != GKRScheme::GkrSquare || config.mpi_config.world_size() == 1,
+            "GKR^2 does not support MPI"
+        );
         Verifier {
             config: config.clone(),
+            pcs_params: None,
         }
     }
 
+    fn pcs_num_vars(&self, circuit: &CircuitStructure<C>) -> usize {
+        circuit.log_input_size()
+            + C::get_field_pack_size().trailing_zeros() as usize
+            + self.config.mpi_config.world_size().trailing_zeros() as usize
+    }
+
+    /// Set up the commitment for the circuit once, e.g., load the KZG SRS, instead of on
+    /// every call to `verify`.
+    pub fn setup(&mut self, circuit: &CircuitStructure<C>) -> PCSSetupResult<()> {
+        let num_vars = self.pcs_num_vars(circuit);
+        self.pcs_params = Some((num_vars, PCSParams::setup(&self.config, num_vars)?));
+        Ok(())
+    }
+
+    /// Verify a proof, which may come from an untrusted source.
+    ///
+    /// Returns `Ok(false)` if a check fails, and an error if the proof can not be read
+    /// or has bytes left over. The public inputs are the ones of all the MPI processes,
+    /// in the order of their ranks. The commitment is set up for the circuit unless
+    /// `Verifier::setup` already did.
     pub fn verify(
         &self,
-        circuit: &mut Circuit<C>,
+        circuit: &CircuitStructure<C>,
         public_input: &[C::SimdCircuitField],
         claimed_v: &C::ChallengeField,
         proof: &Proof,
-    ) -> bool {
+    ) -> VerificationResult<bool> {
+        let expected = circuit.num_public_inputs * self.config.mpi_config.world_size();
+        if public_input.len() != expected {
+            return Err(VerificationError::PublicInputSizeMismatch {
+                expected,
+                found: public_input.len(),
+            });
+        }
+
         let timer = start_timer!(|| "verify");
 
-        let poly_size =
-            circuit.layers.first().unwrap().input_vals.len() * self.config.mpi_config.world_size();
-        let mut cursor = Cursor::new(&proof.bytes);
+        let num_vars = self.pcs_num_vars(circuit);
+        let fresh_params;
+        let pcs_params = match &self.pcs_params {
+            Some((params_num_vars, params)) if *params_num_vars == num_vars => params,
+            _ => {
+                fresh_params = PCSParams::setup(&self.config, num_vars)?;
+                &fresh_params
+            }
+        };
+        let verified = match pcs_params {
+            PCSParams::Raw => self.verify_with_pcs::<RawCommitment<C>>(
+                &(),
+                num_vars,
+                circuit,
+                public_input,
+                claimed_v,
+                proof,
+            ),
+            PCSParams::KZG(srs) => self.verify_with_pcs::<KZGCommitment>(
+                srs,
+                num_vars,
+                circuit,
+                public_input,
+                claimed_v,
+                proof,
+            ),
+            PCSParams::Orion(params) => self.verify_with_pcs::<OrionCommitment<C>>(
+                params,
+                num_vars,
+                circuit,
+                public_input,
+                claimed_v,
+                proof,
+            ),
+            PCSParams::FRI(params) => self.verify_with_pcs::<FRICommitment<C>>(
+                params,
+                num_vars,
+                circuit,
+                public_input,
+                claimed_v,
+                proof,
+            ),
+        };
+
+        end_timer!(timer);
 
-        let commitment = RawCommitment::<C>::deserialize_from(&mut cursor, poly_size);
+        verified
+    }
 
+    fn verify_with_pcs<PCS: PolynomialCommitment<C>>(
+        &self,
+        pcs_params: &PCS::Params,
+        num_vars: usize,
+        circuit: &CircuitStructure<C>,
+        public_input: &[C::SimdCircuitField],
+        claimed_v: &C::ChallengeField,
+        proof: &Proof,
+    ) -> VerificationResult<bool> {
+        let mut reader = ProofReader::new(proof);
         let mut transcript = TranscriptInstance::new();
-        transcript.append_u8_slice(&proof.bytes[..commitment.size()]);
+        circuit.absorb_statement(public_input, &mut transcript);
+        transcript.absorb_label("commitment");
+        let commitment =
+            transcript.receive_with(|r| PCS::deserialize_from(r, num_vars), &mut reader)?;
 
         if self.config.mpi_config.world_size() > 1 {
             transcript.hash_to_digest(); // In prover, we call hash_to_digest before sync up the transcript state
         }
 
-        // ZZ: shall we use probabilistic grinding so the verifier can avoid this cost?
-        // (and also be recursion friendly)
         #[cfg(feature = "grinding")]
-        grind::<C>(&mut transcript, &self.config);
-
-        circuit.fill_rnd_coefs(&mut transcript);
-
-        let mut proof = proof.clone(); // FIXME: consider separating pointers to make proof always immutable?
-
-        #[cfg(feature = "grinding")]
-        proof.step(commitment.size() + 32);
-        #[cfg(not(feature = "grinding"))]
-        proof.step(commitment.size());
+        {
+            transcript.absorb_label("grinding");
+            transcript.hash_to_digest();
+            let nonce_bytes = transcript.receive_from_prover(GRINDING_NONCE_SIZE, &mut reader)?;
+            let nonce = u64::from_le_bytes(nonce_bytes.try_into().unwrap());
+            if grinding_leading_zeros::<C>(&transcript.digest, nonce)
+                < grinding_bits(&self.config, circuit)
+            {
+                log::info!("Grinding verification failed");
+                return Ok(false);
+            }
+        }
 
-        let (mut verified, rz0, rz1, r_simd, r_mpi, claimed_v0, claimed_v1) = gkr_verify(
-            &self.config,
-            circuit,
-            public_input,
-            claimed_v,
-            &mut transcript,
-            &mut proof,
-        );
+        let rnd_coefs = circuit.draw_rnd_coefs(&mut transcript);
+
+        transcript.absorb_label("gkr");
+        let (mut verified, rz0, rz1, r_simd, r_mpi, claimed_v0, claimed_v1) =
+            match self.config.gkr_scheme {
+                GKRScheme::Vanilla => gkr_verify(
+                    &self.config,
+                    circuit,
+                    &rnd_coefs,
+                    public_input,
+                    claimed_v,
+                    &mut transcript,
+                    &mut reader,
+                )?,
+                GKRScheme::GkrSquare => {
+                    let (verified, rz0, r_simd, claimed_v0) = gkr_square_verify(
+                        &self.config,
+                        circuit,
+                        &rnd_coefs,
+                        public_input,
+                        claimed_v,
+                        &mut transcript,
+                        &mut reader,
+                    )?;
+                    (verified, rz0, None, r_simd, vec![], claimed_v0, None)
+                }
+            };
 
         log::info!("GKR verification: {}", verified);
 
-        match self.config.polynomial_commitment_type {
-            PolynomialCommitmentType::Raw => {
-                // for Raw, no need to load from proof
-                log::trace!("rz0.size() = {}", rz0.len());
-                log::trace!("Poly_vals.size() = {}", commitment.poly_vals.len());
-
-                let v1 = commitment.mpi_verify(&rz0, &r_simd, &r_mpi, claimed_v0);
-                verified &= v1;
-
-                if rz1.is_some() {
-                    let v2 = commitment.mpi_verify(
-                        rz1.as_ref().unwrap(),
-                        &r_simd,
-                        &r_mpi,
-                        claimed_v1.unwrap(),
-                    );
-                    verified &= v2;
-                }
-            }
-            _ => todo!(),
+        log::trace!("rz0.size() = {}", rz0.len());
+        transcript.absorb_label("opening");
+        verified &= commitment.verify(
+            pcs_params,
+            &rz0,
+            &r_simd,
+            &r_mpi,
+            claimed_v0,
+            &mut transcript,
+            &mut reader,
+        )?;
+
+        if let Some(rz1) = rz1 {
+            transcript.absorb_label("opening");
+            verified &= commitment.verify(
+                pcs_params,
+                &rz1,
+                &r_simd,
+                &r_mpi,
+                claimed_v1.unwrap(),
+                &mut transcript,
+                &mut reader,
+            )?;
         }
 
-        end_timer!(timer);
-
-        verified
+        reader.finish()?;
+        Ok(verified)
     }
 }
@@ -1,6 +1,6 @@
-use circuit::CircuitLayer;
+use circuit::{CircuitLayer, LayerRndCoefs};
 use config::{GKRConfig, MPIConfig};
-use transcript::{Transcript, TranscriptInstance};
+use transcript::{Proof, Transcript, TranscriptInstance};
 
 use crate::{
     sumcheck_helper::SumcheckGkrHelper, sumcheck_square_helper::SumcheckGkrSquareHelper,
@@ -11,11 +11,12 @@ use crate::{
 fn transcript_io<C: GKRConfig>(
     ps: &[C::ChallengeField],
     transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    proof: &mut Proof,
     mpi_config: &MPIConfig,
 ) -> C::ChallengeField {
     assert!(ps.len() == 3 || ps.len() == 4); // 3 for x, y; 4 for simd var
     for p in ps {
-        transcript.append_field_element::<C::ChallengeField>(p);
+        transcript.send_field_element::<C::ChallengeField>(p, proof);
     }
     let mut r = transcript.generate_challenge::<C::ChallengeField>();
     mpi_config.root_broadcast(&mut r);
@@ -27,6 +28,8 @@ fn transcript_io<C: GKRConfig>(
 #[allow(clippy::type_complexity)]
 pub fn sumcheck_prove_gkr_layer<C: GKRConfig>(
     layer: &CircuitLayer<C>,
+    rnd_coefs: LayerRndCoefs<'_, C>,
+    input_vals: &[C::SimdCircuitField],
     rz0: &[C::ChallengeField],
     rz1: &Option<Vec<C::ChallengeField>>,
     r_simd: &[C::ChallengeField],
@@ -34,6 +37,7 @@ pub fn sumcheck_prove_gkr_layer<C: GKRConfig>(
     alpha: &C::ChallengeField,
     beta: &Option<C::ChallengeField>,
     transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    proof: &mut Proof,
     sp: &mut GkrScratchpad<C>,
     mpi_config: &MPIConfig,
 ) -> (
@@ -42,8 +46,9 @@ pub fn sumcheck_prove_gkr_layer<C: GKRConfig>(
     Vec<C::ChallengeField>,
     Vec<C::ChallengeField>,
 ) {
-    let mut helper =
-        SumcheckGkrHelper::new(layer, rz0, rz1, r_simd, r_mpi, alpha, beta, sp, mpi_config);
+    let mut helper = SumcheckGkrHelper::new(
+        layer, rnd_coefs, input_vals, rz0, rz1, r_simd, r_mpi, alpha, beta, sp, mpi_config,
+    );
 
     helper.prepare_simd();
     helper.prepare_mpi();
@@ -51,36 +56,36 @@ pub fn sumcheck_prove_gkr_layer<C: GKRConfig>(
 
     for i_var in 0..helper.input_var_num {
         let evals = helper.poly_evals_at_rx(i_var, 2);
-        let r = transcript_io::<C>(&evals, transcript, mpi_config);
+        let r = transcript_io::<C>(&evals, transcript, proof, mpi_config);
         helper.receive_rx(i_var, r);
     }
 
     helper.prepare_simd_var_vals();
     for i_var in 0..helper.simd_var_num {
         let evals = helper.poly_evals_at_r_simd_var(i_var, 3);
-        let r = transcript_io::<C>(&evals, transcript, mpi_config);
+        let r = transcript_io::<C>(&evals, transcript, proof, mpi_config);
         helper.receive_r_simd_var(i_var, r);
     }
 
     helper.prepare_mpi_var_vals();
     for i_var in 0..mpi_config.world_size().trailing_zeros() as usize {
         let evals = helper.poly_evals_at_r_mpi_var(i_var, 3);
-        let r = transcript_io::<C>(&evals, transcript, mpi_config);
+        let r = transcript_io::<C>(&evals, transcript, proof, mpi_config);
         helper.receive_r_mpi_var(i_var, r);
     }
 
     let vx_claim = helper.vx_claim();
-    transcript.append_field_element::<C::ChallengeField>(&vx_claim);
+    transcript.send_field_element::<C::ChallengeField>(&vx_claim, proof);
 
     if !layer.structure_info.max_degree_one {
         helper.prepare_y_vals();
         for i_var in 0..helper.input_var_num {
             let evals = helper.poly_evals_at_ry(i_var, 2);
-            let r = transcript_io::<C>(&evals, transcript, mpi_config);
+            let r = transcript_io::<C>(&evals, transcript, proof, mpi_config);
             helper.receive_ry(i_var, r);
         }
         let vy_claim = helper.vy_claim();
-        transcript.append_field_element::<C::ChallengeField>(&vy_claim);
+        transcript.send_field_element::<C::ChallengeField>(&vy_claim, proof);
     }
 
     let rx = helper.rx;
@@ -101,12 +106,15 @@ pub fn sumcheck_prove_gkr_layer<C: GKRConfig>(
 #[allow(clippy::needless_range_loop)] // todo: remove
 pub fn sumcheck_prove_gkr_square_layer<C: GKRConfig>(
     layer: &CircuitLayer<C>,
+    rnd_coefs: LayerRndCoefs<'_, C>,
+    input_vals: &[C::SimdCircuitField],
     rz0: &[C::ChallengeField],
     transcript: &mut TranscriptInstance<C::FiatShamirHashType>,
+    proof: &mut Proof,
     sp: &mut GkrScratchpad<C>,
 ) -> Vec<C::ChallengeField> {
     const D: usize = 7;
-    let mut helper = SumcheckGkrSquareHelper::new(layer, rz0, sp);
+    let mut helper = SumcheckGkrSquareHelper::new(layer, rnd_coefs, input_vals, rz0, sp);
 
     for i_var in 0..layer.input_var_num {
         if i_var == 0 {
@@ -115,7 +123,7 @@ pub fn sumcheck_prove_gkr_square_layer<C: GKRConfig>(
         let evals: [C::Field; D] = helper.poly_evals_at(i_var);
 
         for deg in 0..D {
-            transcript.append_field_element::<C::Field>(&evals[deg]);
+            transcript.send_field_element::<C::Field>(&evals[deg], proof);
         }
 
         let r = transcript.generate_challenge::<C::ChallengeField>();
@@ -123,14 +131,10 @@ pub fn sumcheck_prove_gkr_square_layer<C: GKRConfig>(
         log::trace!("i_var={} evals: {:?} r: {:?}", i_var, evals, r);
 
         helper.receive_challenge(i_var, r);
-        if i_var == layer.input_var_num - 1 {
-            log::trace!("vx claim: {:?}", helper.vx_claim());
-            transcript.append_field_element::<C::Field>(&helper.vx_claim());
-        }
     }
 
     log::trace!("claimed vx = {:?}", helper.vx_claim());
-    transcript.append_field_element::<C::Field>(&helper.vx_claim());
+    transcript.send_field_element::<C::Field>(&helper.vx_claim(), proof);
 
     helper.rx
 }
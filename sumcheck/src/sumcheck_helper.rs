@@ -1,5 +1,5 @@
 use arith::{ExtensionField, Field, SimdField};
-use circuit::CircuitLayer;
+use circuit::{CircuitLayer, GateKind, LayerRndCoefs};
 use config::{FieldType, GKRConfig, MPIConfig};
 
 use crate::GkrScratchpad;
@@ -318,6 +318,8 @@ pub(crate) struct SumcheckGkrHelper<'a, C: GKRConfig> {
     pub(crate) r_mpi_var: Vec<C::ChallengeField>,
 
     layer: &'a CircuitLayer<C>,
+    rnd_coefs: LayerRndCoefs<'a, C>,
+    input_vals: &'a [C::SimdCircuitField],
     sp: &'a mut GkrScratchpad<C>,
     rz0: &'a [C::ChallengeField],
     rz1: &'a Option<Vec<C::ChallengeField>>,
@@ -345,7 +347,7 @@ impl<'a, C: GKRConfig> SumcheckGkrHelper<'a, C> {
             r,
             &mut self.sp.v_evals,
             &mut self.sp.hg_evals,
-            &self.layer.input_vals,
+            self.input_vals,
             &mut self.sp.gate_exists_5,
         );
     }
@@ -356,6 +358,8 @@ impl<'a, C: GKRConfig> SumcheckGkrHelper<'a, C> {
 impl<'a, C: GKRConfig> SumcheckGkrHelper<'a, C> {
     pub(crate) fn new(
         layer: &'a CircuitLayer<C>,
+        rnd_coefs: LayerRndCoefs<'a, C>,
+        input_vals: &'a [C::SimdCircuitField],
         rz0: &'a [C::ChallengeField],
         rz1: &'a Option<Vec<C::ChallengeField>>,
         r_simd: &'a [C::ChallengeField],
@@ -373,6 +377,8 @@ impl<'a, C: GKRConfig> SumcheckGkrHelper<'a, C> {
             r_mpi_var: vec![],
 
             layer,
+            rnd_coefs,
+            input_vals,
             sp,
             rz0,
             rz1,
@@ -408,7 +414,7 @@ impl<'a, C: GKRConfig> SumcheckGkrHelper<'a, C> {
             degree,
             &mut self.sp.v_evals,
             &mut self.sp.hg_evals,
-            &self.layer.input_vals,
+            self.input_vals,
             &self.sp.gate_exists_5,
         );
 
@@ -552,7 +558,7 @@ impl<'a, C: GKRConfig> SumcheckGkrHelper<'a, C> {
     pub(crate) fn prepare_x_vals(&mut self) {
         let mul = &self.layer.mul;
         let add = &self.layer.add;
-        let vals = &self.layer.input_vals;
+        let vals = self.input_vals;
         let eq_evals_at_rz0 = &mut self.sp.eq_evals_at_rz0;
         let eq_evals_at_rz1 = &mut self.sp.eq_evals_at_rz1;
         let gate_exists = &mut self.sp.gate_exists_5;
@@ -589,17 +595,19 @@ impl<'a, C: GKRConfig> SumcheckGkrHelper<'a, C> {
             }
         }
 
-        for g in mul.iter() {
-            let r = C::challenge_mul_circuit_field(&eq_evals_at_rz0[g.o_id], &g.coef);
+        for (index, g) in mul.iter().enumerate() {
+            let coef = self.rnd_coefs.coef(GateKind::Mul, index, g);
+            let r = C::challenge_mul_circuit_field(&eq_evals_at_rz0[g.o_id], &coef);
             hg_vals[g.i_ids[0]] += C::simd_circuit_field_mul_challenge_field(&vals[g.i_ids[1]], &r);
 
             gate_exists[g.i_ids[0]] = true;
         }
 
-        for g in add.iter() {
+        for (index, g) in add.iter().enumerate() {
+            let coef = self.rnd_coefs.coef(GateKind::Add, index, g);
             hg_vals[g.i_ids[0]] += C::Field::from(C::challenge_mul_circuit_field(
                 &eq_evals_at_rz0[g.o_id],
-                &g.coef,
+                &coef,
             ));
             gate_exists[g.i_ids[0]] = true;
         }
@@ -669,10 +677,11 @@ impl<'a, C: GKRConfig> SumcheckGkrHelper<'a, C> {
         );
 
         // TODO-OPTIMIZATION: hg_vals does not have to be simd here
-        for g in mul.iter() {
+        for (index, g) in mul.iter().enumerate() {
+            let coef = self.rnd_coefs.coef(GateKind::Mul, index, g);
             hg_vals[g.i_ids[1]] += C::Field::from(C::challenge_mul_circuit_field(
                 &(eq_evals_at_rz0[g.o_id] * eq_evals_at_rx[g.i_ids[0]]),
-                &g.coef,
+                &coef,
             ));
             gate_exists[g.i_ids[1]] = true;
         }
@@ -1,5 +1,5 @@
 use arith::{Field, SimdField};
-use circuit::CircuitLayer;
+use circuit::{CircuitLayer, GateKind, LayerRndCoefs, POW1_GATE_TYPE, POW5_GATE_TYPE};
 use config::GKRConfig;
 
 use crate::{sumcheck_helper::eq_eval_at, GkrScratchpad};
@@ -143,12 +143,14 @@ impl<const D: usize> SumcheckMultiSquareHelper<D> {
                 let mut hg_v = [C::ChallengeField::zero(); 3];
                 f_v[0] = src_v[i * 2];
                 f_v[1] = src_v[i * 2 + 1];
+                f_v[2] = f_v[1] + f_v[1] - f_v[0];
                 hg_v[0] = bk_hg_1[i * 2];
                 hg_v[1] = bk_hg_1[i * 2 + 1];
+                hg_v[2] = hg_v[1] + hg_v[1] - hg_v[0];
                 p_add[0] += C::challenge_mul_field(&hg_v[0], &f_v[0]);
                 p_add[1] += C::challenge_mul_field(&hg_v[1], &f_v[1]);
+                p_add[2] += C::challenge_mul_field(&hg_v[2], &f_v[2]);
             }
-            p_add[2] = p_add[1] + p_add[1] - p_add[0] + C::Field::from(2);
             // interpolate p_add into 7 points
             Self::interpolate_3::<C>(&p_add, &mut p);
             p
@@ -226,6 +228,8 @@ pub(crate) struct SumcheckGkrSquareHelper<'a, C: GKRConfig, const D: usize> {
     pub(crate) rx: Vec<C::ChallengeField>,
 
     layer: &'a CircuitLayer<C>,
+    rnd_coefs: LayerRndCoefs<'a, C>,
+    input_vals: &'a [C::SimdCircuitField],
     sp: &'a mut GkrScratchpad<C>,
     rz0: &'a [C::ChallengeField],
 
@@ -238,6 +242,8 @@ pub(crate) struct SumcheckGkrSquareHelper<'a, C: GKRConfig, const D: usize> {
 impl<'a, C: GKRConfig, const D: usize> SumcheckGkrSquareHelper<'a, C, D> {
     pub(crate) fn new(
         layer: &'a CircuitLayer<C>,
+        rnd_coefs: LayerRndCoefs<'a, C>,
+        input_vals: &'a [C::SimdCircuitField],
         rz0: &'a [C::ChallengeField],
         sp: &'a mut GkrScratchpad<C>,
     ) -> Self {
@@ -245,6 +251,8 @@ impl<'a, C: GKRConfig, const D: usize> SumcheckGkrSquareHelper<'a, C, D> {
             rx: vec![],
 
             layer,
+            rnd_coefs,
+            input_vals,
             sp,
             rz0,
 
@@ -262,7 +270,7 @@ impl<'a, C: GKRConfig, const D: usize> SumcheckGkrSquareHelper<'a, C, D> {
             &mut self.sp.v_evals,
             &mut self.sp.hg_evals_5,
             &mut self.sp.hg_evals_1,
-            &self.layer.input_vals,
+            self.input_vals,
             &self.sp.gate_exists_5,
             &self.sp.gate_exists_1,
         )
@@ -276,7 +284,7 @@ impl<'a, C: GKRConfig, const D: usize> SumcheckGkrSquareHelper<'a, C, D> {
             &mut self.sp.v_evals,
             &mut self.sp.hg_evals_5,
             &mut self.sp.hg_evals_1,
-            &self.layer.input_vals,
+            self.input_vals,
             &mut self.sp.gate_exists_5,
             &mut self.sp.gate_exists_1,
         );
@@ -291,7 +299,7 @@ impl<'a, C: GKRConfig, const D: usize> SumcheckGkrSquareHelper<'a, C, D> {
 
     pub(crate) fn prepare_g_x_vals(&mut self) {
         let uni = &self.layer.uni; // univariate things like square, pow5, etc.
-        let vals = &self.layer.input_vals;
+        let vals = self.input_vals;
         let eq_evals_at_rz0 = &mut self.sp.eq_evals_at_rz0;
         let gate_exists_5 = &mut self.sp.gate_exists_5;
         let gate_exists_1 = &mut self.sp.gate_exists_1;
@@ -315,16 +323,17 @@ impl<'a, C: GKRConfig, const D: usize> SumcheckGkrSquareHelper<'a, C, D> {
             &mut self.sp.eq_evals_second_half,
         );
 
-        for g in uni.iter() {
+        for (index, g) in uni.iter().enumerate() {
+            let coef = self.rnd_coefs.coef(GateKind::Uni, index, g);
             match g.gate_type {
-                12345 => {
+                POW5_GATE_TYPE => {
                     hg_evals_5[g.i_ids[0]] +=
-                        C::challenge_mul_circuit_field(&eq_evals_at_rz0[g.o_id], &g.coef);
+                        C::challenge_mul_circuit_field(&eq_evals_at_rz0[g.o_id], &coef);
                     gate_exists_5[g.i_ids[0]] = true;
                 }
-                12346 => {
+                POW1_GATE_TYPE => {
                     hg_evals_1[g.i_ids[0]] +=
-                        C::challenge_mul_circuit_field(&eq_evals_at_rz0[g.o_id], &g.coef);
+                        C::challenge_mul_circuit_field(&eq_evals_at_rz0[g.o_id], &coef);
                     gate_exists_1[g.i_ids[0]] = true;
                 }
                 _ => panic!("Unsupported gate type"),
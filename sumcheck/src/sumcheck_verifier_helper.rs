@@ -1,7 +1,10 @@
 use std::{cmp::max, ptr};
 
 use arith::{ExtensionField, Field};
-use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateConst, GateMul};
+use circuit::{
+    CircuitLayer, CircuitStructure, CoefType, GateAdd, GateConst, GateKind, GateMul, GateUni,
+    LayerRndCoefs, POW1_GATE_TYPE, POW5_GATE_TYPE,
+};
 use config::{Config, FieldType, GKRConfig};
 
 use crate::sumcheck_helper::{_eq_vec, eq_eval_at, unpack_and_combine};
@@ -28,10 +31,14 @@ pub struct VerifierScratchPad<C: GKRConfig> {
     gf2_deg2_eval_coef: C::ChallengeField, // 1 / x(x - 1)
     deg3_eval_at: [C::ChallengeField; 4],
     deg3_lag_denoms_inv: [C::ChallengeField; 4],
+
+    // ====== for GKR^2 deg6 eval ======
+    deg6_eval_at: [C::ChallengeField; 7],
+    deg6_lag_denoms_inv: [C::ChallengeField; 7],
 }
 
 impl<C: GKRConfig> VerifierScratchPad<C> {
-    pub fn new(config: &Config<C>, circuit: &Circuit<C>) -> Self {
+    pub fn new(config: &Config<C>, circuit: &CircuitStructure<C>) -> Self {
         let mut max_num_var = circuit
             .layers
             .iter()
@@ -80,6 +87,26 @@ impl<C: GKRConfig> VerifierScratchPad<C> {
             deg3_lag_denoms_inv[i] = denominator.inv().unwrap();
         }
 
+        // GKR^2 evaluates its round polynomials at 0, 1, ..., 6,
+        // which are not distinct points over GF2, so it is left unsupported there
+        let mut deg6_eval_at = [C::ChallengeField::ZERO; 7];
+        let mut deg6_lag_denoms_inv = [C::ChallengeField::ZERO; 7];
+        if C::FIELD_TYPE != FieldType::GF2 {
+            for (i, eval_at) in deg6_eval_at.iter_mut().enumerate() {
+                *eval_at = C::ChallengeField::from(i as u32);
+            }
+            for i in 0..7 {
+                let mut denominator = C::ChallengeField::ONE;
+                for j in 0..7 {
+                    if j == i {
+                        continue;
+                    }
+                    denominator *= deg6_eval_at[i] - deg6_eval_at[j];
+                }
+                deg6_lag_denoms_inv[i] = denominator.inv().unwrap();
+            }
+        }
+
         Self {
             eq_evals_at_rz0: vec![C::ChallengeField::zero(); max_io_size],
             eq_evals_at_rz1: vec![C::ChallengeField::zero(); max_io_size],
@@ -100,6 +127,9 @@ impl<C: GKRConfig> VerifierScratchPad<C> {
             gf2_deg2_eval_coef,
             deg3_eval_at,
             deg3_lag_denoms_inv,
+
+            deg6_eval_at,
+            deg6_lag_denoms_inv,
         }
     }
 }
@@ -162,9 +192,25 @@ impl GKRVerifierHelper {
         sp.r_mpi = r_mpi;
     }
 
+    /// GKR^2 layers only combine a single claim, and keep the simd lanes apart
+    #[inline(always)]
+    pub fn prepare_square_layer<C: GKRConfig>(
+        rz0: &[C::ChallengeField],
+        sp: &mut VerifierScratchPad<C>,
+    ) {
+        eq_eval_at(
+            rz0,
+            &C::ChallengeField::ONE,
+            &mut sp.eq_evals_at_rz0,
+            &mut sp.eq_evals_first_part,
+            &mut sp.eq_evals_second_part,
+        );
+    }
+
     #[inline(always)]
     pub fn eval_cst<C: GKRConfig>(
         cst_gates: &[GateConst<C>],
+        rnd_coefs: &LayerRndCoefs<C>,
         public_input: &[C::SimdCircuitField],
         sp: &VerifierScratchPad<C>,
     ) -> C::ChallengeField {
@@ -177,7 +223,7 @@ impl GKRVerifierHelper {
         let mpi_sum: C::ChallengeField = sp.eq_evals_at_r_mpi.iter().sum();
         let simd_mpi_sum = simd_sum * mpi_sum;
 
-        for cst_gate in cst_gates {
+        for (index, cst_gate) in cst_gates.iter().enumerate() {
             let tmp = match cst_gate.coef_type {
                 CoefType::PublicInput(input_idx) => {
                     let mut input = vec![];
@@ -202,7 +248,7 @@ impl GKRVerifierHelper {
                 _ => {
                     C::challenge_mul_circuit_field(
                         &sp.eq_evals_at_rz0[cst_gate.o_id],
-                        &cst_gate.coef,
+                        &rnd_coefs.coef(GateKind::Const, index, cst_gate),
                     ) * simd_mpi_sum
                 }
             };
@@ -212,17 +258,42 @@ impl GKRVerifierHelper {
         v * simd_sum * mpi_sum
     }
 
+    /// Evaluate the constant gates lane-wise, i.e., without combining the simd lanes
+    #[inline(always)]
+    pub fn eval_cst_simd<C: GKRConfig>(
+        cst_gates: &[GateConst<C>],
+        rnd_coefs: &LayerRndCoefs<C>,
+        public_input: &[C::SimdCircuitField],
+        sp: &VerifierScratchPad<C>,
+    ) -> C::Field {
+        let mut v = C::Field::zero();
+        for (index, cst_gate) in cst_gates.iter().enumerate() {
+            v += match cst_gate.coef_type {
+                CoefType::PublicInput(input_idx) => C::simd_circuit_field_mul_challenge_field(
+                    &public_input[input_idx],
+                    &sp.eq_evals_at_rz0[cst_gate.o_id],
+                ),
+                _ => C::Field::from(C::challenge_mul_circuit_field(
+                    &sp.eq_evals_at_rz0[cst_gate.o_id],
+                    &rnd_coefs.coef(GateKind::Const, index, cst_gate),
+                )),
+            };
+        }
+        v
+    }
+
     #[inline(always)]
     pub fn eval_add<C: GKRConfig>(
         add_gates: &[GateAdd<C>],
+        rnd_coefs: &LayerRndCoefs<C>,
         sp: &VerifierScratchPad<C>,
     ) -> C::ChallengeField {
         let mut v = C::ChallengeField::zero();
-        for add_gate in add_gates {
+        for (index, add_gate) in add_gates.iter().enumerate() {
             v += sp.eq_evals_at_rz0[add_gate.o_id]
                 * C::challenge_mul_circuit_field(
                     &sp.eq_evals_at_rx[add_gate.i_ids[0]],
-                    &add_gate.coef,
+                    &rnd_coefs.coef(GateKind::Add, index, add_gate),
                 );
         }
         v * sp.eq_r_simd_r_simd_xy * sp.eq_r_mpi_r_mpi_xy
@@ -231,20 +302,58 @@ impl GKRVerifierHelper {
     #[inline(always)]
     pub fn eval_mul<C: GKRConfig>(
         mul_gates: &[GateMul<C>],
+        rnd_coefs: &LayerRndCoefs<C>,
         sp: &VerifierScratchPad<C>,
     ) -> C::ChallengeField {
         let mut v = C::ChallengeField::zero();
-        for mul_gate in mul_gates {
+        for (index, mul_gate) in mul_gates.iter().enumerate() {
             let tmp = sp.eq_evals_at_rx[mul_gate.i_ids[0]]
                 * C::challenge_mul_circuit_field(
                     &sp.eq_evals_at_ry[mul_gate.i_ids[1]],
-                    &mul_gate.coef,
+                    &rnd_coefs.coef(GateKind::Mul, index, mul_gate),
                 );
             v += sp.eq_evals_at_rz0[mul_gate.o_id] * tmp;
         }
         v * sp.eq_r_simd_r_simd_xy * sp.eq_r_mpi_r_mpi_xy
     }
 
+    #[inline(always)]
+    pub fn eval_pow5<C: GKRConfig>(
+        uni_gates: &[GateUni<C>],
+        rnd_coefs: &LayerRndCoefs<C>,
+        sp: &VerifierScratchPad<C>,
+    ) -> C::ChallengeField {
+        Self::eval_uni_of_type(uni_gates, rnd_coefs, POW5_GATE_TYPE, sp)
+    }
+
+    #[inline(always)]
+    pub fn eval_pow1<C: GKRConfig>(
+        uni_gates: &[GateUni<C>],
+        rnd_coefs: &LayerRndCoefs<C>,
+        sp: &VerifierScratchPad<C>,
+    ) -> C::ChallengeField {
+        Self::eval_uni_of_type(uni_gates, rnd_coefs, POW1_GATE_TYPE, sp)
+    }
+
+    #[inline(always)]
+    fn eval_uni_of_type<C: GKRConfig>(
+        uni_gates: &[GateUni<C>],
+        rnd_coefs: &LayerRndCoefs<C>,
+        gate_type: usize,
+        sp: &VerifierScratchPad<C>,
+    ) -> C::ChallengeField {
+        let mut v = C::ChallengeField::zero();
+        let gates = uni_gates.iter().enumerate();
+        for (index, uni_gate) in gates.filter(|(_, g)| g.gate_type == gate_type) {
+            v += sp.eq_evals_at_rz0[uni_gate.o_id]
+                * C::challenge_mul_circuit_field(
+                    &sp.eq_evals_at_rx[uni_gate.i_ids[0]],
+                    &rnd_coefs.coef(GateKind::Uni, index, uni_gate),
+                );
+        }
+        v
+    }
+
     #[inline(always)]
     pub fn set_rx<C: GKRConfig>(rx: &[C::ChallengeField], sp: &mut VerifierScratchPad<C>) {
         eq_eval_at(
@@ -318,6 +427,30 @@ impl GKRVerifierHelper {
         Self::lag_eval(vals, x, sp)
     }
 
+    /// Evaluate the GKR^2 round polynomial at x, from its 7 evaluations at 0, 1, ..., 6.
+    /// The evaluations are kept lane-wise in the simd field.
+    #[inline(always)]
+    pub fn degree_6_eval<C: GKRConfig>(
+        vals: &[C::Field],
+        x: C::ChallengeField,
+        sp: &VerifierScratchPad<C>,
+    ) -> C::Field {
+        assert_eq!(sp.deg6_eval_at.len(), vals.len());
+
+        let mut v = C::Field::zero();
+        for i in 0..vals.len() {
+            let mut numerator = C::ChallengeField::ONE;
+            for j in 0..vals.len() {
+                if j == i {
+                    continue;
+                }
+                numerator *= x - sp.deg6_eval_at[j];
+            }
+            v += C::challenge_mul_field(&(numerator * sp.deg6_lag_denoms_inv[i]), &vals[i]);
+        }
+        v
+    }
+
     #[inline(always)]
     fn lag_eval<C: GKRConfig>(
         vals: &[C::ChallengeField],
@@ -4,6 +4,12 @@ pub use sha2_256::*;
 pub mod keccak_256;
 pub use keccak_256::*;
 
+pub mod poseidon;
+pub use poseidon::*;
+
+pub mod mimc7;
+pub use mimc7::*;
+
 pub trait FiatShamirHash {
     /// The size of the hash output in bytes.
     const DIGEST_SIZE: usize;
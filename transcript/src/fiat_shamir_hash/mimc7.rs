@@ -0,0 +1,106 @@
+use std::sync::OnceLock;
+
+use arith::{BN254Fr, Field, FieldSerde};
+use tiny_keccak::{Hasher, Keccak};
+
+use super::FiatShamirHash;
+
+/// Number of rounds of the MiMC7 permutation, as in circomlib.
+pub const MIMC7_ROUNDS: usize = 91;
+
+/// Seed of the round constants, as in circomlib.
+const MIMC7_SEED: &[u8] = b"mimc";
+
+/// Number of input bytes per field element; 2^248 is below the BN254 modulus.
+pub const MIMC7_LIMB_SIZE: usize = 31;
+
+/// MiMC7 over the BN254 scalar field, built on the circomlib permutation and multi-hash.
+///
+/// The hash of a byte string is not a circomlib function, but the following convention, which
+/// a circomlib or Solidity verifier replays as `MiMC7.multiHash(limbs, len)`:
+/// - the input is split into limbs of `MIMC7_LIMB_SIZE` bytes, the last one zero padded, each
+///   read as a little-endian integer, which is below the modulus;
+/// - the limbs are absorbed with the multi-hash, keyed by the input length `len` in bytes, so
+///   that inputs differing only by trailing zeros have distinct digests;
+/// - the digest is the little-endian serialization of the resulting field element.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MiMC7hasher;
+
+impl MiMC7hasher {
+    /// The round constants: c_0 = 0, and c_i = keccak256^(i + 1)(seed) mod p for i > 0.
+    pub fn round_constants() -> &'static [BN254Fr] {
+        static CONSTANTS: OnceLock<Vec<BN254Fr>> = OnceLock::new();
+        CONSTANTS.get_or_init(|| {
+            let mut constants = vec![BN254Fr::ZERO; MIMC7_ROUNDS];
+            let mut c = keccak256(MIMC7_SEED);
+            for constant in constants.iter_mut().skip(1) {
+                c = keccak256(&c);
+                // keccak output is read as a big-endian integer
+                let mut le_bytes = c;
+                le_bytes.reverse();
+                *constant = BN254Fr::from_uniform_bytes(&le_bytes);
+            }
+            constants
+        })
+    }
+
+    /// The MiMC7 permutation of x under the key k.
+    pub fn mimc7_hash(x: &BN254Fr, k: &BN254Fr) -> BN254Fr {
+        let constants = Self::round_constants();
+        let mut r = BN254Fr::ZERO;
+        for (i, c) in constants.iter().enumerate() {
+            let t = if i == 0 { *x + k } else { r + k + c };
+            let t2 = t.square();
+            let t4 = t2.square();
+            r = t4 * t2 * t;
+        }
+        r + k
+    }
+
+    /// The MiMC7 multi-hash: r_0 = key, r_{i+1} = r_i + x_i + mimc7(x_i, r_i).
+    pub fn multi_hash(inputs: &[BN254Fr], key: &BN254Fr) -> BN254Fr {
+        inputs
+            .iter()
+            .fold(*key, |r, x| r + x + Self::mimc7_hash(x, &r))
+    }
+}
+
+#[inline]
+fn keccak256(input: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(input);
+    hasher.finalize(&mut output);
+    output
+}
+
+impl FiatShamirHash for MiMC7hasher {
+    const DIGEST_SIZE: usize = 32;
+
+    #[inline]
+    fn new() -> MiMC7hasher {
+        MiMC7hasher
+    }
+
+    fn hash(output: &mut [u8], input: &[u8]) {
+        let elems: Vec<BN254Fr> = input
+            .chunks(MIMC7_LIMB_SIZE)
+            .map(|limb| {
+                let mut bytes = [0u8; 32];
+                bytes[..limb.len()].copy_from_slice(limb);
+                BN254Fr::deserialize_from(bytes.as_slice()).unwrap()
+            })
+            .collect();
+
+        let key = BN254Fr::from(input.len() as u32);
+        Self::multi_hash(&elems, &key)
+            .serialize_into(output)
+            .unwrap();
+    }
+
+    #[inline]
+    fn hash_inplace(buffer: &mut [u8]) {
+        let input = buffer.to_vec();
+        Self::hash(buffer, &input);
+    }
+}
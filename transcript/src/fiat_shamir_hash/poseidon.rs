@@ -0,0 +1,225 @@
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+
+use arith::{BN254Fr, Field, FieldSerde};
+use mersenne31::M31;
+use sha2::{Digest, Sha256};
+
+use super::FiatShamirHash;
+
+/// Parameters of a Poseidon permutation with the x^5 S-box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoseidonParams<F: Field> {
+    /// Number of field elements in the state.
+    pub width: usize,
+
+    /// Number of state elements absorbed per permutation; the rest is the capacity.
+    pub rate: usize,
+
+    /// Total number of full rounds, split evenly before and after the partial rounds.
+    pub full_rounds: usize,
+
+    /// Number of partial rounds, applying the S-box to the first state element only.
+    pub partial_rounds: usize,
+
+    /// One vector of `width` constants per round.
+    pub round_constants: Vec<Vec<F>>,
+
+    /// The `width` x `width` MDS matrix.
+    pub mds: Vec<Vec<F>>,
+}
+
+impl<F: Field> PoseidonParams<F> {
+    /// Derive the parameters for the given shape.
+    ///
+    /// The round constants are SHA256(domain || round || index), mapped into the field, in place
+    /// of the Grain LFSR of the reference implementation: the security analysis only needs them
+    /// to be pseudo-random, but the instances do not match other Poseidon implementations.
+    /// The MDS matrix is the Cauchy matrix M[i][j] = 1 / (x_i + y_j) with x_i = i and
+    /// y_j = width + j, which is MDS as the x_i and the y_j are distinct and x_i + y_j < p.
+    pub fn new(
+        domain: &[u8],
+        width: usize,
+        rate: usize,
+        full_rounds: usize,
+        partial_rounds: usize,
+    ) -> Self {
+        assert!(rate < width);
+        assert!(full_rounds % 2 == 0);
+
+        let round_constants = (0..full_rounds + partial_rounds)
+            .map(|round| {
+                (0..width)
+                    .map(|index| {
+                        let mut hasher = Sha256::new();
+                        hasher.update(domain);
+                        hasher.update((round as u32).to_le_bytes());
+                        hasher.update((index as u32).to_le_bytes());
+                        F::from_uniform_bytes(&hasher.finalize().into())
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mds = (0..width)
+            .map(|i| {
+                (0..width)
+                    .map(|j| F::from((i + width + j) as u32).inv().unwrap())
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            width,
+            rate,
+            full_rounds,
+            partial_rounds,
+            round_constants,
+            mds,
+        }
+    }
+
+    /// Apply the permutation to the state in place.
+    pub fn permute(&self, state: &mut [F]) {
+        assert_eq!(state.len(), self.width);
+
+        let half_full_rounds = self.full_rounds / 2;
+        for (round, constants) in self.round_constants.iter().enumerate() {
+            state
+                .iter_mut()
+                .zip(constants.iter())
+                .for_each(|(s, c)| *s += c);
+
+            let is_full_round =
+                round < half_full_rounds || round >= half_full_rounds + self.partial_rounds;
+            if is_full_round {
+                state.iter_mut().for_each(|s| *s = sbox(s));
+            } else {
+                state[0] = sbox(&state[0]);
+            }
+
+            let mixed: Vec<F> = self
+                .mds
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .zip(state.iter())
+                        .fold(F::ZERO, |acc, (m, s)| acc + *m * s)
+                })
+                .collect();
+            state.copy_from_slice(&mixed);
+        }
+    }
+}
+
+#[inline(always)]
+fn sbox<F: Field>(x: &F) -> F {
+    x.square().square() * x
+}
+
+/// A field with a fixed Poseidon instance for Fiat-Shamir.
+///
+/// Both instances use the x^5 S-box and target 128 bits of security. Their round numbers are at
+/// least the bounds of `calc_round_numbers.py` of the Poseidon reference implementation
+/// (statistical, interpolation and Groebner basis attacks, including eprint 2023/537), after
+/// its security margin of two more full rounds and 7.5% more partial rounds, which are 8 full
+/// and 14 partial rounds for M31 at width 16, and 8 full and 56 partial rounds for BN254 at
+/// width 3. The instances use 8 full rounds and 22 partial rounds for M31, and 8 full rounds
+/// and 57 partial rounds for BN254.
+pub trait PoseidonField: Field + FieldSerde + 'static {
+    /// Number of input bytes packed into a field element. 2^(8 * LIMB_SIZE) is below the
+    /// modulus, so that distinct inputs of the same length give distinct field elements.
+    const LIMB_SIZE: usize;
+
+    fn poseidon_params() -> &'static PoseidonParams<Self>;
+}
+
+/// 8 full and 22 partial rounds, 8 partial rounds above the bound of `calc_round_numbers.py`
+/// at width 16; the capacity of 8 elements is 248 bits.
+impl PoseidonField for M31 {
+    const LIMB_SIZE: usize = 3;
+
+    fn poseidon_params() -> &'static PoseidonParams<Self> {
+        static PARAMS: OnceLock<PoseidonParams<M31>> = OnceLock::new();
+        PARAMS.get_or_init(|| PoseidonParams::new(b"Expander Poseidon M31x16", 16, 8, 8, 22))
+    }
+}
+
+/// 8 full and 57 partial rounds, the round numbers of the BN254 instance at width 3 in the
+/// Poseidon paper (eprint 2019/458), one partial round above the current bound of
+/// `calc_round_numbers.py`.
+impl PoseidonField for BN254Fr {
+    const LIMB_SIZE: usize = 31;
+
+    fn poseidon_params() -> &'static PoseidonParams<Self> {
+        static PARAMS: OnceLock<PoseidonParams<BN254Fr>> = OnceLock::new();
+        PARAMS.get_or_init(|| PoseidonParams::new(b"Expander Poseidon BN254x3", 3, 2, 8, 57))
+    }
+}
+
+/// Poseidon sponge over the field F.
+///
+/// The input is read as a sequence of little-endian limbs of `F::LIMB_SIZE` bytes, the last one
+/// zero padded, each mapped to the field element of the same value. The capacity is initialized
+/// with the input length in bytes, so that the encoding of the input is injective.
+/// The digest is the serialization of the first state elements after absorbing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Poseidonhasher<F: PoseidonField> {
+    phantom: PhantomData<F>,
+}
+
+pub type PoseidonM31x16hasher = Poseidonhasher<M31>;
+
+pub type PoseidonBN254hasher = Poseidonhasher<BN254Fr>;
+
+impl<F: PoseidonField> FiatShamirHash for Poseidonhasher<F> {
+    const DIGEST_SIZE: usize = 32;
+
+    #[inline]
+    fn new() -> Self {
+        Poseidonhasher {
+            phantom: PhantomData,
+        }
+    }
+
+    fn hash(output: &mut [u8], input: &[u8]) {
+        let params = F::poseidon_params();
+        assert!(Self::DIGEST_SIZE % F::SIZE == 0);
+        assert!(Self::DIGEST_SIZE / F::SIZE <= params.rate);
+
+        let mut state = vec![F::ZERO; params.width];
+        state[params.rate] = F::from(input.len() as u32);
+
+        let elems: Vec<F> = input
+            .chunks(F::LIMB_SIZE)
+            .map(|limb| {
+                // the canonical serialization of the limb value, which is below the modulus
+                let mut bytes = vec![0u8; F::SIZE];
+                bytes[..limb.len()].copy_from_slice(limb);
+                F::deserialize_from(bytes.as_slice()).unwrap()
+            })
+            .collect();
+
+        if elems.is_empty() {
+            params.permute(&mut state);
+        }
+        for block in elems.chunks(params.rate) {
+            state
+                .iter_mut()
+                .zip(block.iter())
+                .for_each(|(s, e)| *s += e);
+            params.permute(&mut state);
+        }
+
+        output
+            .chunks_mut(F::SIZE)
+            .zip(state.iter())
+            .for_each(|(out, s)| s.serialize_into(out).unwrap());
+    }
+
+    #[inline]
+    fn hash_inplace(buffer: &mut [u8]) {
+        let input = buffer.to_vec();
+        Self::hash(buffer, &input);
+    }
+}
@@ -2,7 +2,7 @@ use sha2::{digest::Output, Digest, Sha256};
 
 use super::FiatShamirHash;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct SHA256hasher;
 
 impl FiatShamirHash for SHA256hasher {
@@ -1,11 +1,14 @@
 mod fiat_shamir_hash;
-pub use fiat_shamir_hash::{FiatShamirHash, Keccak256hasher, SHA256hasher};
+pub use fiat_shamir_hash::{
+    FiatShamirHash, Keccak256hasher, MiMC7hasher, PoseidonBN254hasher, PoseidonField,
+    PoseidonM31x16hasher, PoseidonParams, Poseidonhasher, SHA256hasher, MIMC7_ROUNDS,
+};
 
 mod transcript;
 pub use transcript::{Transcript, TranscriptInstance};
 
 mod proof;
-pub use proof::Proof;
+pub use proof::{Proof, ProofReadError, ProofReadResult, ProofReader};
 
 #[cfg(test)]
 mod tests;
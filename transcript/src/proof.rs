@@ -1,11 +1,11 @@
-use std::io::{Read, Write};
+use std::io::{ErrorKind, Read, Write};
 
-use arith::{Field, FieldSerde, FieldSerdeResult};
+use arith::{FieldSerde, FieldSerdeError, FieldSerdeResult};
+use thiserror::Error;
 
 /// Proof. In the serialized mode.
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct Proof {
-    idx: usize,
     pub bytes: Vec<u8>,
 }
 
@@ -14,18 +14,6 @@ impl Proof {
     pub fn append_u8_slice(&mut self, buffer: &[u8], size: usize) {
         self.bytes.extend_from_slice(&buffer[..size]);
     }
-
-    #[inline(always)]
-    pub fn step(&mut self, size: usize) {
-        self.idx += size;
-    }
-
-    #[inline(always)]
-    pub fn get_next_and_step<F: Field + FieldSerde>(&mut self) -> F {
-        let ret = F::deserialize_from(&self.bytes[self.idx..]).unwrap(); // TODO: error propagation
-        self.step(F::SIZE);
-        ret
-    }
 }
 
 impl FieldSerde for Proof {
@@ -40,16 +28,112 @@ impl FieldSerde for Proof {
 
     #[inline(always)]
     fn deserialize_from<R: Read>(mut reader: R) -> FieldSerdeResult<Self> {
-        let proof_len = u64::deserialize_from(&mut reader)? as usize;
-        let mut proof = vec![0u8; proof_len];
-        reader.read_exact(&mut proof).unwrap();
-        Ok(Self {
-            idx: 0,
-            bytes: proof,
-        })
+        // the length is not trusted for the allocation
+        let proof_len = u64::deserialize_from(&mut reader)?;
+        let mut bytes = vec![];
+        reader.take(proof_len).read_to_end(&mut bytes)?;
+        if (bytes.len() as u64) < proof_len {
+            return Err(std::io::Error::from(ErrorKind::UnexpectedEof).into());
+        }
+        Ok(Self { bytes })
     }
 
     fn try_deserialize_from_ecc_format<R: Read>(_reader: R) -> FieldSerdeResult<Self> {
         unimplemented!("not implemented for Proof")
     }
 }
+
+#[derive(Debug, Error)]
+pub enum ProofReadError {
+    #[error("proof truncated at byte {0}")]
+    Truncated(usize),
+
+    #[error("invalid encoding at byte {offset}: {source}")]
+    InvalidEncoding {
+        offset: usize,
+        source: FieldSerdeError,
+    },
+
+    #[error("{0} bytes left unread at the end of the proof")]
+    TrailingBytes(usize),
+}
+
+pub type ProofReadResult<T> = Result<T, ProofReadError>;
+
+/// Reads a proof from the start, failing instead of panicking on malformed input.
+#[derive(Debug, Clone)]
+pub struct ProofReader<'a> {
+    bytes: &'a [u8],
+    idx: usize,
+}
+
+impl<'a> ProofReader<'a> {
+    #[inline]
+    pub fn new(proof: &'a Proof) -> Self {
+        Self {
+            bytes: &proof.bytes,
+            idx: 0,
+        }
+    }
+
+    /// Number of bytes read so far.
+    #[inline(always)]
+    pub fn position(&self) -> usize {
+        self.idx
+    }
+
+    #[inline(always)]
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.idx
+    }
+
+    /// The next `size` bytes, without reading them.
+    #[inline]
+    pub fn peek_bytes(&self, size: usize) -> ProofReadResult<&'a [u8]> {
+        self.bytes[self.idx..]
+            .get(..size)
+            .ok_or(ProofReadError::Truncated(self.bytes.len()))
+    }
+
+    #[inline]
+    pub fn read_bytes(&mut self, size: usize) -> ProofReadResult<&'a [u8]> {
+        let bytes = self.peek_bytes(size)?;
+        self.idx += size;
+        Ok(bytes)
+    }
+
+    #[inline]
+    pub fn read<F: FieldSerde>(&mut self) -> ProofReadResult<F> {
+        self.read_with(F::deserialize_from)
+    }
+
+    /// Read a value of variable size, advancing by the number of bytes `f` consumed.
+    pub fn read_with<T>(
+        &mut self,
+        f: impl FnOnce(&mut &'a [u8]) -> FieldSerdeResult<T>,
+    ) -> ProofReadResult<T> {
+        let mut rest = &self.bytes[self.idx..];
+        match f(&mut rest) {
+            Ok(v) => {
+                self.idx = self.bytes.len() - rest.len();
+                Ok(v)
+            }
+            Err(FieldSerdeError::IOError(e)) if e.kind() == ErrorKind::UnexpectedEof => {
+                Err(ProofReadError::Truncated(self.bytes.len()))
+            }
+            Err(source) => Err(ProofReadError::InvalidEncoding {
+                offset: self.idx,
+                source,
+            }),
+        }
+    }
+
+    /// Check that the whole proof has been read.
+    #[inline]
+    pub fn finish(&self) -> ProofReadResult<()> {
+        match self.remaining() {
+            0 => Ok(()),
+            n => Err(ProofReadError::TrailingBytes(n)),
+        }
+    }
+}
@@ -15,3 +15,286 @@ fn check_sha256_aligned() {
     println!("{:?}", out);
     assert_eq!(out, EXAMPLE_OUT.into());
 }
+
+#[test]
+fn check_poseidon_m31_absorbs_limbs() {
+    use arith::{Field, FieldSerde};
+    use mersenne31::M31;
+
+    use crate::{FiatShamirHash, PoseidonField, PoseidonM31x16hasher};
+
+    let limbs: Vec<u32> = (0..11u32).map(|i| i * 0x12345 % (1 << 24)).collect();
+    let bytes: Vec<u8> = limbs
+        .iter()
+        .flat_map(|limb| limb.to_le_bytes()[..3].to_vec())
+        .collect();
+
+    let mut out = [0u8; 32];
+    PoseidonM31x16hasher::hash(&mut out, &bytes);
+
+    // replay the sponge natively over the 3-byte limbs
+    let elems: Vec<M31> = limbs.iter().map(|limb| M31::from(*limb)).collect();
+    let params = M31::poseidon_params();
+    let mut state = vec![M31::ZERO; params.width];
+    state[params.rate] = M31::from(bytes.len() as u32);
+    for block in elems.chunks(params.rate) {
+        state.iter_mut().zip(block).for_each(|(s, e)| *s += e);
+        params.permute(&mut state);
+    }
+    let mut expected = vec![];
+    state[..8]
+        .iter()
+        .for_each(|s| s.serialize_into(&mut expected).unwrap());
+    assert_eq!(out.to_vec(), expected);
+
+    let mut buffer = bytes.clone();
+    buffer.resize(32, 0);
+    let mut padded_out = [0u8; 32];
+    PoseidonM31x16hasher::hash(&mut padded_out, &buffer);
+    // zero padding changes the absorbed length
+    assert_ne!(out, padded_out);
+    PoseidonM31x16hasher::hash_inplace(&mut buffer);
+    assert_eq!(buffer, padded_out.to_vec());
+
+    // p and 0 serialize differently, so they must not collide
+    let mut zero_out = [0u8; 32];
+    PoseidonM31x16hasher::hash(&mut zero_out, &[0, 0, 0, 0]);
+    let mut modulus_out = [0u8; 32];
+    PoseidonM31x16hasher::hash(&mut modulus_out, &[255, 255, 255, 127]);
+    assert_ne!(zero_out, modulus_out);
+}
+
+#[test]
+fn check_poseidon_bn254_challenges() {
+    use arith::{BN254Fr, Field};
+
+    use crate::{PoseidonBN254hasher, Transcript, TranscriptInstance};
+
+    let mut transcript = TranscriptInstance::<PoseidonBN254hasher>::new();
+    transcript.absorb_public_field_element(&BN254Fr::from(42u32));
+    let challenges = transcript.generate_challenge_vector::<BN254Fr>(4);
+
+    let mut replay = TranscriptInstance::<PoseidonBN254hasher>::new();
+    replay.absorb_public_field_element(&BN254Fr::from(42u32));
+    assert_eq!(challenges, replay.generate_challenge_vector::<BN254Fr>(4));
+
+    // rehashing the digest gives fresh challenges
+    assert!(challenges.windows(2).all(|w| w[0] != w[1]));
+    assert!(challenges.iter().all(|c| !c.is_zero()));
+}
+
+// MiMC7 vectors from the circomlib reference implementation, serialized in little-endian
+const MIMC7_C1: [u8; 32] = [
+    229, 152, 10, 231, 183, 108, 184, 46, 221, 29, 146, 13, 235, 212, 193, 139, 217, 106, 151, 240,
+    152, 193, 142, 216, 99, 107, 41, 120, 177, 187, 46, 46,
+];
+const MIMC7_C90: [u8; 32] = [
+    245, 158, 107, 54, 198, 179, 14, 9, 253, 86, 146, 123, 5, 55, 114, 29, 26, 196, 192, 124, 149,
+    188, 120, 177, 49, 212, 242, 239, 184, 137, 18, 30,
+];
+// mimc7.hash(1, 2)
+const MIMC7_HASH_1_2: [u8; 32] = [
+    8, 26, 159, 133, 231, 58, 236, 193, 221, 147, 123, 149, 227, 196, 209, 187, 133, 168, 247, 230,
+    216, 2, 96, 19, 214, 248, 253, 195, 239, 110, 108, 23,
+];
+// mimc7.multiHash([12, 45, 78, 41], 0)
+const MIMC7_MULTI_HASH: [u8; 32] = [
+    235, 75, 16, 133, 250, 74, 150, 45, 221, 252, 226, 229, 213, 44, 104, 157, 23, 238, 243, 111,
+    59, 67, 58, 162, 51, 89, 51, 79, 243, 193, 75, 40,
+];
+// mimc7.multiHash([0x0504030201], 5), i.e. the digest of the bytes [1, 2, 3, 4, 5]
+const MIMC7_DIGEST_UNALIGNED: [u8; 32] = [
+    212, 245, 220, 126, 6, 158, 117, 125, 51, 18, 247, 151, 154, 187, 175, 177, 7, 106, 74, 130,
+    135, 101, 59, 96, 252, 161, 234, 170, 206, 165, 118, 23,
+];
+
+#[test]
+fn check_mimc7_reference_vectors() {
+    use arith::{BN254Fr, Field, FieldSerde};
+
+    use crate::{MiMC7hasher, MIMC7_ROUNDS};
+
+    let fr = |bytes: &[u8; 32]| BN254Fr::deserialize_from(&bytes[..]).unwrap();
+
+    let constants = MiMC7hasher::round_constants();
+    assert_eq!(constants.len(), MIMC7_ROUNDS);
+    assert!(constants[0].is_zero());
+    assert_eq!(constants[1], fr(&MIMC7_C1));
+    assert_eq!(constants[90], fr(&MIMC7_C90));
+
+    assert_eq!(
+        MiMC7hasher::mimc7_hash(&BN254Fr::from(1u32), &BN254Fr::from(2u32)),
+        fr(&MIMC7_HASH_1_2)
+    );
+
+    let inputs = [12u32, 45, 78, 41].map(BN254Fr::from);
+    assert_eq!(
+        MiMC7hasher::multi_hash(&inputs, &BN254Fr::ZERO),
+        fr(&MIMC7_MULTI_HASH)
+    );
+}
+
+#[test]
+fn check_mimc7_digest() {
+    use arith::{BN254Fr, FieldSerde};
+
+    use crate::{FiatShamirHash, MiMC7hasher};
+
+    let mut input = vec![];
+    BN254Fr::from(12u32).serialize_into(&mut input).unwrap();
+    BN254Fr::from(45u32).serialize_into(&mut input).unwrap();
+
+    // the 31-byte limbs of the two serialized elements are 12, 45 * 2^8 and 0
+    let mut out = [0u8; 32];
+    MiMC7hasher::hash(&mut out, &input);
+    let limbs = [12u32, 45 << 8, 0].map(BN254Fr::from);
+    let mut expected = vec![];
+    MiMC7hasher::multi_hash(&limbs, &BN254Fr::from(64u32))
+        .serialize_into(&mut expected)
+        .unwrap();
+    assert_eq!(out.to_vec(), expected);
+
+    // p and 0 serialize differently, so they must not collide
+    let mut modulus = vec![];
+    (-BN254Fr::from(1u32)).serialize_into(&mut modulus).unwrap();
+    modulus[0] += 1;
+    let mut modulus_out = [0u8; 32];
+    MiMC7hasher::hash(&mut modulus_out, &modulus);
+    let mut zero_out = [0u8; 32];
+    MiMC7hasher::hash(&mut zero_out, &[0u8; 32]);
+    assert_ne!(modulus_out, zero_out);
+
+    MiMC7hasher::hash(&mut out, &[1, 2, 3, 4, 5]);
+    assert_eq!(out, MIMC7_DIGEST_UNALIGNED);
+
+    let mut buffer = MIMC7_DIGEST_UNALIGNED;
+    MiMC7hasher::hash_inplace(&mut buffer);
+    MiMC7hasher::hash(&mut out, &MIMC7_DIGEST_UNALIGNED);
+    assert_eq!(buffer, out);
+}
+
+#[test]
+fn check_proof_reader() {
+    use arith::FieldSerde;
+    use mersenne31::M31;
+
+    use crate::{Proof, ProofReadError, ProofReader};
+
+    let mut proof = Proof::default();
+    7u64.serialize_into(&mut proof.bytes).unwrap();
+    M31::from(5).serialize_into(&mut proof.bytes).unwrap();
+    proof.bytes.push(1);
+
+    let mut reader = ProofReader::new(&proof);
+    assert_eq!(reader.read::<u64>().unwrap(), 7);
+    assert_eq!(reader.peek_bytes(4).unwrap(), reader.read_bytes(4).unwrap());
+    assert_eq!(reader.position(), 12);
+    assert!(matches!(
+        reader.finish(),
+        Err(ProofReadError::TrailingBytes(1))
+    ));
+    assert!(matches!(
+        reader.read::<M31>(),
+        Err(ProofReadError::Truncated(13))
+    ));
+    // a failed read does not advance
+    assert_eq!(reader.read_bytes(1).unwrap(), [1]);
+    reader.finish().unwrap();
+
+    let mut bytes = vec![];
+    proof.serialize_into(&mut bytes).unwrap();
+    assert_eq!(Proof::deserialize_from(bytes.as_slice()).unwrap(), proof);
+    assert!(Proof::deserialize_from(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn check_prover_verifier_transcripts() {
+    use arith::Field;
+    use mersenne31::M31;
+
+    use crate::{Proof, ProofReader, SHA256hasher, Transcript, TranscriptInstance};
+
+    // public data is absorbed by both sides without being part of the proof
+    let mut prover = TranscriptInstance::<SHA256hasher>::new();
+    let mut proof = Proof::default();
+    prover.absorb_public(b"circuit");
+    prover.send_field_element(&M31::from(3), &mut proof);
+    let r = prover.generate_challenge::<M31>();
+    prover.send_to_verifier(&[1, 2], &mut proof);
+    assert_eq!(proof.bytes.len(), 6);
+
+    let mut verifier = TranscriptInstance::<SHA256hasher>::new();
+    let mut reader = ProofReader::new(&proof);
+    verifier.absorb_public(b"circuit");
+    assert_eq!(
+        verifier.receive_field_element::<M31>(&mut reader).unwrap(),
+        M31::from(3)
+    );
+    assert_eq!(verifier.generate_challenge::<M31>(), r);
+    assert_eq!(
+        verifier.receive_from_prover(2, &mut reader).unwrap(),
+        [1, 2]
+    );
+    reader.finish().unwrap();
+    assert_eq!(verifier, prover);
+
+    // without the public data, the challenges differ
+    let mut verifier = TranscriptInstance::<SHA256hasher>::new();
+    let mut reader = ProofReader::new(&proof);
+    verifier.receive_field_element::<M31>(&mut reader).unwrap();
+    assert_ne!(verifier.generate_challenge::<M31>(), r);
+    assert!(!r.is_zero());
+}
+
+#[test]
+fn check_labelled_transcript() {
+    use arith::Field;
+    use mersenne31::M31;
+
+    use crate::{SHA256hasher, Transcript, TranscriptInstance};
+
+    type T = TranscriptInstance<SHA256hasher>;
+
+    // the same bytes under other labels, or split differently, give other challenges
+    let challenge = |labelled: &[(&'static str, &str)]| {
+        let mut transcript = T::new();
+        labelled
+            .iter()
+            .for_each(|&(label, buffer)| transcript.absorb_labelled(label, buffer.as_bytes()));
+        transcript.generate_labelled_challenge::<M31>("r")
+    };
+    let r = challenge(&[("a", "xy")]);
+    assert_eq!(challenge(&[("a", "xy")]), r);
+    assert_ne!(challenge(&[("b", "xy")]), r);
+    assert_ne!(challenge(&[("a", "x"), ("a", "y")]), r);
+    assert_ne!(challenge(&[("ax", "y")]), r);
+
+    // any number of bytes can be squeezed, the shorter outputs being prefixes
+    let mut transcript = T::new();
+    transcript.absorb_public(b"seed");
+    let mut long = [0u8; 100];
+    transcript.clone().challenge_bytes("bytes", &mut long);
+    let mut short = [0u8; 40];
+    transcript.clone().challenge_bytes("bytes", &mut short);
+    assert_eq!(short, long[..40]);
+    assert_ne!(long[..32], long[32..64]);
+
+    // a challenge vector is drawn from a single squeeze
+    let challenges = transcript.clone().generate_challenge_vector::<M31>(3);
+    let mut bytes = [0u8; 96];
+    transcript.clone().squeeze_bytes(&mut bytes);
+    for (c, chunk) in challenges.iter().zip(bytes.chunks_exact(32)) {
+        assert_eq!(*c, M31::from_uniform_bytes(chunk.try_into().unwrap()));
+    }
+    assert!(challenges.iter().all(|c| !c.is_zero()));
+
+    // the digest is chained, so later challenges depend on everything absorbed before
+    let later = |first: &[u8]| {
+        let mut transcript = T::new();
+        transcript.absorb_public(first);
+        transcript.generate_challenge::<M31>();
+        transcript.absorb_public_field_element(&M31::from(7));
+        transcript.generate_challenge::<M31>()
+    };
+    assert_ne!(later(b"one"), later(b"two"));
+}
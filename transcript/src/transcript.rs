@@ -1,37 +1,115 @@
 use std::marker::PhantomData;
 
-use arith::{Field, FieldSerde};
+use arith::{Field, FieldSerde, FieldSerdeResult};
 
-use crate::{fiat_shamir_hash::FiatShamirHash, Proof};
+use crate::{fiat_shamir_hash::FiatShamirHash, Proof, ProofReadResult, ProofReader};
 
+/// Number of bytes a field challenge is sampled from.
+const CHALLENGE_BYTES: usize = 32;
+
+/// Fiat-Shamir transcript. Data is either public, i.e., known to both sides beforehand,
+/// or a prover message, which the prover writes to the proof and the verifier reads from it.
 pub trait Transcript<H: FiatShamirHash> {
     /// Create a new transcript.
     fn new() -> Self;
 
-    /// Append a field element to the transcript.
-    fn append_field_element<F>(&mut self, f: &F)
-    where
-        F: FieldSerde,
-    {
+    /// Absorb public data, which is not part of the proof.
+    fn absorb_public(&mut self, buffer: &[u8]);
+
+    /// Absorb a public field element, which is not part of the proof.
+    fn absorb_public_field_element<F: FieldSerde>(&mut self, f: &F) {
         let mut buf = vec![];
         f.serialize_into(&mut buf).unwrap();
-        self.append_u8_slice(&buf);
+        self.absorb_public(&buf);
+    }
+
+    /// Prover side: absorb a message and write it to the proof.
+    fn send_to_verifier(&mut self, buffer: &[u8], proof: &mut Proof) {
+        proof.bytes.extend_from_slice(buffer);
+        self.absorb_public(buffer);
+    }
+
+    /// Prover side: absorb a field element and write it to the proof.
+    fn send_field_element<F: FieldSerde>(&mut self, f: &F, proof: &mut Proof) {
+        let mut buf = vec![];
+        f.serialize_into(&mut buf).unwrap();
+        self.send_to_verifier(&buf, proof);
+    }
+
+    /// Verifier side: read `size` bytes sent by the prover and absorb them.
+    fn receive_from_prover<'a>(
+        &mut self,
+        size: usize,
+        proof: &mut ProofReader<'a>,
+    ) -> ProofReadResult<&'a [u8]> {
+        let buffer = proof.read_bytes(size)?;
+        self.absorb_public(buffer);
+        Ok(buffer)
+    }
+
+    /// Verifier side: read a value sent by the prover with `f` and absorb its bytes.
+    fn receive_with<'a, T>(
+        &mut self,
+        f: impl FnOnce(&mut &'a [u8]) -> FieldSerdeResult<T>,
+        proof: &mut ProofReader<'a>,
+    ) -> ProofReadResult<T> {
+        let rest = proof.peek_bytes(proof.remaining())?;
+        let start = proof.position();
+        let v = proof.read_with(f)?;
+        self.absorb_public(&rest[..proof.position() - start]);
+        Ok(v)
+    }
+
+    /// Verifier side: read a field element sent by the prover and absorb it.
+    fn receive_field_element<F: FieldSerde>(
+        &mut self,
+        proof: &mut ProofReader,
+    ) -> ProofReadResult<F> {
+        self.receive_with(F::deserialize_from, proof)
     }
 
-    /// Append a byte slice to the transcript.
-    fn append_u8_slice(&mut self, buffer: &[u8]);
+    /// Absorb a label, separating what follows from what is absorbed under other labels.
+    fn absorb_label(&mut self, label: &'static str);
+
+    /// Absorb public data under a label. The length is absorbed too, so that the same bytes
+    /// split differently between labels give another transcript.
+    fn absorb_labelled(&mut self, label: &'static str, buffer: &[u8]) {
+        self.absorb_label(label);
+        self.absorb_public(&(buffer.len() as u64).to_le_bytes());
+        self.absorb_public(buffer);
+    }
+
+    /// Fill `out` with challenge bytes, updating the state once whatever the length.
+    fn squeeze_bytes(&mut self, out: &mut [u8]);
+
+    /// Fill `out` with challenge bytes drawn under a label.
+    fn challenge_bytes(&mut self, label: &'static str, out: &mut [u8]) {
+        self.absorb_label(label);
+        self.squeeze_bytes(out);
+    }
 
     /// Generate a challenge.
-    fn generate_challenge<F: Field>(&mut self) -> F;
+    fn generate_challenge<F: Field>(&mut self) -> F {
+        let mut bytes = [0u8; CHALLENGE_BYTES];
+        self.squeeze_bytes(&mut bytes);
+        F::from_uniform_bytes(&bytes)
+    }
+
+    /// Generate a challenge under a label.
+    fn generate_labelled_challenge<F: Field>(&mut self, label: &'static str) -> F {
+        self.absorb_label(label);
+        self.generate_challenge()
+    }
 
-    /// Generate a challenge vector.
+    /// Generate a challenge vector from a single squeeze.
     #[inline]
     fn generate_challenge_vector<F: Field>(&mut self, n: usize) -> Vec<F> {
-        let mut challenges = Vec::with_capacity(n);
-        for _ in 0..n {
-            challenges.push(self.generate_challenge());
-        }
-        challenges
+        let mut bytes = vec![0u8; n * CHALLENGE_BYTES];
+        self.squeeze_bytes(&mut bytes);
+        bytes
+            .chunks_exact(CHALLENGE_BYTES)
+            .map(|chunk| F::from_uniform_bytes(chunk.try_into().unwrap()))
+            .collect()
     }
 }
 
@@ -39,14 +117,11 @@ pub trait Transcript<H: FiatShamirHash> {
 pub struct TranscriptInstance<H: FiatShamirHash> {
     phantom: PhantomData<H>,
 
-    /// The digest bytes.
+    /// The digest bytes, committing to everything absorbed so far.
     pub digest: Vec<u8>,
 
-    /// The proof bytes.
-    pub proof: Proof,
-
-    /// The pointer to the proof bytes indicating where the hash starts.
-    hash_start_index: usize,
+    /// The bytes absorbed since the digest was last updated.
+    absorbed: Vec<u8>,
 }
 
 impl<H: FiatShamirHash> Transcript<H> for TranscriptInstance<H> {
@@ -54,36 +129,84 @@ impl<H: FiatShamirHash> Transcript<H> for TranscriptInstance<H> {
         TranscriptInstance {
             phantom: PhantomData,
             digest: vec![0u8; H::DIGEST_SIZE],
-            proof: Proof::default(),
-            hash_start_index: 0,
+            absorbed: vec![],
         }
     }
 
-    /// Append a byte slice to the transcript.
-    fn append_u8_slice(&mut self, buffer: &[u8]) {
-        self.proof.bytes.extend_from_slice(buffer);
+    /// Absorb public data, which is not part of the proof.
+    /// The messages of the prover are absorbed here on both sides, when sent and when
+    /// received, so with the `trace` feature every message is logged with its length and a
+    /// fingerprint of the state, in the same order in the logs of the prover and the verifier.
+    fn absorb_public(&mut self, buffer: &[u8]) {
+        self.absorbed.extend_from_slice(buffer);
+
+        #[cfg(feature = "trace")]
+        log::trace!(
+            "transcript absorb {} bytes: {}",
+            buffer.len(),
+            self.fingerprint()
+        );
     }
 
-    /// Generate a challenge.
-    fn generate_challenge<F: Field>(&mut self) -> F {
+    /// Absorb a label, prefixed with its length so that no label is a prefix of another.
+    /// With the `trace` feature, the label is logged with a fingerprint of the state, so
+    /// that the logs of the prover and the verifier show where they diverge.
+    fn absorb_label(&mut self, label: &'static str) {
+        #[cfg(feature = "trace")]
+        log::trace!("transcript {}: {}", label, self.fingerprint());
+
+        self.absorbed
+            .extend_from_slice(&(label.len() as u64).to_le_bytes());
+        self.absorbed.extend_from_slice(label.as_bytes());
+    }
+
+    /// The first block of output is the digest, the next ones are H(digest || i), so a
+    /// single challenge costs one hash and longer outputs one more hash per block.
+    fn squeeze_bytes(&mut self, out: &mut [u8]) {
         self.hash_to_digest();
-        assert!(F::SIZE <= H::DIGEST_SIZE);
-        F::from_uniform_bytes(&self.digest.clone().try_into().unwrap())
+
+        #[cfg(feature = "trace")]
+        log::trace!(
+            "transcript squeeze {} bytes: {}",
+            out.len(),
+            self.fingerprint()
+        );
+
+        let mut input = self.digest.clone();
+        input.extend_from_slice(&0u64.to_le_bytes());
+        let mut block = vec![0u8; H::DIGEST_SIZE];
+        for (i, chunk) in out.chunks_mut(H::DIGEST_SIZE).enumerate() {
+            if i == 0 {
+                block.copy_from_slice(&self.digest);
+            } else {
+                input[H::DIGEST_SIZE..].copy_from_slice(&(i as u64).to_le_bytes());
+                H::hash(&mut block, &input);
+            }
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
     }
 }
 
 impl<H: FiatShamirHash> TranscriptInstance<H> {
-    /// Hash the input into the output.
+    /// Hash the absorbed bytes into the digest, chained with the previous digest.
     pub fn hash_to_digest(&mut self) {
-        let hash_end_index = self.proof.bytes.len();
-        if hash_end_index > self.hash_start_index {
-            H::hash(
-                &mut self.digest,
-                &self.proof.bytes[self.hash_start_index..hash_end_index],
-            );
-            self.hash_start_index = hash_end_index;
+        if !self.absorbed.is_empty() {
+            let input = [self.digest.as_slice(), self.absorbed.as_slice()].concat();
+            H::hash(&mut self.digest, &input);
+            self.absorbed.clear();
         } else {
             H::hash_inplace(&mut self.digest);
         }
     }
+
+    /// A hash of the whole state, without updating it.
+    #[cfg(feature = "trace")]
+    fn fingerprint(&self) -> String {
+        let mut output = vec![0u8; H::DIGEST_SIZE];
+        H::hash(
+            &mut output,
+            &[self.digest.as_slice(), self.absorbed.as_slice()].concat(),
+        );
+        output.iter().map(|b| format!("{:02x}", b)).collect()
+    }
 }